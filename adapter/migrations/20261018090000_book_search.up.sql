@@ -0,0 +1,6 @@
+-- 日本語の書名でも部分一致検索ができるよう、分かち書きに依存しない
+-- トライグラム（n-gram）インデックスを検索対象の列を連結した式に張る。
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS books_search_trgm_idx
+    ON books USING gin ((title || ' ' || author || ' ' || isbn || ' ' || description) gin_trgm_ops);
@@ -0,0 +1,2 @@
+-- 正規化前の表記は保持していないため、ISBN を元に戻す操作は行わない。
+DROP TABLE IF EXISTS invalid_isbns;
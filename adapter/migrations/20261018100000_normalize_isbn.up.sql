@@ -0,0 +1,54 @@
+-- 形式やチェックディジットが正しくない ISBN は正規化できないため、書き換える前の値を記録しておく。
+-- 記録した蔵書は、ISBN を修正（更新）したうえで invalid_isbns から削除する。
+CREATE TABLE IF NOT EXISTS invalid_isbns
+(
+    book_id     UUID                        NOT NULL PRIMARY KEY,
+    isbn        VARCHAR(255)                NOT NULL,
+    detected_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+INSERT INTO invalid_isbns (book_id, isbn)
+SELECT b.book_id, b.isbn
+FROM books AS b
+CROSS JOIN LATERAL (SELECT upper(regexp_replace(b.isbn, '[- ]', '', 'g')) AS digits) AS n
+WHERE NOT (
+    -- ISBN-13: 978 または 979 で始まり、重みを 1, 3 と交互に掛けた和が 10 の倍数
+    (n.digits ~ '^97[89][0-9]{10}$'
+        AND (SELECT sum(substr(n.digits, i, 1)::int * CASE WHEN i % 2 = 1 THEN 1 ELSE 3 END)
+             FROM generate_series(1, 13) AS i) % 10 = 0)
+    -- ISBN-10: 重みを 10 から 1 まで掛けた和が 11 の倍数（チェックディジットの X は 10）
+    OR (n.digits ~ '^[0-9]{9}[0-9X]$'
+        AND (SELECT sum(CASE WHEN substr(n.digits, i, 1) = 'X' THEN 10
+                             ELSE substr(n.digits, i, 1)::int END * (11 - i))
+             FROM generate_series(1, 10) AS i) % 11 = 0)
+);
+
+DO $$
+DECLARE
+    invalid_count BIGINT;
+BEGIN
+    SELECT count(*) INTO invalid_count FROM invalid_isbns;
+    IF invalid_count > 0 THEN
+        RAISE WARNING '% 件の蔵書の ISBN が正しくないため正規化していません。invalid_isbns を確認してください。', invalid_count;
+    END IF;
+END
+$$;
+
+-- 登録済みの ISBN を、アプリケーションで扱う形式（数字のみの ISBN-13）に揃える。
+UPDATE books
+SET isbn = regexp_replace(isbn, '[- ]', '', 'g')
+WHERE book_id NOT IN (SELECT book_id FROM invalid_isbns);
+
+-- ISBN-10 は先頭に 978 を付け、チェックディジットを計算し直して ISBN-13 に変換する。
+UPDATE books
+SET isbn = '978' || left(isbn, 9) || (
+    (10 - (SELECT sum(substr('978' || left(books.isbn, 9), i, 1)::int
+                      * CASE WHEN i % 2 = 1 THEN 1 ELSE 3 END)
+           FROM generate_series(1, 12) AS i) % 10) % 10
+)::text
+WHERE isbn ~ '^[0-9]{9}[0-9Xx]$'
+  AND book_id NOT IN (SELECT book_id FROM invalid_isbns);
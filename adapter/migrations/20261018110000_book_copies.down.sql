@@ -0,0 +1,18 @@
+ALTER TABLE returned_checkouts
+    DROP COLUMN IF EXISTS copy_id;
+
+-- 同じ蔵書に対する複数の貸出は、最も古いものだけを残す。
+DELETE FROM checkouts AS c
+USING checkouts AS other
+WHERE c.book_id = other.book_id
+  AND (c.checked_out_at, c.checkout_id) > (other.checked_out_at, other.checkout_id);
+
+DROP INDEX IF EXISTS checkouts_book_id_idx;
+
+ALTER TABLE checkouts
+    DROP COLUMN IF EXISTS copy_id,
+    ADD CONSTRAINT checkouts_book_id_key UNIQUE (book_id);
+
+DROP TRIGGER IF EXISTS book_copies_updated_at_trigger ON book_copies;
+DROP TABLE IF EXISTS book_copies;
+DROP SEQUENCE IF EXISTS book_copy_barcode_seq;
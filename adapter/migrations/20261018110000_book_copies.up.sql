@@ -0,0 +1,58 @@
+-- 蔵書（書誌情報）と、貸出の対象となる現物（複本）を分けて管理する。
+CREATE SEQUENCE IF NOT EXISTS book_copy_barcode_seq;
+
+CREATE TABLE IF NOT EXISTS book_copies
+(
+    copy_id    UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    book_id    UUID                        NOT NULL,
+    barcode    VARCHAR(255)                NOT NULL UNIQUE
+        DEFAULT ('BK' || lpad(nextval('book_copy_barcode_seq')::text, 8, '0')),
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_copies_book_id_idx ON book_copies (book_id);
+
+CREATE TRIGGER book_copies_updated_at_trigger
+    BEFORE UPDATE
+    ON book_copies
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at();
+
+-- 既存の蔵書には、それぞれ 1 冊ずつ現物があるものとする。
+INSERT INTO book_copies (book_id)
+SELECT book_id
+FROM books
+ORDER BY created_at;
+
+-- 貸出は蔵書ではなく現物に対して行う。
+ALTER TABLE checkouts
+    ADD COLUMN copy_id UUID;
+
+UPDATE checkouts AS c
+SET copy_id = bc.copy_id
+FROM book_copies AS bc
+WHERE bc.book_id = c.book_id;
+
+ALTER TABLE checkouts
+    ALTER COLUMN copy_id SET NOT NULL,
+    ADD CONSTRAINT checkouts_copy_id_key UNIQUE (copy_id),
+    ADD CONSTRAINT checkouts_copy_id_fkey FOREIGN KEY (copy_id) REFERENCES book_copies (copy_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    DROP CONSTRAINT checkouts_book_id_key;
+
+CREATE INDEX IF NOT EXISTS checkouts_book_id_idx ON checkouts (book_id);
+
+-- 複本管理の導入前に返却された貸出にも、移行した現物を紐づけておく。
+ALTER TABLE returned_checkouts
+    ADD COLUMN copy_id UUID;
+
+UPDATE returned_checkouts AS rc
+SET copy_id = bc.copy_id
+FROM book_copies AS bc
+WHERE bc.book_id = rc.book_id;
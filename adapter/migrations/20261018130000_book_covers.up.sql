@@ -0,0 +1,22 @@
+-- 蔵書の表紙画像。画像そのものは BlobStore に保存し、ここでは形式と更新日時を管理する。
+-- 画像は更新のたびに blob_id を変えた別のキーに保存し、コミット後に古い画像を削除する。
+CREATE TABLE IF NOT EXISTS book_covers
+(
+    book_id      UUID PRIMARY KEY,
+    blob_id      UUID                        NOT NULL,
+    content_type VARCHAR(32)                 NOT NULL,
+    width        INTEGER                     NOT NULL,
+    height       INTEGER                     NOT NULL,
+    created_at   TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+    updated_at   TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE TRIGGER book_covers_updated_at_trigger
+    BEFORE UPDATE
+    ON book_covers
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at();
@@ -0,0 +1,4 @@
+-- 除籍（論理削除）した日時。NULL の場合は除籍されていない
+ALTER TABLE books ADD COLUMN deleted_at TIMESTAMP(3) WITH TIME ZONE;
+
+CREATE INDEX books_deleted_at_idx ON books (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,2 @@
+-- 楽観的排他制御のための版数。蔵書の情報を更新するたびに 1 ずつ増やす
+ALTER TABLE books ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
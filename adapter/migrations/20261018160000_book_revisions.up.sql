@@ -0,0 +1,34 @@
+-- 蔵書の変更履歴。蔵書の登録・更新・除籍・復元・差し戻しのたびに 1 行を記録する。
+-- old_* は変更前の値で、登録・除籍・復元のように書誌情報を変更しない操作では NULL になる。
+-- new_* は操作後の値。
+-- seq は記録した順に増える通し番号で、同じ時刻に記録した履歴も順序が定まるよう並び替えに使う。
+CREATE TABLE IF NOT EXISTS book_revisions
+(
+    revision_id     UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    seq             BIGINT                      NOT NULL GENERATED ALWAYS AS IDENTITY,
+    book_id         UUID                        NOT NULL,
+    version         BIGINT                      NOT NULL,
+    action          VARCHAR(16)                 NOT NULL,
+    user_id         UUID                        NOT NULL,
+    old_title       VARCHAR(255),
+    old_author      VARCHAR(255),
+    old_isbn        VARCHAR(255),
+    old_description VARCHAR(1024),
+    new_title       VARCHAR(255)                NOT NULL,
+    new_author      VARCHAR(255)                NOT NULL,
+    new_isbn        VARCHAR(255)                NOT NULL,
+    new_description VARCHAR(1024)               NOT NULL,
+    created_at      TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_revisions_book_id_idx ON book_revisions (book_id, seq);
+
+-- 既存の蔵書は、現在の内容を所有者が登録したものとして履歴の起点にする
+INSERT INTO book_revisions
+    (book_id, version, action, user_id, new_title, new_author, new_isbn, new_description, created_at)
+SELECT book_id, version, 'create', user_id, title, author, isbn, description, created_at
+FROM books;
@@ -0,0 +1,13 @@
+-- 管理者が所有者以外の蔵書を操作した記録。
+-- 蔵書を完全に削除した後も残すため、蔵書への外部キーは設けない。
+CREATE TABLE IF NOT EXISTS book_audit_logs
+(
+    audit_log_id UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    book_id      UUID                        NOT NULL,
+    owner_id     UUID                        NOT NULL,
+    user_id      UUID                        NOT NULL,
+    action       VARCHAR(16)                 NOT NULL,
+    created_at   TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3)
+);
+
+CREATE INDEX IF NOT EXISTS book_audit_logs_book_id_idx ON book_audit_logs (book_id, created_at);
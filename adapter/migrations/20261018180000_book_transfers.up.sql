@@ -0,0 +1,17 @@
+-- 蔵書の所有者の譲渡履歴。user_id は譲渡を行ったユーザー。
+-- 譲渡元・譲渡先のユーザーが削除された後も残すため、ユーザーへの外部キーは設けない。
+CREATE TABLE IF NOT EXISTS book_transfers
+(
+    transfer_id  UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    book_id      UUID                        NOT NULL,
+    from_user_id UUID                        NOT NULL,
+    to_user_id   UUID                        NOT NULL,
+    user_id      UUID                        NOT NULL,
+    created_at   TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_transfers_book_id_idx ON book_transfers (book_id, created_at);
@@ -0,0 +1,25 @@
+-- 蔵書のレビュー。1 人のユーザーが 1 冊の蔵書に書けるレビューは 1 件まで。
+CREATE TABLE IF NOT EXISTS book_reviews
+(
+    review_id  UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    book_id    UUID                        NOT NULL,
+    user_id    UUID                        NOT NULL,
+    rating     SMALLINT                    NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    comment    VARCHAR(2048)               NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    UNIQUE (book_id, user_id),
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE TRIGGER book_reviews_updated_at_trigger
+    BEFORE UPDATE
+    ON book_reviews
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at();
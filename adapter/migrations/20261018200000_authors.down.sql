@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS split_author_names(TEXT);
+DROP TABLE IF EXISTS book_authors;
+DROP TRIGGER IF EXISTS authors_updated_at_trigger ON authors;
+DROP TABLE IF EXISTS authors;
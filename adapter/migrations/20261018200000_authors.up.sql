@@ -0,0 +1,67 @@
+-- 蔵書の著者。名前は大文字・小文字を区別せずに一意とする。
+CREATE TABLE IF NOT EXISTS authors
+(
+    author_id  UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    name       VARCHAR(255)                NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS authors_name_key ON authors (lower(name));
+
+CREATE TRIGGER authors_updated_at_trigger
+    BEFORE UPDATE
+    ON authors
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at();
+
+-- 蔵書と著者の関連。同じ著者が 1 冊の蔵書に複数の役割で関わることもある。
+-- position は蔵書の著者表記での並び順を表す。
+CREATE TABLE IF NOT EXISTS book_authors
+(
+    book_id    UUID                        NOT NULL,
+    author_id  UUID                        NOT NULL,
+    role       VARCHAR(16)                 NOT NULL CHECK (role IN ('author', 'translator', 'editor')),
+    position   INTEGER                     NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    PRIMARY KEY (book_id, author_id, role),
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors (author_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_authors_author_id_idx ON book_authors (author_id);
+
+-- 著者表記の文字列を、区切り文字（、 ， ; ；）で個々の著者名に分割する。
+-- 「Blandy, Jim」「Procter & Gamble」「AC/DC」のように , / & and は名前の一部にもなるため、区切りとみなさない。
+-- 分割しきれなかった表記は 1 人の著者として登録し、著者の統合で手作業で整理する。
+-- 前後の空白と空の名前は除き、表記での並び順（1 始まり）を ord として返す。
+CREATE OR REPLACE FUNCTION split_author_names(notation TEXT)
+    RETURNS TABLE (name TEXT, ord INTEGER)
+    LANGUAGE sql
+    IMMUTABLE
+AS
+$$
+SELECT btrim(s.name), (row_number() OVER (ORDER BY s.n))::integer
+FROM regexp_split_to_table(notation, '(、|，|;|；)') WITH ORDINALITY AS s(name, n)
+WHERE btrim(s.name) <> ''
+$$;
+
+-- 既存の蔵書の著者表記を分割し、著者として登録する。表記の揺れは大文字・小文字の違いのみ同一視する。
+INSERT INTO authors (name)
+SELECT DISTINCT ON (lower(s.name)) s.name
+FROM books AS b
+CROSS JOIN LATERAL split_author_names(b.author) AS s
+ORDER BY lower(s.name), s.name
+ON CONFLICT DO NOTHING;
+
+INSERT INTO book_authors (book_id, author_id, role, position)
+SELECT b.book_id, a.author_id, 'author', MIN(s.ord)
+FROM books AS b
+CROSS JOIN LATERAL split_author_names(b.author) AS s
+INNER JOIN authors AS a ON lower(a.name) = lower(s.name)
+GROUP BY b.book_id, a.author_id;
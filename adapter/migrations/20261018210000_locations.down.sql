@@ -0,0 +1,6 @@
+ALTER TABLE returned_checkouts DROP COLUMN IF EXISTS returned_location_id;
+ALTER TABLE book_copies DROP COLUMN IF EXISTS current_location_id;
+ALTER TABLE books DROP COLUMN IF EXISTS location_id;
+DROP FUNCTION IF EXISTS location_branch(UUID);
+DROP TRIGGER IF EXISTS locations_updated_at_trigger ON locations;
+DROP TABLE IF EXISTS locations;
@@ -0,0 +1,61 @@
+-- 蔵書を配架する場所。拠点 (branch) → 部屋 (room) → 書架 (shelf) の階層になっている。
+-- 拠点は親を持たず、部屋の親は拠点、書架の親は部屋とする。名前は同じ親の中で大文字・小文字を区別せずに一意とする。
+CREATE TABLE IF NOT EXISTS locations
+(
+    location_id UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    parent_id   UUID,
+    kind        VARCHAR(16)                 NOT NULL CHECK (kind IN ('branch', 'room', 'shelf')),
+    name        VARCHAR(255)                NOT NULL,
+    created_at  TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+    updated_at  TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    CHECK ((kind = 'branch') = (parent_id IS NULL)),
+    FOREIGN KEY (parent_id) REFERENCES locations (location_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS locations_name_key ON locations (parent_id, lower(name)) NULLS NOT DISTINCT;
+
+CREATE TRIGGER locations_updated_at_trigger
+    BEFORE UPDATE
+    ON locations
+    FOR EACH ROW
+EXECUTE PROCEDURE set_updated_at();
+
+-- 場所が属する拠点を返す。
+CREATE OR REPLACE FUNCTION location_branch(location UUID)
+    RETURNS UUID
+    LANGUAGE sql
+    STABLE
+AS
+$$
+WITH RECURSIVE ancestors AS (
+    SELECT location_id, parent_id FROM locations WHERE location_id = location
+    UNION ALL
+    SELECT l.location_id, l.parent_id
+    FROM locations AS l
+    INNER JOIN ancestors AS a ON l.location_id = a.parent_id
+)
+SELECT location_id FROM ancestors WHERE parent_id IS NULL
+$$;
+
+-- 蔵書の配架場所（書架）
+ALTER TABLE books
+    ADD COLUMN IF NOT EXISTS location_id UUID REFERENCES locations (location_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS books_location_id_idx ON books (location_id);
+
+-- 配架場所と異なる拠点で返却され、配架場所へ戻す途中の現物の現在の場所。配架済みの場合は NULL
+ALTER TABLE book_copies
+    ADD COLUMN IF NOT EXISTS current_location_id UUID REFERENCES locations (location_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL;
+
+-- 返却された場所
+ALTER TABLE returned_checkouts
+    ADD COLUMN IF NOT EXISTS returned_location_id UUID REFERENCES locations (location_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL;
@@ -0,0 +1,6 @@
+ALTER TABLE books
+    DROP COLUMN IF EXISTS in_repair_since;
+
+ALTER TABLE returned_checkouts
+    DROP COLUMN IF EXISTS condition_note,
+    DROP COLUMN IF EXISTS condition;
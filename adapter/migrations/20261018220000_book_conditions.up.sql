@@ -0,0 +1,9 @@
+-- 返却時に記録する蔵書の状態
+ALTER TABLE returned_checkouts
+    ADD COLUMN IF NOT EXISTS condition VARCHAR(16)
+        CHECK (condition IN ('good', 'worn', 'damaged')),
+    ADD COLUMN IF NOT EXISTS condition_note TEXT;
+
+-- 修理中にした日時。修理中の蔵書は貸し出さない
+ALTER TABLE books
+    ADD COLUMN IF NOT EXISTS in_repair_since TIMESTAMP(3) WITH TIME ZONE;
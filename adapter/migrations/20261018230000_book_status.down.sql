@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS book_status_histories;
+
+ALTER TABLE books
+    ADD COLUMN IF NOT EXISTS in_repair_since TIMESTAMP(3) WITH TIME ZONE;
+
+UPDATE books
+SET in_repair_since = COALESCE(status_changed_at, CURRENT_TIMESTAMP(3))
+WHERE status = 'in_repair';
+
+ALTER TABLE books
+    DROP COLUMN IF EXISTS status_changed_at,
+    DROP COLUMN IF EXISTS status;
@@ -0,0 +1,33 @@
+-- 蔵書の状態。貸出できるのは available の蔵書のみ
+ALTER TABLE books
+    ADD COLUMN IF NOT EXISTS status VARCHAR(16) NOT NULL DEFAULT 'available'
+        CHECK (status IN ('available', 'in_repair', 'lost', 'withdrawn')),
+    ADD COLUMN IF NOT EXISTS status_changed_at TIMESTAMP(3) WITH TIME ZONE;
+
+-- 修理中の印は状態に置き換える
+UPDATE books
+SET status = 'in_repair', status_changed_at = in_repair_since
+WHERE in_repair_since IS NOT NULL;
+
+ALTER TABLE books
+    DROP COLUMN IF EXISTS in_repair_since;
+
+-- 蔵書の状態の変更履歴。user_id は変更を行ったユーザー。
+-- 変更したユーザーが削除された後も残すため、ユーザーへの外部キーは設けない。
+CREATE TABLE IF NOT EXISTS book_status_histories
+(
+    status_history_id UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    book_id           UUID                        NOT NULL,
+    from_status       VARCHAR(16)                 NOT NULL,
+    to_status         VARCHAR(16)                 NOT NULL,
+    reason            TEXT,
+    user_id           UUID                        NOT NULL,
+    created_at        TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_status_histories_book_id_idx
+    ON book_status_histories (book_id, created_at);
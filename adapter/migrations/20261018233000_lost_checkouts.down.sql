@@ -0,0 +1,7 @@
+ALTER TABLE book_copies
+    DROP COLUMN IF EXISTS lost_at;
+
+DROP TABLE IF EXISTS replacement_charges;
+
+ALTER TABLE returned_checkouts
+    DROP COLUMN IF EXISTS outcome;
@@ -0,0 +1,28 @@
+-- 貸出の終わり方。lost は借りたユーザーが紛失したことを表す
+ALTER TABLE returned_checkouts
+    ADD COLUMN IF NOT EXISTS outcome VARCHAR(16) NOT NULL DEFAULT 'returned'
+        CHECK (outcome IN ('returned', 'lost'));
+
+-- 紛失した蔵書の弁償金。user_id は弁償を求めるユーザー（借りたユーザー）、charged_by は記録したユーザー。
+-- ユーザーが削除された後も残すため、ユーザーへの外部キーは設けない。
+CREATE TABLE IF NOT EXISTS replacement_charges
+(
+    charge_id   UUID PRIMARY KEY                     DEFAULT gen_random_uuid(),
+    checkout_id UUID                        NOT NULL UNIQUE,
+    book_id     UUID                        NOT NULL,
+    user_id     UUID                        NOT NULL,
+    amount      BIGINT                      NOT NULL CHECK (amount > 0),
+    charged_by  UUID                        NOT NULL,
+    created_at  TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS replacement_charges_user_id_idx ON replacement_charges (user_id, created_at);
+
+-- 紛失は現物ごとに記録する。lost_at が設定された現物は、見つかるまで貸し出さない。
+-- 蔵書全体が紛失の状態になるのは、貸し出せる現物が 1 冊も残っていない場合のみ。
+ALTER TABLE book_copies
+    ADD COLUMN IF NOT EXISTS lost_at TIMESTAMP(3) WITH TIME ZONE;
@@ -0,0 +1,19 @@
+-- 「この本を借りた人はこんな本も借りています」の集計結果。
+-- 貸出履歴（貸出中・返却済み）から定期的に作り直す。borrower_count は両方の蔵書を借りたユーザーの数。
+CREATE TABLE IF NOT EXISTS book_co_borrows
+(
+    book_id         UUID                        NOT NULL,
+    related_book_id UUID                        NOT NULL,
+    borrower_count  BIGINT                      NOT NULL CHECK (borrower_count > 0),
+    refreshed_at    TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT current_timestamp(3),
+
+    PRIMARY KEY (book_id, related_book_id),
+    FOREIGN KEY (book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (related_book_id) REFERENCES books (book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_co_borrows_related_book_id_idx ON book_co_borrows (related_book_id);
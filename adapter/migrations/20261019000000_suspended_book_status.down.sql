@@ -0,0 +1,10 @@
+ALTER TABLE books
+    DROP CONSTRAINT IF EXISTS books_status_check;
+
+UPDATE books SET status = 'withdrawn' WHERE status = 'suspended';
+UPDATE book_status_histories SET from_status = 'withdrawn' WHERE from_status = 'suspended';
+UPDATE book_status_histories SET to_status = 'withdrawn' WHERE to_status = 'suspended';
+
+ALTER TABLE books
+    ADD CONSTRAINT books_status_check
+        CHECK (status IN ('available', 'in_repair', 'lost', 'withdrawn'));
@@ -0,0 +1,11 @@
+-- 貸出停止の状態は除籍（deleted_at）と区別するため、withdrawn から suspended に名前を変える。
+ALTER TABLE books
+    DROP CONSTRAINT IF EXISTS books_status_check;
+
+UPDATE books SET status = 'suspended' WHERE status = 'withdrawn';
+UPDATE book_status_histories SET from_status = 'suspended' WHERE from_status = 'withdrawn';
+UPDATE book_status_histories SET to_status = 'suspended' WHERE to_status = 'withdrawn';
+
+ALTER TABLE books
+    ADD CONSTRAINT books_status_check
+        CHECK (status IN ('available', 'in_repair', 'lost', 'suspended'));
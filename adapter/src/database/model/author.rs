@@ -0,0 +1,71 @@
+use kernel::model::author::{Author, AuthorRole, AuthoredBook, BookAuthor};
+use kernel::model::id::{AuthorId, BookId};
+use shared::error::AppError;
+use std::str::FromStr;
+
+pub struct AuthorRow {
+    pub author_id: AuthorId,
+    pub name: String,
+}
+
+impl From<AuthorRow> for Author {
+    fn from(value: AuthorRow) -> Self {
+        let AuthorRow { author_id, name } = value;
+        Author {
+            id: author_id,
+            name,
+        }
+    }
+}
+
+pub struct BookAuthorRow {
+    pub book_id: BookId,
+    pub author_id: AuthorId,
+    pub name: String,
+    pub role: String,
+}
+
+impl TryFrom<BookAuthorRow> for BookAuthor {
+    type Error = AppError;
+
+    fn try_from(value: BookAuthorRow) -> Result<Self, Self::Error> {
+        let BookAuthorRow {
+            author_id,
+            name,
+            role,
+            ..
+        } = value;
+        Ok(BookAuthor {
+            id: author_id,
+            name,
+            role: parse_role(&role)?,
+        })
+    }
+}
+
+pub struct AuthoredBookRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub role: String,
+}
+
+impl TryFrom<AuthoredBookRow> for AuthoredBook {
+    type Error = AppError;
+
+    fn try_from(value: AuthoredBookRow) -> Result<Self, Self::Error> {
+        let AuthoredBookRow {
+            book_id,
+            title,
+            role,
+        } = value;
+        Ok(AuthoredBook {
+            book_id,
+            title,
+            role: parse_role(&role)?,
+        })
+    }
+}
+
+fn parse_role(role: &str) -> Result<AuthorRole, AppError> {
+    AuthorRole::from_str(role).map_err(|e| AppError::ConversionEntityError(e.to_string()))
+}
@@ -1,21 +1,57 @@
+use super::location::LocationRow;
 use chrono::{DateTime, Utc};
-use kernel::model::book::{Book, Checkout};
-use kernel::model::id::{BookId, CheckoutId, UserId};
+use kernel::model::author::BookAuthor;
+use kernel::model::book::revision::{
+    BookAuditLog, BookRevision, BookRevisionAction, BookRevisionFields, BookRevisionUser,
+};
+use kernel::model::book::{Book, BookCopy, BookCover, BookStatus, BookStatusChange, Checkout};
+use kernel::model::id::{
+    BookAuditLogId, BookCopyId, BookId, BookRevisionId, CheckoutId, LocationId, UserId,
+};
+use kernel::model::isbn::Isbn;
+use kernel::model::location::{BookLocation, Location};
+use kernel::model::tag::Tag;
 use kernel::model::user::{BookOwner, CheckoutUser};
+use shared::error::AppError;
+use std::str::FromStr;
 
 pub struct BookRow {
     pub book_id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
 
     pub owned_by: UserId,
     pub owner_name: String,
+
+    pub total_copies: i64,
+    pub available_copies: i64,
+
+    pub cover_content_type: Option<String>,
+    pub cover_updated_at: Option<DateTime<Utc>>,
+
+    pub location_id: Option<LocationId>,
+    pub location_branch: Option<String>,
+    pub location_room: Option<String>,
+    pub location_shelf: Option<String>,
+
+    pub status: String,
+    pub status_changed_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i64,
+
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
 }
 
 impl BookRow {
-    pub fn into_book(self, checkout: Option<Checkout>) -> Book {
+    pub fn into_book(
+        self,
+        authors: Vec<BookAuthor>,
+        checkouts: Vec<Checkout>,
+        tags: Vec<Tag>,
+    ) -> Result<Book, AppError> {
         let BookRow {
             book_id,
             title,
@@ -24,30 +60,129 @@ impl BookRow {
             description,
             owned_by,
             owner_name,
+            total_copies,
+            available_copies,
+            cover_content_type,
+            cover_updated_at,
+            location_id,
+            location_branch,
+            location_room,
+            location_shelf,
+            status,
+            status_changed_at,
+            deleted_at,
+            version,
+            average_rating,
+            review_count,
         } = self;
-        Book {
+        let cover = cover_content_type
+            .zip(cover_updated_at)
+            .map(|(content_type, updated_at)| BookCover {
+                content_type,
+                updated_at,
+            });
+        let location = match (location_id, location_branch, location_room, location_shelf) {
+            (Some(id), Some(branch), Some(room), Some(shelf)) => Some(BookLocation {
+                id,
+                branch,
+                room,
+                shelf,
+            }),
+            _ => None,
+        };
+        Ok(Book {
             id: book_id,
             title,
             author,
+            authors,
             isbn,
             description,
             owner: BookOwner {
                 id: owned_by,
                 name: owner_name,
             },
-            checkout,
-        }
+            total_copies,
+            available_copies,
+            checkouts,
+            tags,
+            cover,
+            location,
+            status: BookStatus::from_str(&status)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            status_changed_at,
+            deleted_at,
+            version,
+            average_rating,
+            review_count,
+        })
     }
 }
 
+#[derive(sqlx::FromRow)]
 pub struct PaginatedBookRow {
     pub total: i64,
     pub id: BookId,
 }
 
+/// カーソル方式のページングで取得する、蔵書 ID と並び替えの基準の値の文字列表現
+#[derive(sqlx::FromRow)]
+pub struct BookCursorRow {
+    pub id: BookId,
+    pub sort_value: String,
+}
+
+pub struct BookCopyRow {
+    pub copy_id: BookCopyId,
+    pub book_id: BookId,
+    pub barcode: String,
+    pub current_location_id: Option<LocationId>,
+    pub current_location_parent_id: Option<LocationId>,
+    pub current_location_kind: Option<String>,
+    pub current_location_name: Option<String>,
+    pub lost_at: Option<DateTime<Utc>>,
+}
+
+impl BookCopyRow {
+    pub fn into_copy(self, checkout: Option<Checkout>) -> Result<BookCopy, AppError> {
+        let BookCopyRow {
+            copy_id,
+            book_id,
+            barcode,
+            current_location_id,
+            current_location_parent_id,
+            current_location_kind,
+            current_location_name,
+            lost_at,
+        } = self;
+        let in_transit_at = match (
+            current_location_id,
+            current_location_kind,
+            current_location_name,
+        ) {
+            (Some(location_id), Some(kind), Some(name)) => Some(Location::try_from(LocationRow {
+                location_id,
+                parent_id: current_location_parent_id,
+                kind,
+                name,
+            })?),
+            _ => None,
+        };
+        Ok(BookCopy {
+            id: copy_id,
+            book_id,
+            barcode,
+            checkout,
+            in_transit_at,
+            lost_at,
+        })
+    }
+}
+
 pub struct BookCheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: BookCopyId,
+    pub barcode: String,
     pub user_id: UserId,
     pub user_name: String,
     pub checked_out_at: DateTime<Utc>,
@@ -58,12 +193,16 @@ impl From<BookCheckoutRow> for Checkout {
         let BookCheckoutRow {
             checkout_id,
             book_id: _,
+            copy_id,
+            barcode,
             user_id,
             user_name,
             checked_out_at,
         } = value;
         Self {
             checkout_id,
+            copy_id,
+            barcode,
             checked_out_by: CheckoutUser {
                 id: user_id,
                 name: user_name,
@@ -72,3 +211,158 @@ impl From<BookCheckoutRow> for Checkout {
         }
     }
 }
+
+/// 変更履歴に記録する蔵書の書誌情報
+pub struct BookFieldsRow {
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    pub description: String,
+}
+
+pub struct BookRevisionRow {
+    pub revision_id: BookRevisionId,
+    pub book_id: BookId,
+    pub version: i64,
+    pub action: String,
+    pub user_id: UserId,
+    pub user_name: Option<String>,
+    pub old_title: Option<String>,
+    pub old_author: Option<String>,
+    pub old_isbn: Option<Isbn>,
+    pub old_description: Option<String>,
+    pub new_title: String,
+    pub new_author: String,
+    pub new_isbn: Isbn,
+    pub new_description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BookRevisionRow> for BookRevision {
+    type Error = AppError;
+
+    fn try_from(value: BookRevisionRow) -> Result<Self, Self::Error> {
+        let BookRevisionRow {
+            revision_id,
+            book_id,
+            version,
+            action,
+            user_id,
+            user_name,
+            old_title,
+            old_author,
+            old_isbn,
+            old_description,
+            new_title,
+            new_author,
+            new_isbn,
+            new_description,
+            created_at,
+        } = value;
+        let old = match (old_title, old_author, old_isbn, old_description) {
+            (Some(title), Some(author), Some(isbn), Some(description)) => {
+                Some(BookRevisionFields {
+                    title,
+                    author,
+                    isbn,
+                    description,
+                })
+            }
+            _ => None,
+        };
+        Ok(BookRevision {
+            id: revision_id,
+            book_id,
+            version,
+            action: BookRevisionAction::from_str(&action)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            changed_by: BookRevisionUser {
+                id: user_id,
+                name: user_name,
+            },
+            old,
+            new: BookRevisionFields {
+                title: new_title,
+                author: new_author,
+                isbn: new_isbn,
+                description: new_description,
+            },
+            created_at,
+        })
+    }
+}
+
+pub struct BookAuditLogRow {
+    pub audit_log_id: BookAuditLogId,
+    pub book_id: BookId,
+    pub owner_id: UserId,
+    pub user_id: UserId,
+    pub user_name: Option<String>,
+    pub action: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BookAuditLogRow> for BookAuditLog {
+    type Error = AppError;
+
+    fn try_from(value: BookAuditLogRow) -> Result<Self, Self::Error> {
+        let BookAuditLogRow {
+            audit_log_id,
+            book_id,
+            owner_id,
+            user_id,
+            user_name,
+            action,
+            created_at,
+        } = value;
+        Ok(BookAuditLog {
+            id: audit_log_id,
+            book_id,
+            owner_id,
+            operated_by: BookRevisionUser {
+                id: user_id,
+                name: user_name,
+            },
+            action: BookRevisionAction::from_str(&action)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            created_at,
+        })
+    }
+}
+
+pub struct BookStatusHistoryRow {
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    pub user_id: UserId,
+    pub user_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BookStatusHistoryRow> for BookStatusChange {
+    type Error = AppError;
+
+    fn try_from(value: BookStatusHistoryRow) -> Result<Self, Self::Error> {
+        let BookStatusHistoryRow {
+            from_status,
+            to_status,
+            reason,
+            user_id,
+            user_name,
+            created_at,
+        } = value;
+        let parse = |status: &str| {
+            BookStatus::from_str(status).map_err(|e| AppError::ConversionEntityError(e.to_string()))
+        };
+        Ok(BookStatusChange {
+            from: parse(&from_status)?,
+            to: parse(&to_status)?,
+            reason,
+            changed_by: BookRevisionUser {
+                id: user_id,
+                name: user_name,
+            },
+            changed_at: created_at,
+        })
+    }
+}
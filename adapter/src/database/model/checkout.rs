@@ -1,6 +1,11 @@
 use chrono::{DateTime, Utc};
-use kernel::model::checkout::{Checkout, CheckoutBook};
-use kernel::model::id::{BookId, CheckoutId, UserId};
+use kernel::model::checkout::{
+    BookCondition, BookConditionReport, Checkout, CheckoutBook, CheckoutOutcome,
+};
+use kernel::model::id::{BookCopyId, BookId, CheckoutId, UserId};
+use kernel::model::isbn::Isbn;
+use shared::error::AppError;
+use std::str::FromStr;
 
 pub struct CheckoutStateRow {
     pub book_id: BookId,
@@ -8,14 +13,23 @@ pub struct CheckoutStateRow {
     pub user_id: Option<UserId>,
 }
 
+pub struct AvailableCopyRow {
+    pub book_id: BookId,
+    pub copy_id: Option<BookCopyId>,
+    /// 貸出を申請したユーザーが、同じ蔵書の現物をすでに借りているか
+    pub already_checked_out: bool,
+    pub status: String,
+}
+
 pub struct CheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: BookCopyId,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
 }
 
 impl From<CheckoutRow> for Checkout {
@@ -23,6 +37,7 @@ impl From<CheckoutRow> for Checkout {
         let CheckoutRow {
             checkout_id,
             book_id,
+            copy_id,
             user_id,
             checked_out_at,
             title,
@@ -31,9 +46,12 @@ impl From<CheckoutRow> for Checkout {
         } = value;
         Self {
             id: checkout_id,
+            copy_id: Some(copy_id),
             checked_out_by: user_id,
             checked_out_at,
             returned_at: None,
+            outcome: None,
+            replacement_charge: None,
             book: CheckoutBook {
                 book_id,
                 title,
@@ -44,40 +62,89 @@ impl From<CheckoutRow> for Checkout {
     }
 }
 
-pub struct ReturnedCheckoutRow {
+pub struct CheckoutHistoryRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: Option<BookCopyId>,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
-    pub returned_at: DateTime<Utc>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub outcome: Option<String>,
+    pub replacement_charge: Option<i64>,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
 }
 
-impl From<ReturnedCheckoutRow> for Checkout {
-    fn from(value: ReturnedCheckoutRow) -> Self {
-        let ReturnedCheckoutRow {
+impl TryFrom<CheckoutHistoryRow> for Checkout {
+    type Error = AppError;
+
+    fn try_from(value: CheckoutHistoryRow) -> Result<Self, Self::Error> {
+        let CheckoutHistoryRow {
             checkout_id,
             book_id,
+            copy_id,
             user_id,
             checked_out_at,
             returned_at,
+            outcome,
+            replacement_charge,
             title,
             author,
             isbn,
         } = value;
-        Self {
+        Ok(Self {
             id: checkout_id,
+            copy_id,
             checked_out_by: user_id,
             checked_out_at,
-            returned_at: Some(returned_at),
+            returned_at,
+            outcome: outcome
+                .map(|o| {
+                    CheckoutOutcome::from_str(&o)
+                        .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+                })
+                .transpose()?,
+            replacement_charge,
             book: CheckoutBook {
                 book_id,
                 title,
                 author,
                 isbn,
             },
-        }
+        })
+    }
+}
+
+pub struct BookConditionRow {
+    pub checkout_id: CheckoutId,
+    pub copy_id: Option<BookCopyId>,
+    pub user_id: UserId,
+    pub returned_at: DateTime<Utc>,
+    pub condition: String,
+    pub condition_note: Option<String>,
+}
+
+impl TryFrom<BookConditionRow> for BookConditionReport {
+    type Error = AppError;
+
+    fn try_from(value: BookConditionRow) -> Result<Self, Self::Error> {
+        let BookConditionRow {
+            checkout_id,
+            copy_id,
+            user_id,
+            returned_at,
+            condition,
+            condition_note,
+        } = value;
+        Ok(Self {
+            checkout_id,
+            copy_id,
+            reported_by: user_id,
+            reported_at: returned_at,
+            condition: BookCondition::from_str(&condition)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            note: condition_note,
+        })
     }
 }
@@ -0,0 +1,31 @@
+use kernel::model::id::LocationId;
+use kernel::model::location::{Location, LocationKind};
+use shared::error::AppError;
+use std::str::FromStr;
+
+pub struct LocationRow {
+    pub location_id: LocationId,
+    pub parent_id: Option<LocationId>,
+    pub kind: String,
+    pub name: String,
+}
+
+impl TryFrom<LocationRow> for Location {
+    type Error = AppError;
+
+    fn try_from(value: LocationRow) -> Result<Self, Self::Error> {
+        let LocationRow {
+            location_id,
+            parent_id,
+            kind,
+            name,
+        } = value;
+        Ok(Location {
+            id: location_id,
+            parent_id,
+            kind: LocationKind::from_str(&kind)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            name,
+        })
+    }
+}
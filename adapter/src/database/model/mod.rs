@@ -1,4 +1,9 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod location;
+pub mod recommendation;
+pub mod review;
+pub mod tag;
 pub mod user;
@@ -0,0 +1,30 @@
+use kernel::model::id::BookId;
+use kernel::model::isbn::Isbn;
+use kernel::model::recommendation::RecommendedBook;
+
+pub struct RecommendedBookRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    pub score: i64,
+}
+
+impl From<RecommendedBookRow> for RecommendedBook {
+    fn from(value: RecommendedBookRow) -> Self {
+        let RecommendedBookRow {
+            book_id,
+            title,
+            author,
+            isbn,
+            score,
+        } = value;
+        Self {
+            book_id,
+            title,
+            author,
+            isbn,
+            score,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use kernel::model::id::{BookId, TagId};
+use kernel::model::tag::Tag;
+
+pub struct TagRow {
+    pub tag_id: TagId,
+    pub name: String,
+}
+
+impl From<TagRow> for Tag {
+    fn from(value: TagRow) -> Self {
+        let TagRow { tag_id, name } = value;
+        Tag { id: tag_id, name }
+    }
+}
+
+pub struct BookTagRow {
+    pub book_id: BookId,
+    pub tag_id: TagId,
+    pub name: String,
+}
+
+impl From<BookTagRow> for Tag {
+    fn from(value: BookTagRow) -> Self {
+        let BookTagRow { tag_id, name, .. } = value;
+        Tag { id: tag_id, name }
+    }
+}
@@ -0,0 +1,650 @@
+use crate::database::ConnectionPool;
+use crate::database::model::author::{AuthorRow, AuthoredBookRow};
+use crate::database::model::book::BookFieldsRow;
+use crate::repository::book::{authorize_book_owner, insert_revision};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::author::event::{CreateAuthor, MergeAuthor, UpdateAuthor, UpdateBookAuthors};
+use kernel::model::author::{Author, AuthorDetail, AuthoredBook};
+use kernel::model::book::revision::BookRevisionAction;
+use kernel::model::id::{AuthorId, BookId, UserId};
+use kernel::model::isbn::Isbn;
+use kernel::repository::author::AuthorRepository;
+use shared::error::{AppError, AppResult};
+use sqlx::PgConnection;
+use std::collections::HashSet;
+
+#[derive(new)]
+pub struct AuthorRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl AuthorRepository for AuthorRepositoryImpl {
+    async fn create(&self, event: CreateAuthor) -> AppResult<Author> {
+        let row = sqlx::query_as!(
+            AuthorRow,
+            r#"
+                INSERT INTO authors (name)
+                VALUES ($1)
+                RETURNING author_id, name
+            "#,
+            event.name
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(|e| duplicated_name_error(e, &event.name))?;
+
+        Ok(Author::from(row))
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Author>> {
+        let authors = sqlx::query_as!(
+            AuthorRow,
+            r#"
+                SELECT author_id, name
+                FROM authors
+                ORDER BY lower(name)
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Author::from)
+        .collect();
+
+        Ok(authors)
+    }
+
+    async fn find_by_id(&self, author_id: AuthorId) -> AppResult<Option<AuthorDetail>> {
+        let Some(author) = sqlx::query_as!(
+            AuthorRow,
+            r#"
+                SELECT author_id, name
+                FROM authors
+                WHERE author_id = $1
+            "#,
+            author_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        else {
+            return Ok(None);
+        };
+
+        let books = sqlx::query_as!(
+            AuthoredBookRow,
+            r#"
+                SELECT
+                    b.book_id,
+                    b.title,
+                    ba.role
+                FROM book_authors AS ba
+                INNER JOIN books AS b USING(book_id)
+                WHERE ba.author_id = $1
+                AND b.deleted_at IS NULL
+                ORDER BY lower(b.title), b.book_id, ba.role
+            "#,
+            author_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(AuthoredBook::try_from)
+        .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(Some(AuthorDetail {
+            id: author.author_id,
+            name: author.name,
+            books,
+        }))
+    }
+
+    async fn update(&self, event: UpdateAuthor) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let res = sqlx::query!(
+            r#"
+                UPDATE authors
+                SET name = $1
+                WHERE author_id = $2
+            "#,
+            event.name,
+            event.author_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| duplicated_name_error(e, &event.name))?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified author not found".into(),
+            ));
+        }
+
+        // 名前の変更を、この著者が関わった蔵書の著者表記に反映する
+        let book_ids = find_authored_book_ids(&mut tx, event.author_id).await?;
+        let changed = sync_author_notation(&mut tx, &book_ids).await?;
+        bump_book_versions(&mut tx, &changed).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn merge(&self, event: MergeAuthor) -> AppResult<()> {
+        if event.source == event.target {
+            return Err(AppError::UnprocessableEntity(
+                "同じ著者どうしは統合できません。".into(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        // 統合中に他の操作で削除されないよう、両方の著者の行をロックしてから確認する
+        let locked = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM (
+                    SELECT 1 FROM authors
+                    WHERE author_id IN ($1, $2)
+                    FOR UPDATE
+                ) AS a
+            "#,
+            event.source as _,
+            event.target as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if locked < 2 {
+            return Err(AppError::EntityNotFound(
+                "specified author not found".into(),
+            ));
+        }
+
+        // 統合先がすでに同じ役割で関わっている蔵書では、統合先の並び順を残す
+        sqlx::query!(
+            r#"
+                INSERT INTO book_authors (book_id, author_id, role, position)
+                SELECT book_id, $2, role, position
+                FROM book_authors
+                WHERE author_id = $1
+                ON CONFLICT DO NOTHING
+            "#,
+            event.source as _,
+            event.target as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 統合元の著者と蔵書との関連は、外部キーの ON DELETE CASCADE で削除される
+        sqlx::query!(
+            r#"
+                DELETE FROM authors WHERE author_id = $1
+            "#,
+            event.source as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let book_ids = find_authored_book_ids(&mut tx, event.target).await?;
+        let changed = sync_author_notation(&mut tx, &book_ids).await?;
+        bump_book_versions(&mut tx, &changed).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn update_book_authors(&self, event: UpdateBookAuthors) -> AppResult<()> {
+        let mut seen = HashSet::new();
+        if !event.authors.iter().all(|author| seen.insert(*author)) {
+            return Err(AppError::UnprocessableEntity(
+                "同じ著者を同じ役割で複数回指定することはできません。".into(),
+            ));
+        }
+        let (author_ids, roles): (Vec<_>, Vec<_>) = event
+            .authors
+            .iter()
+            .map(|(author_id, role)| (*author_id, role.as_ref().to_string()))
+            .unzip();
+
+        let mut tx = self.db.begin().await?;
+
+        // 同じ蔵書の著者が同時に変更されないよう、蔵書の行をロックする
+        let row = sqlx::query!(
+            r#"
+                SELECT
+                    title,
+                    author,
+                    isbn AS "isbn: Isbn",
+                    description,
+                    user_id AS "owner_id: UserId"
+                FROM books
+                WHERE book_id = $1
+                AND deleted_at IS NULL
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            row.owner_id,
+            event.requested_user,
+            event.is_admin,
+            BookRevisionAction::Update,
+        )
+        .await?;
+        let old = BookFieldsRow {
+            title: row.title,
+            author: row.author,
+            isbn: row.isbn,
+            description: row.description,
+        };
+
+        sqlx::query!(
+            r#"
+                DELETE FROM book_authors WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO book_authors (book_id, author_id, role, position)
+                SELECT $1, a.author_id, a.role, a.position
+                FROM UNNEST($2::uuid[], $3::varchar[]) WITH ORDINALITY AS a(author_id, role, position)
+            "#,
+            event.book_id as _,
+            author_ids as _,
+            &roles
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified author not found".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        // 著者表記を、置き換えた著者から作り直す。表記が変わらなくても著者の関連は変わるため、版数は上げる
+        sync_author_notation(&mut tx, &[event.book_id]).await?;
+        bump_book_versions(&mut tx, &[event.book_id]).await?;
+        insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Update,
+            Some(old),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+/// 著者が関わった蔵書の ID を、除籍した蔵書も含めて取得する。
+async fn find_authored_book_ids(
+    conn: &mut PgConnection,
+    author_id: AuthorId,
+) -> AppResult<Vec<BookId>> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT DISTINCT book_id AS "book_id: BookId"
+            FROM book_authors
+            WHERE author_id = $1
+        "#,
+        author_id as _
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)
+}
+
+/// 蔵書に関連付けた著者から著者表記を作り直す。著者の並び順に、名前を「、」でつないだ表記にする。
+/// 著者が 1 人もいない蔵書は、表記をそのまま残す。表記が変わった蔵書の ID を返す。
+async fn sync_author_notation(
+    conn: &mut PgConnection,
+    book_ids: &[BookId],
+) -> AppResult<Vec<BookId>> {
+    sqlx::query_scalar!(
+        r#"
+            UPDATE books AS b
+            SET author = n.notation
+            FROM (
+                SELECT book_id, left(string_agg(name, '、' ORDER BY position), 255) AS notation
+                FROM (
+                    -- 複数の役割で関わる著者も、表記には 1 度だけ含める
+                    SELECT DISTINCT ON (ba.book_id, ba.author_id)
+                        ba.book_id, a.name, ba.position
+                    FROM book_authors AS ba
+                    INNER JOIN authors AS a USING(author_id)
+                    WHERE ba.book_id = ANY($1)
+                    ORDER BY ba.book_id, ba.author_id, ba.position
+                ) AS d
+                GROUP BY book_id
+            ) AS n
+            WHERE b.book_id = n.book_id
+            AND b.author <> n.notation
+            RETURNING b.book_id AS "book_id: BookId"
+        "#,
+        book_ids as _
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)
+}
+
+async fn bump_book_versions(conn: &mut PgConnection, book_ids: &[BookId]) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE books SET version = version + 1 WHERE book_id = ANY($1)
+        "#,
+        book_ids as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+fn duplicated_name_error(e: sqlx::Error, name: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::UnprocessableEntity(format!("著者 ({name}) はすでに存在します。"))
+        }
+        e => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::book::BookRepositoryImpl;
+    use kernel::model::author::AuthorRole;
+    use kernel::model::book::event::CreateBook;
+    use kernel::model::book::{Book, BookListOptions, DuplicateBookPolicy};
+    use kernel::model::id::{BookId, UserId};
+    use kernel::model::isbn::Isbn;
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[sqlx::test]
+    async fn test_split_author_names(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let split = |notation: &'static str| {
+            let pool = &pool;
+            async move {
+                let names = sqlx::query_scalar!(
+                    r#"
+                        SELECT name AS "name!" FROM split_author_names($1) ORDER BY ord
+                    "#,
+                    notation
+                )
+                .fetch_all(pool)
+                .await?;
+                anyhow::Ok(names)
+            }
+        };
+
+        assert_eq!(
+            split("山田太郎、 山田花子，Jim Blandy; Jason Orendorff；").await?,
+            ["山田太郎", "山田花子", "Jim Blandy", "Jason Orendorff"]
+        );
+        // 名前の一部にもなる区切りでは分割せず、1 人の著者として扱う
+        for notation in [
+            "Blandy, Jim",
+            "Procter & Gamble",
+            "AC/DC",
+            "Jim Blandy and Jason Orendorff",
+        ] {
+            assert_eq!(split(notation).await?, [notation]);
+        }
+        assert!(split(" 、 ").await?.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_manage_book_authors(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = AuthorRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let create_book = |title: &str, author: &str, isbn: &str| -> anyhow::Result<CreateBook> {
+            Ok(CreateBook {
+                title: title.into(),
+                author: author.into(),
+                isbn: Isbn::from_str(isbn)?,
+                description: "".into(),
+                copies: 1,
+                on_duplicate: DuplicateBookPolicy::Reject,
+            })
+        };
+        let find_book = |title: &'static str| {
+            let book_repo = &book_repo;
+            async move {
+                let books = book_repo
+                    .find_all(BookListOptions {
+                        limit: 20,
+                        offset: 0,
+                        ..Default::default()
+                    })
+                    .await?
+                    .items;
+                anyhow::Ok(books.into_iter().find(|b| b.title == title).unwrap())
+            }
+        };
+        let names = |book: &Book| {
+            book.authors
+                .iter()
+                .map(|a| (a.name.clone(), a.role))
+                .collect::<Vec<_>>()
+        };
+
+        // 登録時に著者表記が分割され、大文字・小文字だけが異なる名前は同じ著者になる
+        book_repo
+            .create(
+                create_book(
+                    "Programming Rust",
+                    "Jim Blandy、Jason Orendorff; Leonora F. S. Tindall",
+                    "978-4-7980-6170-2",
+                )?,
+                owner,
+            )
+            .await?;
+        book_repo
+            .create(
+                create_book("Rust 入門", "jim blandy；山田太郎", "978-4-06-530195-1")?,
+                owner,
+            )
+            .await?;
+        let first = find_book("Programming Rust").await?;
+        assert_eq!(
+            names(&first),
+            [
+                ("Jim Blandy".to_string(), AuthorRole::Author),
+                ("Jason Orendorff".to_string(), AuthorRole::Author),
+                ("Leonora F. S. Tindall".to_string(), AuthorRole::Author),
+            ]
+        );
+        let second = find_book("Rust 入門").await?;
+        assert_eq!(second.authors[0].id, first.authors[0].id);
+        assert_eq!(second.authors[1].name, "山田太郎");
+
+        let jim = first.authors[0].id;
+        let detail = repo.find_by_id(jim).await?.unwrap();
+        assert_eq!(detail.name, "Jim Blandy");
+        let titles = detail
+            .books
+            .iter()
+            .map(|b| b.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["Programming Rust", "Rust 入門"]);
+
+        let res = repo
+            .create(CreateAuthor {
+                name: "JIM BLANDY".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let translator = repo
+            .create(CreateAuthor {
+                name: "山田花子".into(),
+            })
+            .await?;
+
+        let update = |requested_user, authors| UpdateBookAuthors {
+            book_id: second.id,
+            requested_user,
+            is_admin: false,
+            authors,
+        };
+        repo.update_book_authors(update(
+            owner,
+            vec![
+                (second.authors[1].id, AuthorRole::Author),
+                (translator.id, AuthorRole::Translator),
+                (jim, AuthorRole::Editor),
+            ],
+        ))
+        .await?;
+        let second = find_book("Rust 入門").await?;
+        assert_eq!(
+            names(&second),
+            [
+                ("山田太郎".to_string(), AuthorRole::Author),
+                ("山田花子".to_string(), AuthorRole::Translator),
+                ("Jim Blandy".to_string(), AuthorRole::Editor),
+            ]
+        );
+        // 著者表記も置き換えた著者から作り直される
+        assert_eq!(second.author, "山田太郎、山田花子、Jim Blandy");
+
+        // 同じ著者を同じ役割で重複して指定できない
+        let res = repo
+            .update_book_authors(update(
+                owner,
+                vec![(jim, AuthorRole::Author), (jim, AuthorRole::Author)],
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        // 存在しない著者は指定できない
+        let res = repo
+            .update_book_authors(update(owner, vec![(AuthorId::new(), AuthorRole::Author)]))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        // 所有者以外は、管理者でなければ変更できない
+        let other = UserId::new();
+        let res = repo
+            .update_book_authors(update(other, vec![(jim, AuthorRole::Author)]))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.update_book_authors(UpdateBookAuthors {
+            is_admin: true,
+            ..update(
+                other,
+                vec![
+                    (second.authors[0].id, AuthorRole::Author),
+                    (translator.id, AuthorRole::Translator),
+                    (jim, AuthorRole::Editor),
+                ],
+            )
+        })
+        .await?;
+        let audit_logs = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM book_audit_logs
+                WHERE book_id = $1 AND user_id = $2 AND owner_id = $3
+            "#,
+            second.id as _,
+            other as _,
+            owner as _
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(audit_logs, 1);
+        let res = repo
+            .update_book_authors(UpdateBookAuthors {
+                book_id: BookId::new(),
+                requested_user: owner,
+                is_admin: false,
+                authors: vec![],
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 統合すると、統合元が関わっていた蔵書には統合先が同じ役割で関わる
+        repo.merge(MergeAuthor {
+            source: translator.id,
+            target: jim,
+        })
+        .await?;
+        assert!(repo.find_by_id(translator.id).await?.is_none());
+        let second = find_book("Rust 入門").await?;
+        assert_eq!(
+            names(&second),
+            [
+                ("山田太郎".to_string(), AuthorRole::Author),
+                ("Jim Blandy".to_string(), AuthorRole::Translator),
+                ("Jim Blandy".to_string(), AuthorRole::Editor),
+            ]
+        );
+        let res = repo
+            .merge(MergeAuthor {
+                source: jim,
+                target: jim,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        repo.update(UpdateAuthor {
+            author_id: jim,
+            name: "James Blandy".into(),
+        })
+        .await?;
+        // 名前の変更は、著者が関わった蔵書の著者表記に反映される
+        let first = find_book("Programming Rust").await?;
+        assert_eq!(
+            first.author,
+            "James Blandy、Jason Orendorff、Leonora F. S. Tindall"
+        );
+        assert_eq!(
+            find_book("Rust 入門").await?.author,
+            "山田太郎、James Blandy"
+        );
+        let res = repo
+            .update(UpdateAuthor {
+                author_id: jim,
+                name: "山田太郎".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        assert_eq!(repo.find_all().await?.len(), 4);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use kernel::repository::blob_store::BlobStore;
+use shared::error::{AppError, AppResult};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use tokio::fs;
+use uuid::Uuid;
+
+/// ローカルのファイルシステムの、指定したディレクトリ以下にデータを保存する。
+/// キーの `/` 区切りの各要素を、ディレクトリとファイルの名前として使う。
+pub struct LocalFsBlobStore {
+    root: PathBuf,
+}
+
+impl LocalFsBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// キーに対応するファイルのパス。ルートの外を指しうるキーはエラーとする。
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        let is_valid = key
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | "..") && !segment.contains(['\\', '\0']));
+        if !is_valid {
+            return Err(AppError::BlobStoreError(format!("invalid blob key: {key}")));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalFsBlobStore {
+    async fn put(&self, key: &str, data: Vec<u8>) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+        // 書き込み途中のファイルを読まれないよう、一時ファイルに書き込んでから置き換える。
+        // 同じキーへの同時の書き込みが一時ファイルを取り合わないよう、名前は書き込みごとに変える
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(format!(".{}.tmp", Uuid::new_v4().simple()));
+        fs::write(&tmp, data).await.map_err(io_error)?;
+        fs::rename(&tmp, &path).await.map_err(io_error)
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        match fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::BlobStoreError(format!("blob store operation failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_get_delete() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = LocalFsBlobStore::new(dir.path());
+
+        assert!(store.get("covers/book/original").await?.is_none());
+
+        store.put("covers/book/original", b"first".to_vec()).await?;
+        store
+            .put("covers/book/original", b"second".to_vec())
+            .await?;
+        assert_eq!(
+            store.get("covers/book/original").await?.as_deref(),
+            Some(&b"second"[..])
+        );
+
+        store.delete("covers/book/original").await?;
+        assert!(store.get("covers/book/original").await?.is_none());
+        // 存在しないデータの削除はエラーにならない
+        store.delete("covers/book/original").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reject_key_outside_root() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = LocalFsBlobStore::new(dir.path().join("root"));
+
+        for key in [
+            "../escaped",
+            "/etc/passwd",
+            "covers/./original",
+            "covers//original",
+            "",
+        ] {
+            let res = store.put(key, b"data".to_vec()).await;
+            assert!(res.is_err(), "{key}");
+        }
+
+        Ok(())
+    }
+}
@@ -1,97 +1,175 @@
 use crate::database::ConnectionPool;
-use crate::database::model::book::{BookCheckoutRow, BookRow, PaginatedBookRow};
+use crate::database::model::author::BookAuthorRow;
+use crate::database::model::book::{
+    BookAuditLogRow, BookCheckoutRow, BookCopyRow, BookCursorRow, BookFieldsRow, BookRevisionRow,
+    BookRow, BookStatusHistoryRow, PaginatedBookRow,
+};
+use crate::database::model::tag::BookTagRow;
+use crate::repository::book_cover::delete_cover_blobs;
 use async_trait::async_trait;
 use derive_new::new;
-use kernel::model::book::event::{CreateBook, DeleteBook, UpdateBook};
-use kernel::model::book::{Book, BookListOptions, Checkout};
-use kernel::model::id::{BookId, UserId};
-use kernel::model::list::PaginatedList;
-use kernel::repository::book::BookRepository;
+use kernel::model::author::{AuthorRole, BookAuthor};
+use kernel::model::book::event::{
+    ChangeBookStatus, CreateBook, CreateBookCopy, DeleteBook, DeleteBookCopy, ImportBook,
+    ImportBooks, MarkBookFound, PatchBook, PurgeBook, RestoreBook, RevertBook, TransferBook,
+    TransferBooks, UpdateBook,
+};
+use kernel::model::book::revision::{BookAuditLog, BookRevision, BookRevisionAction};
+use kernel::model::book::{
+    Book, BookAvailability, BookCopy, BookImportResult, BookImportStatus, BookListOptions,
+    BookRegistration, BookSort, BookSortKey, BookStatus, BookStatusChange, Checkout,
+    DuplicateBookPolicy, outdated_version_error,
+};
+use kernel::model::id::{BookId, LocationId, UserId};
+use kernel::model::isbn::Isbn;
+use kernel::model::list::{
+    Cursor, CursorDirection, CursorPaginatedList, CursorPosition, PaginatedList, SortOrder,
+};
+use kernel::model::tag::Tag;
+use kernel::repository::blob_store::BlobStore;
+use kernel::repository::book::{BookRepository, BookStream};
 use shared::error::{AppError, AppResult};
+use sqlx::{Acquire, PgConnection, Postgres, QueryBuilder};
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// `stream_all` で一度に取得する蔵書の件数
+const STREAM_BATCH_SIZE: i64 = 100;
+/// 重複登録の検出で、書名・著者表記がよく似ているとみなす類似度（pg_trgm の `similarity`）の下限
+const DUPLICATE_TITLE_SIMILARITY: f32 = 0.8;
+const DUPLICATE_AUTHOR_SIMILARITY: f32 = 0.5;
 
 #[derive(new)]
 pub struct BookRepositoryImpl {
     db: ConnectionPool,
+    /// 完全に削除した蔵書の表紙画像を削除するために使う
+    blob_store: Arc<dyn BlobStore>,
 }
 
 #[async_trait]
 impl BookRepository for BookRepositoryImpl {
-    async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()> {
-        sqlx::query!(
-            r#"
-                INSERT INTO books (title, author, isbn, description, user_id)
-                VALUES($1, $2, $3, $4, $5)
-            "#,
-            event.title,
-            event.author,
-            event.isbn,
-            event.description,
-            user_id as _
-        )
-        .execute(self.db.inner_ref())
-        .await
-        .map_err(AppError::SpecificOperationError)?;
+    async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<BookRegistration> {
+        let mut tx = self.db.begin().await?;
 
-        Ok(())
+        lock_owner_registrations(&mut tx, user_id).await?;
+
+        let duplicated = match event.on_duplicate {
+            DuplicateBookPolicy::Allow => None,
+            DuplicateBookPolicy::Reject | DuplicateBookPolicy::AddCopy => {
+                find_duplicated_book(&mut tx, &event, user_id).await?
+            }
+        };
+        let registration = match duplicated {
+            None => BookRegistration::Created(insert_book(&mut tx, event, user_id).await?),
+            Some(book_id) if event.on_duplicate == DuplicateBookPolicy::AddCopy => {
+                insert_copies(&mut tx, book_id, event.copies).await?;
+                BookRegistration::CopyAdded(book_id)
+            }
+            Some(book_id) => {
+                return Err(AppError::Conflict {
+                    message: "同じ蔵書がすでに登録されています。".into(),
+                    existing_id: book_id.to_string(),
+                });
+            }
+        };
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(registration)
     }
 
-    async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>> {
-        let BookListOptions { limit, offset } = options;
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>> {
+        let ImportBooks {
+            books,
+            requested_user,
+            atomic,
+        } = event;
+        let mut tx = self.db.begin().await?;
+        let mut results = Vec::with_capacity(books.len());
 
-        let rows: Vec<PaginatedBookRow> = sqlx::query_as!(
-            PaginatedBookRow,
-            r#"
-                SELECT
-                    COUNT(*) OVER() AS "total!",
-                    b.book_id AS id
-                FROM books AS b
-                ORDER BY b.created_at DESC
-                LIMIT $1
-                OFFSET $2
-            "#,
-            limit,
-            offset
-        )
-        .fetch_all(self.db.inner_ref())
-        .await
-        .map_err(AppError::SpecificOperationError)?;
+        lock_owner_registrations(&mut tx, requested_user).await?;
 
-        let total = rows.first().map(|r| r.total).unwrap_or_default();
-        let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+        for ImportBook { line, book } in books {
+            // 取り込むデータ内の重複も、先に登録した行との重複として検出される
+            let duplicated = sqlx::query_scalar!(
+                r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM books
+                        WHERE isbn = $1
+                        AND user_id = $2
+                        AND deleted_at IS NULL
+                    ) AS "exists!"
+                "#,
+                book.isbn as _,
+                requested_user as _
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            if duplicated {
+                results.push(BookImportResult {
+                    line,
+                    status: BookImportStatus::Skipped(format!(
+                        "ISBN ({}) の蔵書はすでに登録されています。",
+                        book.isbn
+                    )),
+                });
+                continue;
+            }
 
-        let rows: Vec<BookRow> = sqlx::query_as!(
-            BookRow,
-            r#"
-                SELECT
-                    b.book_id AS book_id,
-                    b.title AS title,
-                    b.author AS author,
-                    b.isbn AS isbn,
-                    b.description AS description,
-                    u.user_id AS owned_by,
-                    u.name AS owner_name
-                FROM books b
-                INNER JOIN users AS u USING(user_id)
-                WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
-                ORDER BY b.created_at DESC
-            "#,
-            &book_ids as _
-        )
-        .fetch_all(self.db.inner_ref())
-        .await
-        .map_err(AppError::SpecificOperationError)?;
+            // 失敗した行だけを取り消して残りの行を続けられるよう、行ごとにセーブポイントを置く
+            let mut savepoint = tx.begin().await.map_err(AppError::TransactionError)?;
+            let status = match insert_book(&mut savepoint, book, requested_user).await {
+                Ok(book_id) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .map_err(AppError::TransactionError)?;
+                    BookImportStatus::Created(book_id)
+                }
+                Err(e) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(AppError::TransactionError)?;
+                    BookImportStatus::Failed(e.to_string())
+                }
+            };
+            results.push(BookImportResult { line, status });
+        }
 
-        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
-        let mut checkouts = self.find_checkouts(&book_ids).await?;
+        // すべての行を試してから、失敗した行があればまとめて取り消し、失敗した行だけを返す
+        if atomic
+            && results
+                .iter()
+                .any(|r| matches!(r.status, BookImportStatus::Failed(_)))
+        {
+            tx.rollback().await.map_err(AppError::TransactionError)?;
+            results.retain(|r| matches!(r.status, BookImportStatus::Failed(_)));
+            return Ok(results);
+        }
 
-        let items = rows
-            .into_iter()
-            .map(|row| {
-                let checkout = checkouts.remove(&row.book_id);
-                row.into_book(checkout)
-            })
-            .collect();
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(results)
+    }
+
+    async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>> {
+        let limit = options.limit;
+        let offset = options.offset;
+
+        let rows: Vec<PaginatedBookRow> = build_book_list_query(&options)
+            .build_query_as()
+            .fetch_all(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+        let items = self.find_by_ids(&book_ids).await?;
 
         Ok(PaginatedList {
             total,
@@ -101,6 +179,85 @@ impl BookRepository for BookRepositoryImpl {
         })
     }
 
+    async fn find_all_with_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<Cursor>,
+    ) -> AppResult<CursorPaginatedList<Book>> {
+        // 並び順や絞り込みの条件を変えた一覧にカーソルを使うと、ページの境界が意味をなさなくなる
+        let scope = options.cursor_scope();
+        let position = match &cursor {
+            None => None,
+            Some(Cursor {
+                position: Some(position),
+                ..
+            }) if position.scope == scope => Some(position),
+            Some(_) => {
+                return Err(AppError::BadRequest(
+                    "カーソルを取得したときと並び順または絞り込みの条件が異なります。".into(),
+                ));
+            }
+        };
+
+        let mut rows = build_book_cursor_query(&options, cursor.as_ref(), position)
+            .build_query_as::<BookCursorRow>()
+            .fetch_all(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        if matches!(&cursor, Some(c) if c.direction == CursorDirection::Prev) {
+            rows.reverse();
+        }
+
+        let page =
+            CursorPaginatedList::from_items(rows, options.limit, cursor.as_ref(), |row, d| {
+                Cursor::new(row.id.raw(), d).with_position(row.sort_value.clone(), scope)
+            });
+        let book_ids = page.items.iter().map(|row| row.id).collect::<Vec<_>>();
+        let items = self.find_by_ids(&book_ids).await?;
+
+        Ok(CursorPaginatedList {
+            items,
+            next_cursor: page.next_cursor,
+            prev_cursor: page.prev_cursor,
+        })
+    }
+
+    fn stream_all(&self, options: BookListOptions) -> BookStream {
+        let repo = Self::new(self.db.clone(), self.blob_store.clone());
+        let (tx, rx) = mpsc::channel(STREAM_BATCH_SIZE as usize);
+
+        // カーソル方式のページングで少しずつ取得し、取得した順に送る
+        tokio::spawn(async move {
+            let options = BookListOptions {
+                limit: STREAM_BATCH_SIZE,
+                offset: 0,
+                ..options
+            };
+            let mut cursor = None;
+            loop {
+                let page = match repo.find_all_with_cursor(options.clone(), cursor).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                for book in page.items {
+                    // 受け取り側が破棄された場合は、以降の取得をやめる
+                    if tx.send(Ok(book)).await.is_err() {
+                        return;
+                    }
+                }
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => return,
+                }
+            }
+        });
+
+        Box::pin(ReceiverStream::new(rx))
+    }
+
     async fn find_by_id(&self, book_id: BookId) -> AppResult<Option<Book>> {
         let row: Option<BookRow> = sqlx::query_as!(
             BookRow,
@@ -109,15 +266,52 @@ impl BookRepository for BookRepositoryImpl {
                     b.book_id AS book_id,
                     b.title AS title,
                     b.author AS author,
-                    b.isbn AS isbn,
+                    b.isbn AS "isbn: Isbn",
                     b.description AS description,
                     u.user_id AS owned_by,
-                    u.name AS owner_name
+                    u.name AS owner_name,
+                    cc.total_copies AS "total_copies!",
+                    cc.available_copies AS "available_copies!",
+                    bcv.content_type AS "cover_content_type?",
+                    bcv.updated_at AS "cover_updated_at?",
+                    b.location_id AS "location_id?: LocationId",
+                    lb.name AS "location_branch?",
+                    lr.name AS "location_room?",
+                    ls.name AS "location_shelf?",
+                    b.status,
+                    b.status_changed_at,
+                    b.deleted_at,
+                    b.version,
+                    rv.average_rating,
+                    rv.review_count AS "review_count!"
                 FROM books b
                 INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN book_covers AS bcv USING(book_id)
+                LEFT OUTER JOIN locations AS ls ON ls.location_id = b.location_id
+                LEFT OUTER JOIN locations AS lr ON lr.location_id = ls.parent_id
+                LEFT OUTER JOIN locations AS lb ON lb.location_id = lr.parent_id
+                CROSS JOIN LATERAL (
+                    SELECT
+                        COUNT(*) AS total_copies,
+                        COUNT(*) FILTER (
+                            WHERE c.checkout_id IS NULL AND bc.lost_at IS NULL AND b.status = $2
+                        ) AS available_copies
+                    FROM book_copies AS bc
+                    LEFT OUTER JOIN checkouts AS c USING(copy_id)
+                    WHERE bc.book_id = b.book_id
+                ) AS cc
+                CROSS JOIN LATERAL (
+                    SELECT
+                        AVG(r.rating)::float8 AS average_rating,
+                        COUNT(*) AS review_count
+                    FROM book_reviews AS r
+                    WHERE r.book_id = b.book_id
+                ) AS rv
                 WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
             "#,
-            book_id as _
+            book_id as _,
+            BookStatus::Available.as_ref()
         )
         .fetch_optional(self.db.inner_ref())
         .await
@@ -125,181 +319,3190 @@ impl BookRepository for BookRepositoryImpl {
 
         match row {
             Some(r) => {
-                let checkout = self.find_checkouts(&[r.book_id]).await?.remove(&r.book_id);
-                Ok(Some(r.into_book(checkout)))
+                let checkouts = self
+                    .find_checkouts(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                let tags = self
+                    .find_tags(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                let authors = self
+                    .find_authors(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                r.into_book(authors, checkouts, tags).map(Some)
             }
             None => Ok(None),
         }
     }
 
-    async fn update(&self, event: UpdateBook) -> AppResult<()> {
-        let res = sqlx::query!(
+    async fn update(&self, event: UpdateBook) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
+
+        let old = lock_book_fields(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            event.is_admin,
+            event.version,
+        )
+        .await?;
+
+        sqlx::query!(
             r#"
                 UPDATE books
                 SET
                     title = $1,
                     author = $2,
                     isbn = $3,
-                    description = $4
+                    description = $4,
+                    version = version + 1
                 WHERE book_id = $5
-                AND   user_id = $6
             "#,
             event.title,
             event.author,
-            event.isbn,
+            event.isbn as _,
             event.description,
-            event.book_id as _,
-            event.requested_user as _
+            event.book_id as _
         )
-        .execute(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
         .map_err(AppError::SpecificOperationError)?;
 
-        if res.rows_affected() < 1 {
-            return Err(AppError::EntityNotFound("specified book not found".into()));
+        if event.author != old.author {
+            relink_authors(&mut tx, event.book_id, &event.author).await?;
         }
+        clear_invalid_isbn(&mut tx, event.book_id).await?;
 
-        Ok(())
+        let version = insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Update,
+            Some(old),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
+    }
+
+    async fn patch(&self, event: PatchBook) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
+
+        let old = lock_book_fields(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            event.is_admin,
+            event.version,
+        )
+        .await?;
+
+        // 指定された列のみを更新する
+        let relinked_author = event.author.clone().filter(|author| *author != old.author);
+        let isbn_updated = event.isbn.is_some();
+        let mut builder = QueryBuilder::<Postgres>::new("UPDATE books SET version = version + 1");
+        if let Some(title) = event.title {
+            builder.push(", title = ").push_bind(title);
+        }
+        if let Some(author) = event.author {
+            builder.push(", author = ").push_bind(author);
+        }
+        if let Some(isbn) = event.isbn {
+            builder.push(", isbn = ").push_bind(isbn);
+        }
+        if let Some(description) = event.description {
+            builder.push(", description = ").push_bind(description);
+        }
+        builder.push(" WHERE book_id = ").push_bind(event.book_id);
+
+        builder
+            .build()
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        if let Some(author) = relinked_author {
+            relink_authors(&mut tx, event.book_id, &author).await?;
+        }
+        if isbn_updated {
+            clear_invalid_isbn(&mut tx, event.book_id).await?;
+        }
+
+        let version = insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Update,
+            Some(old),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
     }
 
     async fn delete(&self, event: DeleteBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 確認後に貸し出されないよう、蔵書の行をロックしてから貸出状況を確認する
         let res = sqlx::query!(
             r#"
-                DELETE FROM books
+                SELECT
+                    b.user_id AS "owner_id: UserId",
+                    EXISTS (
+                        SELECT 1 FROM checkouts AS c WHERE c.book_id = b.book_id
+                    ) AS "checked_out!"
+                FROM books AS b
+                WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            res.owner_id,
+            event.requested_user,
+            event.is_admin,
+            BookRevisionAction::Delete,
+        )
+        .await?;
+
+        if res.checked_out {
+            if !event.force {
+                return Err(AppError::UnprocessableEntity(format!(
+                    "書籍 ({}) は貸出中のため除籍できません。返却後に除籍してください。",
+                    event.book_id
+                )));
+            }
+
+            // 強制除籍の場合は、貸出中の貸出を現時点で返却されたものとして記録する
+            sqlx::query!(
+                r#"
+                    INSERT INTO returned_checkouts
+                    (checkout_id, book_id, copy_id, user_id, checked_out_at, returned_at)
+                    SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, current_timestamp(3)
+                    FROM checkouts
+                    WHERE book_id = $1
+                "#,
+                event.book_id as _
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            sqlx::query!(
+                r#"
+                    DELETE FROM checkouts WHERE book_id = $1
+                "#,
+                event.book_id as _
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        }
+
+        // 貸出履歴を残すため、行は削除せずに除籍日時を記録する
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET deleted_at = current_timestamp(3)
                 WHERE book_id = $1
-                AND user_id = $2
             "#,
-            event.book_id as _,
-            event.requested_user as _
+            event.book_id as _
         )
-        .execute(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
         .map_err(AppError::SpecificOperationError)?;
 
-        if res.rows_affected() < 1 {
-            return Err(AppError::EntityNotFound("specified book not found".into()));
+        insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Delete,
+            None,
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn restore(&self, event: RestoreBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let res = sqlx::query!(
+            r#"
+                SELECT
+                    user_id AS "owner_id: UserId",
+                    deleted_at IS NOT NULL AS "deleted!"
+                FROM books
+                WHERE book_id = $1
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+        if !res.deleted {
+            return Err(AppError::EntityNotFound(
+                "specified deleted book not found".into(),
+            ));
         }
 
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            res.owner_id,
+            event.requested_user,
+            event.is_admin,
+            BookRevisionAction::Restore,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET deleted_at = NULL
+                WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Restore,
+            None,
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
         Ok(())
     }
-}
 
-impl BookRepositoryImpl {
-    async fn find_checkouts(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Checkout>> {
-        let res = sqlx::query_as!(
-            BookCheckoutRow,
+    async fn find_audit_logs(&self, book_id: BookId) -> AppResult<Vec<BookAuditLog>> {
+        sqlx::query_as!(
+            BookAuditLogRow,
             r#"
                 SELECT
-                    c.checkout_id,
-                    c.book_id,
-                    u.user_id,
-                    u.name AS user_name,
-                    c.checked_out_at
-                FROM checkouts AS c
-                INNER JOIN users AS u USING(user_id)
-                WHERE book_id = ANY($1)
-                ;
+                    l.audit_log_id,
+                    l.book_id,
+                    l.owner_id,
+                    l.user_id,
+                    u.name AS "user_name?",
+                    l.action,
+                    l.created_at
+                FROM book_audit_logs AS l
+                LEFT JOIN users AS u ON u.user_id = l.user_id
+                WHERE l.book_id = $1
+                ORDER BY l.created_at DESC, l.audit_log_id
             "#,
-            book_ids as _
+            book_id as _
         )
         .fetch_all(self.db.inner_ref())
         .await
         .map_err(AppError::SpecificOperationError)?
         .into_iter()
-        .map(|checkout| (checkout.book_id, Checkout::from(checkout)))
-        .collect();
+        .map(BookAuditLog::try_from)
+        .collect()
+    }
 
-        Ok(res)
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>> {
+        sqlx::query_as!(
+            BookRevisionRow,
+            r#"
+                SELECT
+                    r.revision_id,
+                    r.book_id,
+                    r.version,
+                    r.action,
+                    r.user_id,
+                    u.name AS "user_name?",
+                    r.old_title,
+                    r.old_author,
+                    r.old_isbn AS "old_isbn: Isbn",
+                    r.old_description,
+                    r.new_title,
+                    r.new_author,
+                    r.new_isbn AS "new_isbn: Isbn",
+                    r.new_description,
+                    r.created_at
+                FROM book_revisions AS r
+                LEFT JOIN users AS u ON u.user_id = r.user_id
+                WHERE r.book_id = $1
+                ORDER BY r.seq DESC
+            "#,
+            book_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(BookRevision::try_from)
+        .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::database::ConnectionPool;
-    use crate::repository::book::BookRepositoryImpl;
-    use crate::repository::user::UserRepositoryImpl;
-    use kernel::model::book::event::{CreateBook, UpdateBook};
-    use kernel::model::book::{Book, BookListOptions};
-    use kernel::model::id::{BookId, UserId};
-    use kernel::model::user::event::CreateUser;
-    use kernel::repository::book::BookRepository;
-    use kernel::repository::user::UserRepository;
-    use std::str::FromStr;
+    async fn revert(&self, event: RevertBook) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
 
-    #[sqlx::test]
-    async fn test_register_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        sqlx::query!(r#"INSERT INTO roles(name) VALUES ('Admin'), ('User');"#)
-            .execute(&pool)
-            .await?;
-        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let target = sqlx::query_as!(
+            BookFieldsRow,
+            r#"
+                SELECT
+                    new_title AS title,
+                    new_author AS author,
+                    new_isbn AS "isbn: Isbn",
+                    new_description AS description
+                FROM book_revisions
+                WHERE revision_id = $1
+                AND book_id = $2
+            "#,
+            event.revision_id as _,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book revision not found".into()))?;
 
-        let user = user_repo
-            .create(CreateUser {
-                name: "Test User".into(),
-                email: "test@example.com".into(),
-                password: "test_password".into(),
-            })
-            .await?;
+        let row = sqlx::query!(
+            r#"
+                SELECT
+                    title,
+                    author,
+                    isbn AS "isbn: Isbn",
+                    description,
+                    user_id AS "owner_id: UserId"
+                FROM books
+                WHERE book_id = $1
+                AND deleted_at IS NULL
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
 
-        let book = CreateBook {
-            title: "Test Title".into(),
-            author: "Test Author".into(),
-            isbn: "Test ISBN".into(),
-            description: "Test Description".into(),
+        // 差し戻しは管理者のみが行える
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            row.owner_id,
+            event.requested_user,
+            true,
+            BookRevisionAction::Revert,
+        )
+        .await?;
+        let old = BookFieldsRow {
+            title: row.title,
+            author: row.author,
+            isbn: row.isbn,
+            description: row.description,
         };
-        repo.create(book, user.id).await?;
 
-        let options = BookListOptions {
-            limit: 20,
-            offset: 0,
-        };
-        let res = repo.find_all(options).await?;
-        assert_eq!(res.items.len(), 1);
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET
+                    title = $1,
+                    author = $2,
+                    isbn = $3,
+                    description = $4,
+                    version = version + 1
+                WHERE book_id = $5
+            "#,
+            target.title,
+            target.author,
+            target.isbn as _,
+            target.description,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
 
-        let book_id = res.items[0].id;
-        let res = repo.find_by_id(book_id).await?;
-        assert!(res.is_some());
+        if target.author != old.author {
+            relink_authors(&mut tx, event.book_id, &target.author).await?;
+        }
 
-        let Book {
-            id,
-            title,
-            author,
-            isbn,
-            description,
-            owner,
-            ..
-        } = res.unwrap();
-        assert_eq!(id, book_id);
+        let version = insert_revision(
+            &mut tx,
+            event.book_id,
+            event.requested_user,
+            BookRevisionAction::Revert,
+            Some(old),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
+    }
+
+    async fn transfer(&self, event: TransferBook) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
+
+        let owner_id = sqlx::query_scalar!(
+            r#"
+                SELECT user_id AS "owner_id: UserId"
+                FROM books
+                WHERE book_id = $1
+                AND deleted_at IS NULL
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            owner_id,
+            event.requested_user,
+            event.is_admin,
+            BookRevisionAction::Transfer,
+        )
+        .await?;
+        if owner_id == event.new_owner {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) はすでに指定したユーザーが所有しています。",
+                event.book_id
+            )));
+        }
+        ensure_user_exists(&mut tx, event.new_owner).await?;
+
+        let version = transfer_book(
+            &mut tx,
+            event.book_id,
+            owner_id,
+            event.new_owner,
+            event.requested_user,
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
+    }
+
+    async fn transfer_all(&self, event: TransferBooks) -> AppResult<u64> {
+        // 譲渡する蔵書がない場合も、権限がなければエラーにする
+        if event.owner != event.requested_user && !event.is_admin {
+            return Err(AppError::ForbiddenOperation);
+        }
+        if event.owner == event.new_owner {
+            return Err(AppError::UnprocessableEntity(
+                "譲渡元と譲渡先に同じユーザーは指定できません。".into(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        ensure_user_exists(&mut tx, event.new_owner).await?;
+
+        // 退職者の蔵書がユーザーの削除とともに消えないよう、除籍した蔵書も譲渡する
+        let book_ids = sqlx::query_scalar!(
+            r#"
+                SELECT book_id AS "book_id: BookId"
+                FROM books
+                WHERE user_id = $1
+                ORDER BY created_at
+                FOR UPDATE
+            "#,
+            event.owner as _
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        for book_id in &book_ids {
+            authorize_book_owner(
+                &mut tx,
+                *book_id,
+                event.owner,
+                event.requested_user,
+                event.is_admin,
+                BookRevisionAction::Transfer,
+            )
+            .await?;
+            transfer_book(
+                &mut tx,
+                *book_id,
+                event.owner,
+                event.new_owner,
+                event.requested_user,
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(book_ids.len() as u64)
+    }
+
+    async fn purge(&self, event: PurgeBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 確認後に貸し出されないよう、蔵書の行をロックしてから確認する
+        let res = sqlx::query!(
+            r#"
+                SELECT
+                    b.user_id AS "owner_id: UserId",
+                    b.deleted_at IS NOT NULL AS "deleted!",
+                    EXISTS (
+                        SELECT 1 FROM checkouts AS c WHERE c.book_id = b.book_id
+                    ) AS "checked_out!"
+                FROM books AS b
+                WHERE b.book_id = $1
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        if !res.deleted {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) は除籍されていないため削除できません。",
+                event.book_id
+            )));
+        }
+        if res.checked_out {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) には貸出中の現物があるため削除できません。",
+                event.book_id
+            )));
+        }
+
+        // 表紙画像の登録情報は蔵書とともに削除されるため、画像を削除できるよう先に取得しておく
+        let cover_blob_id = sqlx::query_scalar!(
+            r#"
+                SELECT blob_id FROM book_covers WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // returned_checkouts には外部キーがないため、蔵書とあわせて明示的に削除する
+        sqlx::query!(
+            r#"
+                DELETE FROM returned_checkouts WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM books WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 変更履歴は蔵書とともに削除されるため、所有者にかかわらず監査ログに残す
+        insert_audit_log(
+            &mut tx,
+            event.book_id,
+            res.owner_id,
+            event.requested_user,
+            BookRevisionAction::Purge,
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        // 削除が確定してから画像を削除する
+        if let Some(blob_id) = cover_blob_id {
+            delete_cover_blobs(self.blob_store.as_ref(), event.book_id, blob_id).await;
+        }
+
+        Ok(())
+    }
+
+    async fn change_status(&self, event: ChangeBookStatus) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
+
+        // 確認後に貸し出されないよう、蔵書の行をロックしてから確認する
+        let row = sqlx::query!(
+            r#"
+                SELECT
+                    b.user_id AS "owner_id: UserId",
+                    b.status,
+                    EXISTS (
+                        SELECT 1 FROM checkouts AS c WHERE c.book_id = b.book_id
+                    ) AS "checked_out!"
+                FROM books AS b
+                WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
+                FOR UPDATE
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            row.owner_id,
+            event.requested_user,
+            event.is_admin,
+            BookRevisionAction::ChangeStatus,
+        )
+        .await?;
+
+        let current = BookStatus::from_str(&row.status)
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+        if current == event.status {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) はすでに{}です。",
+                event.book_id,
+                status_label(current)
+            )));
+        }
+        if !current.can_transition_to(event.status) {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) は{}のため、{}にできません。",
+                event.book_id,
+                status_label(current),
+                status_label(event.status)
+            )));
+        }
+        if row.checked_out && event.status != BookStatus::Available {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) には貸出中の現物があるため、{}にできません。",
+                event.book_id,
+                status_label(event.status)
+            )));
+        }
+
+        let version = change_book_status(
+            &mut tx,
+            event.book_id,
+            current,
+            event.status,
+            event.reason.as_deref(),
+            event.requested_user,
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
+    }
+
+    async fn mark_found(&self, event: MarkBookFound) -> AppResult<i64> {
+        let mut tx = self.db.begin().await?;
+
+        let row = sqlx::query!(
+            r#"
+                SELECT
+                    b.user_id AS "owner_id: UserId",
+                    b.status,
+                    bc.lost_at
+                FROM books AS b
+                INNER JOIN book_copies AS bc USING(book_id)
+                WHERE b.book_id = $1
+                AND bc.copy_id = $2
+                AND b.deleted_at IS NULL
+                FOR UPDATE OF b
+            "#,
+            event.book_id as _,
+            event.copy_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book copy not found".into()))?;
+
+        authorize_book_owner(
+            &mut tx,
+            event.book_id,
+            row.owner_id,
+            event.requested_user,
+            true,
+            BookRevisionAction::ChangeStatus,
+        )
+        .await?;
+
+        if row.lost_at.is_none() {
+            return Err(AppError::UnprocessableEntity(format!(
+                "現物 ({}) は紛失していません。",
+                event.copy_id
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+                UPDATE book_copies SET lost_at = NULL WHERE copy_id = $1
+            "#,
+            event.copy_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 貸し出せる現物が戻ったので、紛失の状態だった蔵書は貸出可能に戻す
+        let version = if row.status == BookStatus::Lost.as_ref() {
+            change_book_status(
+                &mut tx,
+                event.book_id,
+                BookStatus::Lost,
+                BookStatus::Available,
+                Some("紛失した蔵書が見つかった"),
+                event.requested_user,
+            )
+            .await?
+        } else {
+            sqlx::query_scalar!(
+                r#"
+                    UPDATE books SET version = version + 1 WHERE book_id = $1
+                    RETURNING version
+                "#,
+                event.book_id as _
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?
+        };
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(version)
+    }
+
+    async fn find_status_history(&self, book_id: BookId) -> AppResult<Vec<BookStatusChange>> {
+        self.ensure_book_exists(book_id).await?;
+
+        sqlx::query_as!(
+            BookStatusHistoryRow,
+            r#"
+                SELECT
+                    h.from_status,
+                    h.to_status,
+                    h.reason,
+                    h.user_id,
+                    u.name AS "user_name?",
+                    h.created_at
+                FROM book_status_histories AS h
+                LEFT JOIN users AS u ON u.user_id = h.user_id
+                WHERE h.book_id = $1
+                ORDER BY h.created_at DESC, h.status_history_id
+            "#,
+            book_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(BookStatusChange::try_from)
+        .collect()
+    }
+
+    async fn find_copies(&self, book_id: BookId) -> AppResult<Vec<BookCopy>> {
+        self.ensure_book_exists(book_id).await?;
+
+        let rows = sqlx::query_as!(
+            BookCopyRow,
+            r#"
+                SELECT
+                    bc.copy_id,
+                    bc.book_id,
+                    bc.barcode,
+                    l.location_id AS "current_location_id?: LocationId",
+                    l.parent_id AS "current_location_parent_id?: LocationId",
+                    l.kind AS "current_location_kind?",
+                    l.name AS "current_location_name?",
+                    bc.lost_at
+                FROM book_copies AS bc
+                LEFT OUTER JOIN locations AS l ON l.location_id = bc.current_location_id
+                WHERE bc.book_id = $1
+                ORDER BY bc.created_at, bc.barcode
+            "#,
+            book_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut checkouts = self
+            .find_checkouts(&[book_id])
+            .await?
+            .remove(&book_id)
+            .unwrap_or_default();
+
+        rows.into_iter()
+            .map(|row| {
+                let checkout = checkouts
+                    .iter()
+                    .position(|c| c.copy_id == row.copy_id)
+                    .map(|i| checkouts.swap_remove(i));
+                row.into_copy(checkout)
+            })
+            .collect()
+    }
+
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        match event.barcode {
+            Some(barcode) => {
+                sqlx::query!(
+                    r#"
+                        INSERT INTO book_copies (book_id, barcode)
+                        VALUES ($1, $2)
+                    "#,
+                    event.book_id as _,
+                    barcode
+                )
+                .execute(&mut *tx)
+                .await
+            }
+            None => {
+                sqlx::query!(
+                    r#"
+                        INSERT INTO book_copies (book_id)
+                        VALUES ($1)
+                    "#,
+                    event.book_id as _
+                )
+                .execute(&mut *tx)
+                .await
+            }
+        }
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                AppError::UnprocessableEntity("指定のバーコードは既に使用されています。".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 同時に削除されて現物が 0 冊にならないよう、蔵書の行をロックしてから確認する
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        let res = sqlx::query!(
+            r#"
+                SELECT
+                    EXISTS (
+                        SELECT 1 FROM checkouts AS c WHERE c.copy_id = $2
+                    ) AS "checked_out!",
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc WHERE bc.book_id = b.book_id
+                    ) AS "total_copies!"
+                FROM books AS b
+                INNER JOIN book_copies AS bc USING(book_id)
+                WHERE b.book_id = $1
+                AND bc.copy_id = $2
+            "#,
+            event.book_id as _,
+            event.copy_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book copy not found".into()))?;
+
+        if res.checked_out {
+            return Err(AppError::UnprocessableEntity(format!(
+                "貸出中の現物 ({}) は削除できません。",
+                event.copy_id
+            )));
+        }
+        if res.total_copies <= 1 {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) の最後の現物は削除できません。",
+                event.book_id
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+                DELETE FROM book_copies WHERE copy_id = $1
+            "#,
+            event.copy_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+impl BookRepositoryImpl {
+    /// 除籍されていない蔵書が存在することを確かめる。`find_by_id` と同じく、除籍した蔵書は存在しないものとして扱う。
+    async fn ensure_book_exists(&self, book_id: BookId) -> AppResult<()> {
+        let exists = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+            "#,
+            book_id as _
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !exists {
+            return Err(AppError::EntityNotFound("specified book not found".into()));
+        }
+
+        Ok(())
+    }
+
+    /// 指定した ID の蔵書を、貸出状況を含めて `book_ids` の順に取得する。
+    async fn find_by_ids(&self, book_ids: &[BookId]) -> AppResult<Vec<Book>> {
+        let rows: Vec<BookRow> = sqlx::query_as!(
+            BookRow,
+            r#"
+                SELECT
+                    b.book_id AS book_id,
+                    b.title AS title,
+                    b.author AS author,
+                    b.isbn AS "isbn: Isbn",
+                    b.description AS description,
+                    u.user_id AS owned_by,
+                    u.name AS owner_name,
+                    cc.total_copies AS "total_copies!",
+                    cc.available_copies AS "available_copies!",
+                    bcv.content_type AS "cover_content_type?",
+                    bcv.updated_at AS "cover_updated_at?",
+                    b.location_id AS "location_id?: LocationId",
+                    lb.name AS "location_branch?",
+                    lr.name AS "location_room?",
+                    ls.name AS "location_shelf?",
+                    b.status,
+                    b.status_changed_at,
+                    b.deleted_at,
+                    b.version,
+                    rv.average_rating,
+                    rv.review_count AS "review_count!"
+                FROM books b
+                INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN book_covers AS bcv USING(book_id)
+                LEFT OUTER JOIN locations AS ls ON ls.location_id = b.location_id
+                LEFT OUTER JOIN locations AS lr ON lr.location_id = ls.parent_id
+                LEFT OUTER JOIN locations AS lb ON lb.location_id = lr.parent_id
+                CROSS JOIN LATERAL (
+                    SELECT
+                        COUNT(*) AS total_copies,
+                        COUNT(*) FILTER (
+                            WHERE c.checkout_id IS NULL AND bc.lost_at IS NULL AND b.status = $2
+                        ) AS available_copies
+                    FROM book_copies AS bc
+                    LEFT OUTER JOIN checkouts AS c USING(copy_id)
+                    WHERE bc.book_id = b.book_id
+                ) AS cc
+                CROSS JOIN LATERAL (
+                    SELECT
+                        AVG(r.rating)::float8 AS average_rating,
+                        COUNT(*) AS review_count
+                    FROM book_reviews AS r
+                    WHERE r.book_id = b.book_id
+                ) AS rv
+                WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
+                ORDER BY array_position($1::uuid[], b.book_id)
+            "#,
+            book_ids as _,
+            BookStatus::Available.as_ref()
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
+        let mut checkouts = self.find_checkouts(&book_ids).await?;
+        let mut tags = self.find_tags(&book_ids).await?;
+        let mut authors = self.find_authors(&book_ids).await?;
+
+        let items = rows
+            .into_iter()
+            .map(|row| {
+                let checkouts = checkouts.remove(&row.book_id).unwrap_or_default();
+                let tags = tags.remove(&row.book_id).unwrap_or_default();
+                let authors = authors.remove(&row.book_id).unwrap_or_default();
+                row.into_book(authors, checkouts, tags)
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(items)
+    }
+
+    async fn find_checkouts(
+        &self,
+        book_ids: &[BookId],
+    ) -> AppResult<HashMap<BookId, Vec<Checkout>>> {
+        let rows = sqlx::query_as!(
+            BookCheckoutRow,
+            r#"
+                SELECT
+                    c.checkout_id,
+                    c.book_id,
+                    c.copy_id,
+                    bc.barcode,
+                    u.user_id,
+                    u.name AS user_name,
+                    c.checked_out_at
+                FROM checkouts AS c
+                INNER JOIN book_copies AS bc USING(copy_id)
+                INNER JOIN users AS u ON u.user_id = c.user_id
+                WHERE c.book_id = ANY($1)
+                ORDER BY c.checked_out_at
+                ;
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<Checkout>> = HashMap::new();
+        for row in rows {
+            res.entry(row.book_id)
+                .or_default()
+                .push(Checkout::from(row));
+        }
+
+        Ok(res)
+    }
+
+    async fn find_tags(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Vec<Tag>>> {
+        let rows = sqlx::query_as!(
+            BookTagRow,
+            r#"
+                SELECT
+                    bt.book_id,
+                    t.tag_id,
+                    t.name
+                FROM book_tags AS bt
+                INNER JOIN tags AS t USING(tag_id)
+                WHERE bt.book_id = ANY($1)
+                ORDER BY lower(t.name)
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            res.entry(row.book_id).or_default().push(Tag::from(row));
+        }
+
+        Ok(res)
+    }
+
+    async fn find_authors(
+        &self,
+        book_ids: &[BookId],
+    ) -> AppResult<HashMap<BookId, Vec<BookAuthor>>> {
+        let rows = sqlx::query_as!(
+            BookAuthorRow,
+            r#"
+                SELECT
+                    ba.book_id,
+                    a.author_id,
+                    a.name,
+                    ba.role
+                FROM book_authors AS ba
+                INNER JOIN authors AS a USING(author_id)
+                WHERE ba.book_id = ANY($1)
+                ORDER BY ba.position, ba.role
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<BookAuthor>> = HashMap::new();
+        for row in rows {
+            res.entry(row.book_id)
+                .or_default()
+                .push(BookAuthor::try_from(row)?);
+        }
+
+        Ok(res)
+    }
+}
+
+/// 蔵書一覧の絞り込み条件と並び順に応じて、対象の蔵書 ID を取得するクエリを組み立てる。
+/// 並び替えの列は `BookSortKey` から決まる固定の列名のみを埋め込み、値はすべてバインドする。
+fn build_book_list_query(options: &BookListOptions) -> QueryBuilder<Postgres> {
+    let mut builder = QueryBuilder::new(
+        r#"
+            SELECT
+                COUNT(*) OVER() AS total,
+                b.book_id AS id
+            FROM books AS b
+            WHERE TRUE
+        "#,
+    );
+    push_book_filters(&mut builder, options);
+
+    builder.push(" ORDER BY ");
+    if let Some((key, order)) = BookOrderKey::from_options(options) {
+        key.push(&mut builder, "b");
+        builder.push(format_args!(" {}, ", order_sql(order)));
+    }
+    builder
+        .push("b.created_at DESC, b.book_id LIMIT ")
+        .push_bind(options.limit)
+        .push(" OFFSET ")
+        .push_bind(options.offset);
+
+    builder
+}
+
+/// カーソル方式のページングで、対象の蔵書 ID と並び替えの基準の値を `limit + 1` 件まで取得するクエリを組み立てる。
+/// 並び替えの基準と蔵書 ID の組をキーとして、カーソルが指す位置より前または後ろを取得する。
+/// カーソルが持つ並び替えの基準の値と比べるため、カーソルが指す蔵書が削除されていても位置を特定できる。
+/// 前のページを取得する場合は逆順に並ぶため、呼び出し側で並べ直す必要がある。
+fn build_book_cursor_query(
+    options: &BookListOptions,
+    cursor: Option<&Cursor>,
+    position: Option<&CursorPosition>,
+) -> QueryBuilder<Postgres> {
+    let (key, order) = BookOrderKey::from_options(options)
+        .unwrap_or((BookOrderKey::Column("created_at"), SortOrder::Desc));
+    let order = match (cursor.map(|c| c.direction), order) {
+        (Some(CursorDirection::Prev), SortOrder::Asc) => SortOrder::Desc,
+        (Some(CursorDirection::Prev), SortOrder::Desc) => SortOrder::Asc,
+        (_, order) => order,
+    };
+
+    let mut builder = QueryBuilder::new(" SELECT b.book_id AS id, (");
+    key.push(&mut builder, "b");
+    builder.push(")::text AS sort_value FROM books AS b WHERE TRUE");
+    push_book_filters(&mut builder, options);
+
+    if let (Some(cursor), Some(position)) = (cursor, position) {
+        builder.push(" AND (");
+        key.push(&mut builder, "b");
+        builder
+            .push(match order {
+                SortOrder::Asc => ", b.book_id) > (CAST(",
+                SortOrder::Desc => ", b.book_id) < (CAST(",
+            })
+            .push_bind(position.sort_value.as_str())
+            .push(format_args!(" AS {}), ", key.sql_type()))
+            .push_bind(cursor.key)
+            .push(")");
+    }
+
+    builder.push(" ORDER BY ");
+    key.push(&mut builder, "b");
+    builder
+        .push(format_args!(
+            " {order}, b.book_id {order} LIMIT ",
+            order = order_sql(order)
+        ))
+        .push_bind(options.limit + 1);
+
+    builder
+}
+
+fn push_book_filters(builder: &mut QueryBuilder<Postgres>, options: &BookListOptions) {
+    builder.push(if options.deleted {
+        " AND b.deleted_at IS NOT NULL"
+    } else {
+        " AND b.deleted_at IS NULL"
+    });
+    // キーワードが指定された場合は、すべての語を含む蔵書に絞り込む
+    if let Some(query) = &options.query {
+        builder
+            .push(" AND (b.title || ' ' || b.author || ' ' || b.isbn || ' ' || b.description) ILIKE ALL(")
+            .push_bind(to_like_patterns(query))
+            .push(")");
+    }
+    if let Some(author) = &options.author {
+        builder
+            .push(" AND b.author ILIKE ")
+            .push_bind(to_like_pattern(author));
+    }
+    if let Some(owner) = options.owner {
+        builder.push(" AND b.user_id = ").push_bind(owner);
+    }
+    if let Some(location) = options.location {
+        builder
+            .push(
+                r#"
+                    AND b.location_id IN (
+                        WITH RECURSIVE descendants AS (
+                            SELECT location_id FROM locations WHERE location_id = "#,
+            )
+            .push_bind(location)
+            .push(
+                r#"
+                            UNION ALL
+                            SELECT l.location_id
+                            FROM locations AS l
+                            INNER JOIN descendants AS d ON l.parent_id = d.location_id
+                        )
+                        SELECT location_id FROM descendants
+                    )
+                "#,
+            );
+    }
+    match options.availability {
+        Some(BookAvailability::Available) => {
+            builder.push(" AND EXISTS (");
+            push_available_copy(builder);
+            builder.push(")");
+        }
+        // 貸出可能な状態の蔵書のうち、貸出中の現物があり、貸し出せる現物が残っていないもの
+        Some(BookAvailability::CheckedOut) => {
+            builder
+                .push(" AND b.status = ")
+                .push_bind(BookStatus::Available.as_ref())
+                .push(" AND EXISTS (SELECT 1 FROM checkouts AS c WHERE c.book_id = b.book_id)")
+                .push(" AND NOT EXISTS (");
+            push_available_copy(builder);
+            builder.push(")");
+        }
+        Some(BookAvailability::Unavailable) => {
+            builder
+                .push(" AND b.status <> ")
+                .push_bind(BookStatus::Available.as_ref());
+        }
+        None => {}
+    }
+    // 指定されたタグのうち、蔵書に付いていないものが 1 つもない蔵書に絞り込む
+    if !options.tags.is_empty() {
+        builder
+            .push(
+                r#" AND NOT EXISTS (
+                    SELECT 1 FROM UNNEST("#,
+            )
+            .push_bind(options.tags.clone())
+            .push(
+                r#"::text[]) AS q(name)
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM book_tags AS bt
+                        INNER JOIN tags AS t USING(tag_id)
+                        WHERE bt.book_id = b.book_id
+                        AND lower(t.name) = lower(q.name)
+                    )
+                )"#,
+            );
+    }
+    if let Some(created_from) = options.created_from {
+        builder
+            .push(" AND b.created_at >= ")
+            .push_bind(created_from);
+    }
+    if let Some(created_to) = options.created_to {
+        builder.push(" AND b.created_at < ").push_bind(created_to);
+    }
+}
+
+/// 蔵書と、指定された冊数の現物を登録する。
+async fn insert_book(
+    conn: &mut PgConnection,
+    event: CreateBook,
+    user_id: UserId,
+) -> AppResult<BookId> {
+    let book_id = sqlx::query!(
+        r#"
+            INSERT INTO books (title, author, isbn, description, user_id)
+            VALUES($1, $2, $3, $4, $5)
+            RETURNING book_id AS "book_id: BookId"
+        "#,
+        event.title,
+        event.author,
+        event.isbn as _,
+        event.description,
+        user_id as _
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .book_id;
+
+    insert_copies(conn, book_id, event.copies).await?;
+
+    link_authors(conn, book_id, &event.author).await?;
+
+    insert_revision(conn, book_id, user_id, BookRevisionAction::Create, None).await?;
+
+    Ok(book_id)
+}
+
+async fn insert_copies(conn: &mut PgConnection, book_id: BookId, copies: u32) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO book_copies (book_id)
+            SELECT $1 FROM generate_series(1, $2::bigint)
+        "#,
+        book_id as _,
+        i64::from(copies)
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 同じ所有者の蔵書の登録をトランザクションの終了まで直列化する。
+/// 重複の確認は行をロックしない SELECT で行うため、同時に登録された蔵書どうしの重複を見逃さないようにする。
+/// 書名と著者表記の類似でも重複を判定するため、ISBN ではなく所有者単位でロックする。
+async fn lock_owner_registrations(conn: &mut PgConnection, user_id: UserId) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            SELECT pg_advisory_xact_lock(hashtext('book_registration:' || $1::text))
+        "#,
+        user_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 登録しようとしている蔵書と重複する、同じ所有者の除籍されていない蔵書を探す。
+/// ISBN が同じもの、または書名と著者表記がともによく似ているものを重複とみなし、ISBN が同じものを優先する。
+async fn find_duplicated_book(
+    conn: &mut PgConnection,
+    event: &CreateBook,
+    user_id: UserId,
+) -> AppResult<Option<BookId>> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT book_id AS "book_id: BookId"
+            FROM books
+            WHERE user_id = $1
+            AND deleted_at IS NULL
+            AND (
+                isbn = $2
+                OR (
+                    similarity(lower(title), lower($3)) >= $5
+                    AND similarity(lower(author), lower($4)) >= $6
+                )
+            )
+            ORDER BY isbn = $2 DESC, similarity(lower(title), lower($3)) DESC, created_at
+            LIMIT 1
+        "#,
+        user_id as _,
+        event.isbn as _,
+        event.title,
+        event.author,
+        DUPLICATE_TITLE_SIMILARITY,
+        DUPLICATE_AUTHOR_SIMILARITY
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)
+}
+
+/// 著者表記を個々の著者名に分割し、著者として蔵書に関連付ける。
+/// 未登録の著者は作成し、大文字・小文字だけが異なる名前は同じ著者として扱う。
+async fn link_authors(conn: &mut PgConnection, book_id: BookId, author: &str) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO authors (name)
+            SELECT DISTINCT ON (lower(name)) name
+            FROM split_author_names($1)
+            ORDER BY lower(name), ord
+            ON CONFLICT DO NOTHING
+        "#,
+        author
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO book_authors (book_id, author_id, role, position)
+            SELECT $1, a.author_id, $3, MIN(s.ord)
+            FROM split_author_names($2) AS s
+            INNER JOIN authors AS a ON lower(a.name) = lower(s.name)
+            GROUP BY a.author_id
+        "#,
+        book_id as _,
+        author,
+        AuthorRole::Author.as_ref()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 著者表記が変更された蔵書の著者の関連を、新しい表記から作り直す。
+/// 著者の関連と著者表記が食い違わないよう、著者表記を更新するトランザクションの中で呼び出す。
+async fn relink_authors(conn: &mut PgConnection, book_id: BookId, author: &str) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM book_authors WHERE book_id = $1
+        "#,
+        book_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    link_authors(conn, book_id, author).await
+}
+
+/// ISBN を正規化できなかった蔵書として記録されていれば、記録を削除する。
+/// 更新後の ISBN は `Isbn` として検証済みのため、ISBN を更新した場合に呼び出す。
+async fn clear_invalid_isbn(conn: &mut PgConnection, book_id: BookId) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM invalid_isbns WHERE book_id = $1
+        "#,
+        book_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 更新する蔵書の行をロックし、変更前の書誌情報を取得する。
+/// 版数が一致しない場合は `AppError::PreconditionFailed` を返す。
+async fn lock_book_fields(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    user_id: UserId,
+    is_admin: bool,
+    version: i64,
+) -> AppResult<BookFieldsRow> {
+    let row = sqlx::query!(
+        r#"
+            SELECT
+                title,
+                author,
+                isbn AS "isbn: Isbn",
+                description,
+                user_id AS "owner_id: UserId",
+                version
+            FROM books
+            WHERE book_id = $1
+            AND deleted_at IS NULL
+            FOR UPDATE
+        "#,
+        book_id as _
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+    authorize_book_owner(
+        conn,
+        book_id,
+        row.owner_id,
+        user_id,
+        is_admin,
+        BookRevisionAction::Update,
+    )
+    .await?;
+
+    if row.version != version {
+        return Err(outdated_version_error(book_id));
+    }
+
+    Ok(BookFieldsRow {
+        title: row.title,
+        author: row.author,
+        isbn: row.isbn,
+        description: row.description,
+    })
+}
+
+/// 蔵書を操作できるのは所有者と管理者のみ。
+/// 管理者が所有者以外の蔵書を操作する場合は、同じトランザクションで監査ログに記録する。
+pub(crate) async fn authorize_book_owner(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    owner_id: UserId,
+    user_id: UserId,
+    is_admin: bool,
+    action: BookRevisionAction,
+) -> AppResult<()> {
+    if owner_id == user_id {
+        return Ok(());
+    }
+    if !is_admin {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    insert_audit_log(conn, book_id, owner_id, user_id, action).await
+}
+
+/// 蔵書の行をロックし、所有者または管理者であることを確認する。
+/// 書誌情報以外（現物・タグ・書影・配架場所など）を変更する前に呼び出す。
+/// 変更後の蔵書の ETag が変わるよう、あわせて版数を更新する。
+pub(crate) async fn lock_book_owner(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    user_id: UserId,
+    is_admin: bool,
+) -> AppResult<()> {
+    let owner_id = sqlx::query_scalar!(
+        r#"
+            UPDATE books
+            SET version = version + 1
+            WHERE book_id = $1
+            AND deleted_at IS NULL
+            RETURNING user_id AS "owner_id: UserId"
+        "#,
+        book_id as _
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+    authorize_book_owner(
+        conn,
+        book_id,
+        owner_id,
+        user_id,
+        is_admin,
+        BookRevisionAction::Update,
+    )
+    .await
+}
+
+async fn insert_audit_log(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    owner_id: UserId,
+    user_id: UserId,
+    action: BookRevisionAction,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO book_audit_logs (book_id, owner_id, user_id, action)
+            VALUES ($1, $2, $3, $4)
+        "#,
+        book_id as _,
+        owner_id as _,
+        user_id as _,
+        action.as_ref()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// ロックした蔵書の所有者を変更し、譲渡履歴と変更履歴に記録する。更新後の版数を返す。
+async fn transfer_book(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    owner_id: UserId,
+    new_owner: UserId,
+    user_id: UserId,
+) -> AppResult<i64> {
+    // 貸出は蔵書 ID で管理しているため、所有者を変更しても貸出中のまま引き継がれる
+    sqlx::query!(
+        r#"
+            UPDATE books
+            SET user_id = $1, version = version + 1
+            WHERE book_id = $2
+        "#,
+        new_owner as _,
+        book_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO book_transfers (book_id, from_user_id, to_user_id, user_id)
+            VALUES ($1, $2, $3, $4)
+        "#,
+        book_id as _,
+        owner_id as _,
+        new_owner as _,
+        user_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    insert_revision(conn, book_id, user_id, BookRevisionAction::Transfer, None).await
+}
+
+/// ロックした蔵書の状態を変更し、状態の変更履歴と変更履歴に記録する。更新後の版数を返す。
+pub(crate) async fn change_book_status(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    from: BookStatus,
+    to: BookStatus,
+    reason: Option<&str>,
+    user_id: UserId,
+) -> AppResult<i64> {
+    sqlx::query!(
+        r#"
+            UPDATE books
+            SET status = $1, status_changed_at = CURRENT_TIMESTAMP(3), version = version + 1
+            WHERE book_id = $2
+        "#,
+        to.as_ref(),
+        book_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO book_status_histories (book_id, from_status, to_status, reason, user_id)
+            VALUES ($1, $2, $3, $4, $5)
+        "#,
+        book_id as _,
+        from.as_ref(),
+        to.as_ref(),
+        reason,
+        user_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    insert_revision(
+        conn,
+        book_id,
+        user_id,
+        BookRevisionAction::ChangeStatus,
+        None,
+    )
+    .await
+}
+
+/// 状態のエラーメッセージ向けの表記
+pub(crate) fn status_label(status: BookStatus) -> &'static str {
+    match status {
+        BookStatus::Available => "貸出可能",
+        BookStatus::InRepair => "修理中",
+        BookStatus::Lost => "紛失",
+        BookStatus::Suspended => "貸出停止",
+    }
+}
+
+/// 譲渡先のユーザーが存在することを確かめ、確認後に削除されないようロックする。
+async fn ensure_user_exists(conn: &mut PgConnection, user_id: UserId) -> AppResult<()> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT user_id FROM users WHERE user_id = $1 FOR SHARE
+        "#,
+        user_id as _
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| {
+        AppError::UnprocessableEntity(format!("ユーザー ({user_id}) は存在しません。"))
+    })?;
+
+    Ok(())
+}
+
+/// 操作後の蔵書の内容を変更履歴に記録し、その時点の版数を返す。
+pub(crate) async fn insert_revision(
+    conn: &mut PgConnection,
+    book_id: BookId,
+    user_id: UserId,
+    action: BookRevisionAction,
+    old: Option<BookFieldsRow>,
+) -> AppResult<i64> {
+    let old = old.as_ref();
+    sqlx::query_scalar!(
+        r#"
+            INSERT INTO book_revisions (
+                book_id, version, action, user_id,
+                old_title, old_author, old_isbn, old_description,
+                new_title, new_author, new_isbn, new_description
+            )
+            SELECT
+                book_id, version, $2, $3,
+                $4, $5, $6, $7,
+                title, author, isbn, description
+            FROM books
+            WHERE book_id = $1
+            RETURNING version
+        "#,
+        book_id as _,
+        action.as_ref(),
+        user_id as _,
+        old.map(|o| o.title.as_str()),
+        old.map(|o| o.author.as_str()),
+        old.map(|o| o.isbn.as_str()),
+        old.map(|o| o.description.as_str())
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)
+}
+
+/// 蔵書 `b` の現物のうち、貸し出せるものを取得するサブクエリを組み立てる。
+/// 紛失した現物と、蔵書が貸出可能な状態でない場合は貸出中でない現物も含めない。
+fn push_available_copy(builder: &mut QueryBuilder<Postgres>) {
+    builder
+        .push(
+            r#"
+                SELECT 1 FROM book_copies AS bc
+                WHERE bc.book_id = b.book_id
+                AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+                AND bc.lost_at IS NULL
+                AND b.status = "#,
+        )
+        .push_bind(BookStatus::Available.as_ref());
+}
+
+/// 蔵書一覧の並び替えの基準
+enum BookOrderKey<'a> {
+    Column(&'static str),
+    /// キーワード検索の関連度。
+    /// 書名 > 著者名・ISBN > 説明の順に重み付けした類似度で表す。
+    Relevance(&'a str),
+    /// レビューの評価の平均。レビューのない蔵書は 0 とする。
+    Rating,
+}
+
+impl<'a> BookOrderKey<'a> {
+    /// 並び替えの指定がない場合、キーワード検索であれば関連度の高い順とする。
+    fn from_options(options: &'a BookListOptions) -> Option<(Self, SortOrder)> {
+        match (&options.sort, &options.query) {
+            (Some(BookSort { key, order }), _) => {
+                let key = match key {
+                    BookSortKey::Title => Self::Column("title"),
+                    BookSortKey::Author => Self::Column("author"),
+                    BookSortKey::CreatedAt => Self::Column("created_at"),
+                    BookSortKey::Rating => Self::Rating,
+                };
+                Some((key, *order))
+            }
+            (None, Some(query)) => Some((Self::Relevance(query), SortOrder::Desc)),
+            (None, None) => None,
+        }
+    }
+
+    /// 並び替えの基準の値の型。カーソルに含めた文字列表現を元の型に戻して比べるために使う。
+    fn sql_type(&self) -> &'static str {
+        match self {
+            Self::Column("created_at") => "timestamptz",
+            Self::Column(_) => "text",
+            Self::Relevance(_) => "real",
+            Self::Rating => "numeric",
+        }
+    }
+
+    fn push(&self, builder: &mut QueryBuilder<Postgres>, alias: &str) {
+        match self {
+            Self::Column(column) => {
+                builder.push(format_args!("{alias}.{column}"));
+            }
+            Self::Relevance(query) => {
+                builder
+                    .push("(word_similarity(")
+                    .push_bind(*query)
+                    .push(format_args!(", {alias}.title) * 4 + word_similarity("))
+                    .push_bind(*query)
+                    .push(format_args!(", {alias}.author) * 2 + word_similarity("))
+                    .push_bind(*query)
+                    .push(format_args!(", {alias}.isbn) * 2 + word_similarity("))
+                    .push_bind(*query)
+                    .push(format_args!(", {alias}.description))"));
+            }
+            Self::Rating => {
+                builder.push(format_args!(
+                    "COALESCE((SELECT AVG(r.rating) FROM book_reviews AS r WHERE r.book_id = {alias}.book_id), 0)"
+                ));
+            }
+        }
+    }
+}
+
+fn order_sql(order: SortOrder) -> &'static str {
+    match order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    }
+}
+
+/// 空白区切りの各語を、`ILIKE` で部分一致させるためのパターンに変換する。
+/// ISBN として解釈できる語は、保存形式に合わせて正規化してから検索する。
+fn to_like_patterns(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|term| match Isbn::from_str(term) {
+            Ok(isbn) => to_like_pattern(isbn.as_str()),
+            Err(_) => to_like_pattern(term),
+        })
+        .collect()
+}
+
+fn to_like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::ConnectionPool;
+    use crate::repository::book::{BookRepositoryImpl, STREAM_BATCH_SIZE};
+    use crate::repository::user::UserRepositoryImpl;
+    use kernel::model::book::event::{
+        ChangeBookStatus, CreateBook, CreateBookCopy, DeleteBook, DeleteBookCopy, ImportBook,
+        ImportBooks, PatchBook, PurgeBook, RestoreBook, RevertBook, TransferBook, TransferBooks,
+        UpdateBook,
+    };
+    use kernel::model::book::revision::BookRevisionAction;
+    use kernel::model::book::{
+        Book, BookAvailability, BookImportStatus, BookListOptions, BookRegistration, BookSort,
+        BookSortKey, BookStatus, DuplicateBookPolicy,
+    };
+    use kernel::model::id::{BookCopyId, BookId, UserId};
+    use kernel::model::isbn::Isbn;
+    use kernel::model::list::{Cursor, SortOrder};
+    use kernel::model::user::event::CreateUser;
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use kernel::repository::user::UserRepository;
+    use shared::error::{AppError, AppResult};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+
+    #[sqlx::test]
+    async fn test_register_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        sqlx::query!(r#"INSERT INTO roles(name) VALUES ('Admin'), ('User');"#)
+            .execute(&pool)
+            .await?;
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+
+        let user = user_repo
+            .create(CreateUser {
+                name: "Test User".into(),
+                email: "test@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+
+        let book = CreateBook {
+            title: "Test Title".into(),
+            author: "Test Author".into(),
+            isbn: Isbn::from_str("978-4-7980-6170-2")?,
+            description: "Test Description".into(),
+            copies: 2,
+            on_duplicate: DuplicateBookPolicy::Reject,
+        };
+        repo.create(book, user.id).await?;
+
+        let options = BookListOptions {
+            limit: 20,
+            offset: 0,
+            ..Default::default()
+        };
+        let res = repo.find_all(options).await?;
+        assert_eq!(res.items.len(), 1);
+
+        let book_id = res.items[0].id;
+        let res = repo.find_by_id(book_id).await?;
+        assert!(res.is_some());
+
+        let Book {
+            id,
+            title,
+            author,
+            isbn,
+            description,
+            owner,
+            total_copies,
+            available_copies,
+            ..
+        } = res.unwrap();
+        assert_eq!(id, book_id);
         assert_eq!(title, "Test Title");
         assert_eq!(author, "Test Author");
-        assert_eq!(isbn, "Test ISBN");
+        assert_eq!(isbn.as_str(), "9784798061702");
         assert_eq!(description, "Test Description");
         assert_eq!(owner.name, "Test User");
+        assert_eq!(total_copies, 2);
+        assert_eq!(available_copies, 2);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_create_duplicated_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let existing = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let create = |title: &str, author: &str, isbn: &str, on_duplicate| -> anyhow::Result<_> {
+            Ok(CreateBook {
+                title: title.into(),
+                author: author.into(),
+                isbn: Isbn::from_str(isbn)?,
+                description: "".into(),
+                copies: 2,
+                on_duplicate,
+            })
+        };
+        let conflicted_id = |res: AppResult<BookRegistration>| match res {
+            Err(AppError::Conflict { existing_id, .. }) => Some(existing_id),
+            _ => None,
+        };
+
+        // ISBN-10 で指定しても、正規化した ISBN が同じなら重複とみなす
+        let res = repo
+            .create(
+                create(
+                    "別の書名",
+                    "別の著者",
+                    "4798061700",
+                    DuplicateBookPolicy::Reject,
+                )?,
+                owner,
+            )
+            .await;
+        assert_eq!(conflicted_id(res), Some(existing.to_string()));
+
+        // ISBN が異なっても、書名と著者表記がよく似ていれば重複とみなす
+        let res = repo
+            .create(
+                create(
+                    "実践Rustプログラミング入門 ",
+                    "初田直也",
+                    "978-4-297-10033-9",
+                    DuplicateBookPolicy::Reject,
+                )?,
+                owner,
+            )
+            .await;
+        assert_eq!(conflicted_id(res), Some(existing.to_string()));
+
+        // 他のユーザーの蔵書とは重複しない
+        let other = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()))
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?
+            .id;
+        let res = repo
+            .create(
+                create(
+                    "実践Rustプログラミング入門",
+                    "初田直也他",
+                    "9784798061702",
+                    DuplicateBookPolicy::Reject,
+                )?,
+                other,
+            )
+            .await?;
+        assert!(matches!(res, BookRegistration::Created(_)));
+
+        let res = repo
+            .create(
+                create(
+                    "実践Rustプログラミング入門",
+                    "初田直也他",
+                    "9784798061702",
+                    DuplicateBookPolicy::AddCopy,
+                )?,
+                owner,
+            )
+            .await?;
+        assert_eq!(res, BookRegistration::CopyAdded(existing));
+        let book = repo.find_by_id(existing).await?.unwrap();
+        assert_eq!(book.total_copies, 3);
+
+        let res = repo
+            .create(
+                create(
+                    "実践Rustプログラミング入門",
+                    "初田直也他",
+                    "9784798061702",
+                    DuplicateBookPolicy::Allow,
+                )?,
+                owner,
+            )
+            .await?;
+        let BookRegistration::Created(book_id) = res else {
+            panic!("expected a new book, got {res:?}");
+        };
+        assert_ne!(book_id, existing);
+
+        // 同時に登録しても、重複の確認をすり抜けて両方が登録されることはない
+        let isbn = "9784297141738";
+        let (a, b) = tokio::join!(
+            repo.create(
+                create("並行登録", "著者", isbn, DuplicateBookPolicy::Reject)?,
+                owner
+            ),
+            repo.create(
+                create("並行登録", "著者", isbn, DuplicateBookPolicy::Reject)?,
+                owner
+            ),
+        );
+        let results = [a, b];
+        assert_eq!(
+            results
+                .iter()
+                .filter(|r| matches!(r, Ok(BookRegistration::Created(_))))
+                .count(),
+            1
+        );
+        assert_eq!(
+            results
+                .iter()
+                .filter(|r| matches!(r, Err(AppError::Conflict { .. })))
+                .count(),
+            1
+        );
 
         Ok(())
     }
 
     #[sqlx::test(fixtures("common", "book"))]
     async fn test_update_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
         let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
         let book = repo.find_by_id(book_id).await?.unwrap();
         const NEW_AUTHOR: &str = "更新後の著者名";
         assert_ne!(book.author, NEW_AUTHOR);
 
-        let update_book = UpdateBook {
-            book_id: book.id,
-            title: book.title,
-            author: NEW_AUTHOR.into(),
-            isbn: book.isbn,
-            description: book.description,
-            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?,
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let update_book = |book_id, author: &str, version| UpdateBook {
+            book_id,
+            title: book.title.clone(),
+            author: author.into(),
+            isbn: book.isbn.clone(),
+            description: book.description.clone(),
+            requested_user: owner,
+            is_admin: false,
+            version,
+        };
+        let version = repo
+            .update(update_book(book_id, NEW_AUTHOR, book.version))
+            .await?;
+        assert_eq!(version, book.version + 1);
+
+        let updated = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(updated.author, NEW_AUTHOR);
+        assert_eq!(updated.version, version);
+
+        // 取得後に更新された蔵書は更新できない
+        let res = repo
+            .update(update_book(book_id, "古い版数での更新", book.version))
+            .await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+        let res = repo
+            .update(update_book(BookId::new(), NEW_AUTHOR, version))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 所有者以外は、管理者でなければ更新できない
+        let other = UserId::new();
+        let res = repo
+            .update(UpdateBook {
+                requested_user: other,
+                ..update_book(book_id, "所有者以外による更新", version)
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let count_audit_logs = || async {
+            sqlx::query_scalar!(
+                r#"
+                    SELECT COUNT(*) AS "count!"
+                    FROM book_audit_logs
+                    WHERE book_id = $1 AND user_id = $2 AND owner_id = $3
+                "#,
+                book_id as _,
+                other as _,
+                owner as _
+            )
+            .fetch_one(&pool)
+            .await
+        };
+        assert_eq!(count_audit_logs().await?, 0);
+
+        // 管理者による所有者以外の蔵書の更新は、監査ログに記録される
+        repo.update(UpdateBook {
+            requested_user: other,
+            is_admin: true,
+            ..update_book(book_id, "管理者による更新", version)
+        })
+        .await?;
+        assert_eq!(
+            repo.find_by_id(book_id).await?.unwrap().author,
+            "管理者による更新"
+        );
+        assert_eq!(count_audit_logs().await?, 1);
+
+        // 所有者自身の更新は記録されない
+        let version = repo.find_by_id(book_id).await?.unwrap().version;
+        repo.update(update_book(book_id, NEW_AUTHOR, version))
+            .await?;
+        assert_eq!(count_audit_logs().await?, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_patch_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        let patch = |author: &str, version| PatchBook {
+            book_id,
+            title: None,
+            author: Some(author.into()),
+            isbn: None,
+            description: None,
+            requested_user: owner,
+            is_admin: false,
+            version,
+        };
+
+        let version = repo.patch(patch("更新後の著者名", book.version)).await?;
+        assert_eq!(version, book.version + 1);
+
+        // 指定しなかった項目は変更されない
+        let patched = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(patched.author, "更新後の著者名");
+        // 著者表記を変更すると、著者の関連も新しい表記から作り直される
+        let authors = patched
+            .authors
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(authors, ["更新後の著者名"]);
+        assert_eq!(patched.title, book.title);
+        assert_eq!(patched.isbn, book.isbn);
+        assert_eq!(patched.description, book.description);
+
+        let res = repo.patch(patch("古い版数での更新", book.version)).await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_record_and_revert_book_revisions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        let patch = |title: &str, version| PatchBook {
+            book_id,
+            title: Some(title.into()),
+            author: None,
+            isbn: None,
+            description: None,
+            requested_user: owner,
+            is_admin: false,
+            version,
+        };
+
+        let version = repo.patch(patch("1 回目の更新", book.version)).await?;
+        repo.patch(patch("2 回目の更新", version)).await?;
+        repo.delete(DeleteBook {
+            book_id,
+            requested_user: owner,
+            is_admin: false,
+            force: false,
+        })
+        .await?;
+        repo.restore(RestoreBook {
+            book_id,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+
+        let revisions = repo.find_revisions(book_id).await?;
+        let actions = revisions.iter().map(|r| r.action).collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            [
+                BookRevisionAction::Restore,
+                BookRevisionAction::Delete,
+                BookRevisionAction::Update,
+                BookRevisionAction::Update,
+            ]
+        );
+        // 同じ時刻に記録した履歴も、記録した順に並ぶ
+        sqlx::query!(
+            r#"
+                UPDATE book_revisions SET created_at = '2026-10-18T00:00:00Z' WHERE book_id = $1
+            "#,
+            book_id as _
+        )
+        .execute(&pool)
+        .await?;
+        let reordered = repo.find_revisions(book_id).await?;
+        assert_eq!(
+            reordered.iter().map(|r| r.id).collect::<Vec<_>>(),
+            revisions.iter().map(|r| r.id).collect::<Vec<_>>()
+        );
+        let first = &revisions[3];
+        assert_eq!(first.version, book.version + 1);
+        assert_eq!(first.changed_by.id, owner);
+        assert!(first.changed_by.name.is_some());
+        assert_eq!(first.old.as_ref().unwrap().title, book.title);
+        assert_eq!(first.new.title, "1 回目の更新");
+        assert!(revisions[0].old.is_none());
+
+        // 1 回目の更新の時点に戻す
+        let admin = UserId::new();
+        let version = repo
+            .revert(RevertBook {
+                book_id,
+                revision_id: first.id,
+                requested_user: admin,
+            })
+            .await?;
+        let reverted = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(reverted.title, "1 回目の更新");
+        assert_eq!(reverted.version, version);
+
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 5);
+        assert_eq!(revisions[0].action, BookRevisionAction::Revert);
+        assert_eq!(revisions[0].changed_by.id, admin);
+        // 存在しないユーザーの名前は取得できない
+        assert!(revisions[0].changed_by.name.is_none());
+        assert_eq!(revisions[0].old.as_ref().unwrap().title, "2 回目の更新");
+
+        let res = repo
+            .revert(RevertBook {
+                book_id: BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?,
+                revision_id: first.id,
+                requested_user: admin,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_delete_restore_and_purge_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other_book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let options = |deleted| BookListOptions {
+            limit: 20,
+            deleted,
+            ..Default::default()
+        };
+        let count_checkouts = |pool: sqlx::PgPool| async move {
+            sqlx::query_scalar!(
+                r#"
+                    SELECT
+                        (SELECT COUNT(*) FROM checkouts WHERE book_id = $1)
+                        + (SELECT COUNT(*) FROM returned_checkouts WHERE book_id = $1)
+                        AS "count!"
+                "#,
+                book_id as _
+            )
+            .fetch_one(&pool)
+            .await
+        };
+
+        // 貸出中の蔵書は除籍できない
+        let delete = |requested_user, is_admin, force| DeleteBook {
+            book_id,
+            requested_user,
+            is_admin,
+            force,
+        };
+        let res = repo.delete(delete(owner, false, false)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        assert!(repo.find_by_id(book_id).await?.is_some());
+
+        // 所有者以外は、管理者でなければ除籍できない
+        let res = repo.delete(delete(UserId::new(), false, false)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        // 管理者による強制除籍では、貸出中の貸出は返却済みとして記録される
+        repo.delete(delete(UserId::new(), true, true)).await?;
+
+        // 除籍した蔵書は取得できないが、貸出履歴は残る
+        assert!(repo.find_by_id(book_id).await?.is_none());
+        assert_eq!(repo.find_all(options(false)).await?.total, 2);
+        let deleted = repo.find_all(options(true)).await?;
+        assert_eq!(deleted.total, 1);
+        assert_eq!(deleted.items[0].id, book_id);
+        assert!(deleted.items[0].deleted_at.is_some());
+        assert!(deleted.items[0].checkouts.is_empty());
+        assert_eq!(count_checkouts(pool.clone()).await?, 4);
+
+        // 除籍済みの蔵書は再度除籍できない
+        let res = repo.delete(delete(owner, false, false)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 所有者以外は、管理者でなければ復元できない
+        let restore = |requested_user, is_admin| RestoreBook {
+            book_id,
+            requested_user,
+            is_admin,
+        };
+        let res = repo.restore(restore(UserId::new(), false)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.restore(restore(UserId::new(), true)).await?;
+        assert!(repo.find_by_id(book_id).await?.is_some());
+
+        // 除籍されていない蔵書は完全には削除できない
+        let res = repo
+            .purge(PurgeBook {
+                book_id: other_book_id,
+                requested_user: owner,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        repo.delete(delete(owner, false, false)).await?;
+        let admin = UserId::new();
+        repo.purge(PurgeBook {
+            book_id,
+            requested_user: admin,
+        })
+        .await?;
+        assert_eq!(repo.find_all(options(true)).await?.total, 0);
+        assert_eq!(count_checkouts(pool.clone()).await?, 0);
+
+        // 完全に削除した後も、管理者による除籍・復元・削除は監査ログに残る
+        let logs = repo.find_audit_logs(book_id).await?;
+        assert_eq!(logs.len(), 3);
+        assert!(logs.iter().all(|l| l.owner_id == owner));
+        assert!(
+            logs.iter()
+                .any(|l| l.action == BookRevisionAction::Purge && l.operated_by.id == admin)
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_transfer_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let new_owner = user_repo
+            .create(CreateUser {
+                name: "New Owner".into(),
+                email: "new.owner@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?
+            .id;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert!(!book.checkouts.is_empty());
+        let transfer = |requested_user, new_owner| TransferBook {
+            book_id,
+            new_owner,
+            requested_user,
+            is_admin: false,
+        };
+
+        // 所有者以外は、管理者でなければ譲渡できない
+        let res = repo.transfer(transfer(new_owner, new_owner)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        // 存在しないユーザーには譲渡できない
+        let res = repo.transfer(transfer(owner, UserId::new())).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 貸出中の貸出はそのまま引き継がれる
+        let version = repo.transfer(transfer(owner, new_owner)).await?;
+        let transferred = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(transferred.owner.id, new_owner);
+        assert_eq!(transferred.version, version);
+        assert_eq!(transferred.checkouts.len(), book.checkouts.len());
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions[0].action, BookRevisionAction::Transfer);
+
+        let res = repo.transfer(transfer(new_owner, new_owner)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 残りの蔵書を、除籍した蔵書も含めてまとめて譲渡する
+        let other_book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        repo.delete(DeleteBook {
+            book_id: other_book_id,
+            requested_user: owner,
+            is_admin: false,
+            force: false,
+        })
+        .await?;
+        let transfer_all = |requested_user, is_admin| TransferBooks {
+            owner,
+            new_owner,
+            requested_user,
+            is_admin,
+        };
+        let res = repo.transfer_all(transfer_all(new_owner, false)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let count = repo.transfer_all(transfer_all(UserId::new(), true)).await?;
+        assert_eq!(count, 2);
+
+        let owned = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                owner: Some(new_owner),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(owned.total, 2);
+        let owned = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                owner: Some(new_owner),
+                deleted: true,
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(owned.total, 1);
+        assert_eq!(repo.transfer_all(transfer_all(owner, false)).await?, 0);
+
+        let transfers = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM book_transfers
+                WHERE from_user_id = $1 AND to_user_id = $2
+            "#,
+            owner as _,
+            new_owner as _
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(transfers, 3);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_change_book_status(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        // 貸出中の現物がない蔵書
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let checked_out_book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let change = |book_id, status, requested_user| ChangeBookStatus {
+            book_id,
+            status,
+            reason: Some("棚にない".into()),
+            requested_user,
+            is_admin: false,
+        };
+
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.status, BookStatus::Available);
+        assert!(book.status_changed_at.is_none());
+
+        // 所有者以外は、管理者でなければ変更できない
+        let res = repo
+            .change_status(change(book_id, BookStatus::Lost, UserId::new()))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        // 貸出中の現物がある蔵書は、貸出可能以外にできない
+        let res = repo
+            .change_status(change(checked_out_book_id, BookStatus::InRepair, owner))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let version = repo
+            .change_status(change(book_id, BookStatus::Lost, owner))
+            .await?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.status, BookStatus::Lost);
+        assert_eq!(book.version, version);
+        assert!(book.status_changed_at.is_some());
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions[0].action, BookRevisionAction::ChangeStatus);
+
+        // 同じ状態や、許可されていない状態には変更できない
+        for status in [BookStatus::Lost, BookStatus::InRepair] {
+            let res = repo.change_status(change(book_id, status, owner)).await;
+            assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        }
+
+        repo.change_status(change(book_id, BookStatus::Available, owner))
+            .await?;
+        let history = repo.find_status_history(book_id).await?;
+        let changes = history.iter().map(|h| (h.from, h.to)).collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                (BookStatus::Lost, BookStatus::Available),
+                (BookStatus::Available, BookStatus::Lost),
+            ]
+        );
+        assert_eq!(history[0].reason.as_deref(), Some("棚にない"));
+        assert_eq!(history[0].changed_by.id, owner);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_search_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let search = |query: &str| BookListOptions {
+            limit: 20,
+            offset: 0,
+            query: Some(query.into()),
+            ..Default::default()
+        };
+
+        let res = repo.find_all(search("Rust")).await?;
+        assert_eq!(res.total, 3);
+
+        let res = repo.find_all(search("システムプログラミング")).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(
+            res.items[0].id,
+            BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?
+        );
+
+        // 空白で区切られた語はすべてを含む蔵書のみが対象になる
+        let res = repo.find_all(search("Rust\u{3000}蔵書管理")).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(
+            res.items[0].id,
+            BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?
+        );
+
+        let res = repo.find_all(search("978-4798061702")).await?;
+        assert_eq!(res.total, 1);
+
+        let res = repo.find_all(search("100%")).await?;
+        assert_eq!(res.total, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_filter_and_sort_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let checked_out_book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        sqlx::query!(
+            "INSERT INTO checkouts (book_id, copy_id, user_id) VALUES ($1, $2, $3)",
+            checked_out_book_id as _,
+            BookCopyId::from_str("c0b1e5d0-0000-4000-8000-000000000001")? as _,
+            UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")? as _
+        )
+        .execute(&pool)
+        .await?;
+        let options = |f: fn(&mut BookListOptions)| {
+            let mut options = BookListOptions {
+                limit: 20,
+                ..Default::default()
+            };
+            f(&mut options);
+            options
+        };
+
+        let res = repo
+            .find_all(options(|o| {
+                o.sort = Some(BookSort {
+                    key: BookSortKey::Title,
+                    order: SortOrder::Asc,
+                })
+            }))
+            .await?;
+        let titles = res
+            .items
+            .iter()
+            .map(|b| b.title.as_str())
+            .collect::<Vec<_>>();
+        let mut sorted = titles.clone();
+        sorted.sort();
+        assert_eq!(titles, sorted);
+
+        let res = repo
+            .find_all(options(|o| o.author = Some("高野".into())))
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].author, "高野祐輝");
+
+        let res = repo
+            .find_all(options(|o| {
+                o.availability = Some(BookAvailability::CheckedOut)
+            }))
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, checked_out_book_id);
+        assert_eq!(res.items[0].checkouts.len(), 1);
+        assert_eq!(res.items[0].available_copies, 0);
+
+        let res = repo
+            .find_all(options(|o| {
+                o.availability = Some(BookAvailability::Available)
+            }))
+            .await?;
+        assert_eq!(res.total, 2);
+        assert!(res.items.iter().all(|b| b.checkouts.is_empty()));
+
+        let res = repo
+            .find_all(options(|o| o.owner = Some(UserId::new())))
+            .await?;
+        assert_eq!(res.total, 0);
+
+        let res = repo
+            .find_all(options(|o| {
+                o.created_to = Some(chrono::Utc::now() - chrono::Duration::days(1))
+            }))
+            .await?;
+        assert_eq!(res.total, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_deleted_book_copies_and_status_history_not_found(
+        pool: sqlx::PgPool,
+    ) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+
+        assert_eq!(repo.find_copies(book_id).await?.len(), 1);
+        assert!(repo.find_status_history(book_id).await?.is_empty());
+
+        repo.delete(DeleteBook {
+            book_id,
+            requested_user: owner,
+            is_admin: false,
+            force: false,
+        })
+        .await?;
+
+        // 除籍した蔵書は、find_by_id と同じく存在しないものとして扱う
+        let res = repo.find_copies(book_id).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        let res = repo.find_status_history(book_id).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        let res = repo.find_copies(BookId::new()).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_unavailable_status_is_not_available(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let options = |availability| BookListOptions {
+            limit: 20,
+            availability: Some(availability),
+            ..Default::default()
+        };
+
+        repo.change_status(ChangeBookStatus {
+            book_id,
+            status: BookStatus::InRepair,
+            reason: None,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+
+        // 修理中の蔵書は、貸出中の現物がなくても貸し出せるものに含めない
+        let res = repo.find_all(options(BookAvailability::Available)).await?;
+        assert_eq!(res.total, 2);
+        assert!(res.items.iter().all(|b| b.id != book_id));
+
+        // 貸出中の現物がないため、貸出中には含めない
+        let res = repo.find_all(options(BookAvailability::CheckedOut)).await?;
+        assert_eq!(res.total, 0);
+
+        let res = repo
+            .find_all(options(BookAvailability::Unavailable))
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, book_id);
+        assert_eq!(res.items[0].total_copies, 1);
+        assert_eq!(res.items[0].available_copies, 0);
+
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.available_copies, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_books_with_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let options = || BookListOptions {
+            limit: 2,
+            sort: Some(BookSort {
+                key: BookSortKey::Title,
+                order: SortOrder::Asc,
+            }),
+            ..Default::default()
         };
-        repo.update(update_book).await?;
+        let all = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                ..options()
+            })
+            .await?
+            .into_inner()
+            .into_iter()
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+
+        let first = repo.find_all_with_cursor(options(), None).await?;
+        let ids = first.items.iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids, all[..2]);
+        assert!(first.prev_cursor.is_none());
+
+        let second = repo
+            .find_all_with_cursor(options(), first.next_cursor.clone())
+            .await?;
+        let ids = second.items.iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids, all[2..]);
+        assert!(second.next_cursor.is_none());
+
+        let back = repo
+            .find_all_with_cursor(options(), second.prev_cursor)
+            .await?;
+        let ids = back.items.iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids, all[..2]);
+        assert!(back.prev_cursor.is_none());
+        assert!(back.next_cursor.is_some());
+
+        // カーソルが指す蔵書が除籍されても続きから取得できる
+        let cursor = first.next_cursor.unwrap();
+        repo.delete(DeleteBook {
+            book_id: BookId::from(cursor.key),
+            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?,
+            is_admin: true,
+            force: true,
+        })
+        .await?;
+        let second = repo
+            .find_all_with_cursor(options(), Some(cursor.clone()))
+            .await?;
+        let ids = second.items.iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids, all[2..]);
+
+        // 並び順や絞り込みの条件が異なるカーソルは受け付けない
+        let res = repo
+            .find_all_with_cursor(
+                BookListOptions {
+                    sort: None,
+                    ..options()
+                },
+                Some(cursor.clone()),
+            )
+            .await;
+        assert!(matches!(res, Err(AppError::BadRequest(_))));
+        let res = repo
+            .find_all_with_cursor(
+                BookListOptions {
+                    query: Some("rust".into()),
+                    ..options()
+                },
+                Some(cursor.clone()),
+            )
+            .await;
+        assert!(matches!(res, Err(AppError::BadRequest(_))));
+
+        // 並び順の値を含まないカーソルは受け付けない
+        let res = repo
+            .find_all_with_cursor(options(), Some(Cursor::next(cursor.key)))
+            .await;
+        assert!(matches!(res, Err(AppError::BadRequest(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_manage_book_copies(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let checked_out_copy_id = BookCopyId::from_str("c0b1e5d0-0000-4000-8000-000000000001")?;
+        let version = repo.find_by_id(book_id).await?.unwrap().version;
+
+        repo.add_copy(CreateBookCopy {
+            book_id,
+            barcode: Some("TEST00000099".into()),
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+        repo.add_copy(CreateBookCopy {
+            book_id,
+            barcode: None,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+
+        let copies = repo.find_copies(book_id).await?;
+        assert_eq!(copies.len(), 3);
+        assert!(copies.iter().any(|c| c.barcode == "TEST00000099"));
+        assert!(
+            copies
+                .iter()
+                .find(|c| c.id == checked_out_copy_id)
+                .is_some_and(|c| c.checkout.is_some())
+        );
 
         let book = repo.find_by_id(book_id).await?.unwrap();
-        assert_eq!(book.author, NEW_AUTHOR);
+        assert_eq!(book.total_copies, 3);
+        assert_eq!(book.available_copies, 2);
+        // 現物を追加すると蔵書の版数が更新される
+        assert_eq!(book.version, version + 2);
+
+        // 同じバーコードは登録できない
+        let res = repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: Some("TEST00000099".into()),
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 所有者以外は追加できない
+        let res = repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: UserId::new(),
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        // 存在しない蔵書には追加できない
+        let res = repo
+            .add_copy(CreateBookCopy {
+                book_id: BookId::new(),
+                barcode: None,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 貸出中の現物は削除できない
+        let res = repo
+            .delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id: checked_out_copy_id,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 管理者は所有者以外の蔵書の現物も削除でき、監査ログに残る
+        let admin = UserId::new();
+        for copy in copies.iter().filter(|c| c.checkout.is_none()) {
+            repo.delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id: copy.id,
+                requested_user: admin,
+                is_admin: true,
+            })
+            .await?;
+        }
+        assert_eq!(repo.find_audit_logs(book_id).await?.len(), 2);
+        assert_eq!(repo.find_copies(book_id).await?.len(), 1);
+
+        // 最後の 1 冊は削除できない
+        let last_copy_id = BookCopyId::from_str("c0b1e5d0-0000-4000-8000-000000000002")?;
+        let other_book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let res = repo
+            .delete_copy(DeleteBookCopy {
+                book_id: other_book_id,
+                copy_id: last_copy_id,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_import_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = |line: u64, title: &str, isbn: &str| -> anyhow::Result<ImportBook> {
+            Ok(ImportBook {
+                line,
+                book: CreateBook {
+                    title: title.into(),
+                    author: "Test Author".into(),
+                    isbn: Isbn::from_str(isbn)?,
+                    description: "".into(),
+                    copies: 1,
+                    on_duplicate: DuplicateBookPolicy::Reject,
+                },
+            })
+        };
+        let books = || -> anyhow::Result<Vec<ImportBook>> {
+            Ok(vec![
+                book(2, "New Book", "9784873119786")?,
+                // 登録済みの蔵書
+                book(3, "Registered Book", "9784798061702")?,
+                // 取り込むデータ内での重複
+                book(4, "New Book Again", "978-4-87311-978-6")?,
+                // 書名が長すぎるため登録に失敗する
+                book(5, &"x".repeat(256), "9784297141738")?,
+            ])
+        };
+
+        // 1 件でも失敗する場合、すべてを取り消して失敗した行だけを返す
+        let res = repo
+            .import(ImportBooks {
+                books: books()?,
+                requested_user: owner,
+                atomic: true,
+            })
+            .await?;
+        assert_eq!(res.len(), 1);
+        assert!(matches!(
+            (res[0].line, &res[0].status),
+            (5, BookImportStatus::Failed(_))
+        ));
+        let count = |pool: sqlx::PgPool| async move {
+            sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM books"#)
+                .fetch_one(&pool)
+                .await
+        };
+        assert_eq!(count(pool.clone()).await?, 3);
+
+        let res = repo
+            .import(ImportBooks {
+                books: books()?,
+                requested_user: owner,
+                atomic: false,
+            })
+            .await?;
+        let statuses = res.iter().map(|r| (r.line, &r.status)).collect::<Vec<_>>();
+        assert!(matches!(statuses[0], (2, BookImportStatus::Created(_))));
+        assert!(matches!(statuses[1], (3, BookImportStatus::Skipped(_))));
+        assert!(matches!(statuses[2], (4, BookImportStatus::Skipped(_))));
+        assert!(matches!(statuses[3], (5, BookImportStatus::Failed(_))));
+        assert_eq!(count(pool.clone()).await?, 4);
+
+        // 別の所有者であれば、同じ ISBN でも登録できる
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let other = user_repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+        let res = repo
+            .import(ImportBooks {
+                books: vec![book(2, "Registered Book", "9784798061702")?],
+                requested_user: other.id,
+                atomic: true,
+            })
+            .await?;
+        assert!(matches!(res[0].status, BookImportStatus::Created(_)));
+
+        // 除籍した蔵書とは重複しない
+        repo.delete(DeleteBook {
+            book_id: BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?,
+            requested_user: owner,
+            is_admin: false,
+            force: false,
+        })
+        .await?;
+        let res = repo
+            .import(ImportBooks {
+                books: vec![book(2, "Registered Book", "9784798061702")?],
+                requested_user: owner,
+                atomic: true,
+            })
+            .await?;
+        assert!(matches!(res[0].status, BookImportStatus::Created(_)));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_stream_all_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        // 一度に取得する件数を超える蔵書を登録しておく
+        let books = (0..STREAM_BATCH_SIZE as u64 + 20)
+            .map(|i| {
+                // 978-4-00-000000-? の範囲で、チェックディジットを計算して ISBN を作る
+                let digits = format!("978400{i:06}");
+                let sum: u32 = digits
+                    .chars()
+                    .filter_map(|c| c.to_digit(10))
+                    .zip([1, 3].into_iter().cycle())
+                    .map(|(d, w)| d * w)
+                    .sum();
+                Ok(ImportBook {
+                    line: i + 2,
+                    book: CreateBook {
+                        title: format!("Book {i:03}"),
+                        author: "Test Author".into(),
+                        isbn: Isbn::from_str(&format!("{digits}{}", (10 - sum % 10) % 10))?,
+                        description: "".into(),
+                        copies: 1,
+                        on_duplicate: DuplicateBookPolicy::Reject,
+                    },
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        repo.import(ImportBooks {
+            books,
+            requested_user: owner,
+            atomic: true,
+        })
+        .await?;
+
+        let options = BookListOptions {
+            sort: Some(BookSort {
+                key: BookSortKey::Title,
+                order: SortOrder::Asc,
+            }),
+            ..Default::default()
+        };
+        let expected = repo
+            .find_all(BookListOptions {
+                limit: 1000,
+                ..options.clone()
+            })
+            .await?
+            .into_inner()
+            .into_iter()
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), STREAM_BATCH_SIZE as usize + 23);
+
+        let ids = repo
+            .stream_all(options)
+            .map(|b| b.map(|b| b.id))
+            .collect::<Result<Vec<_>, _>>()
+            .await?;
+        assert_eq!(ids, expected);
 
         Ok(())
     }
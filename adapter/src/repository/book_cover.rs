@@ -0,0 +1,313 @@
+use crate::database::ConnectionPool;
+use crate::repository::book::lock_book_owner;
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::book::event::{DeleteBookCover, UpdateBookCover};
+use kernel::model::book::{BookCoverImage, BookCoverSize};
+use kernel::model::id::BookId;
+use kernel::repository::blob_store::BlobStore;
+use kernel::repository::book_cover::BookCoverRepository;
+use shared::error::{AppError, AppResult};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 縮小画像の MIME タイプ。元画像の形式によらず JPEG で保存する。
+const THUMBNAIL_CONTENT_TYPE: &str = "image/jpeg";
+
+#[derive(new)]
+pub struct BookCoverRepositoryImpl {
+    db: ConnectionPool,
+    blob_store: Arc<dyn BlobStore>,
+}
+
+#[async_trait]
+impl BookCoverRepository for BookCoverRepositoryImpl {
+    async fn update(&self, event: UpdateBookCover) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 同じ蔵書の表紙画像が同時に更新されないよう、蔵書の行をロックする
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        let old_blob_id = sqlx::query_scalar!(
+            r#"
+                SELECT blob_id FROM book_covers WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 新しい画像は別のキーに保存するため、コミットするまでは古い画像が参照され続ける
+        let blob_id = Uuid::new_v4();
+        let images = [
+            (BookCoverSize::Original, event.original),
+            (BookCoverSize::Thumbnail, event.thumbnail),
+        ];
+        for (size, data) in images {
+            if let Err(e) = self
+                .blob_store
+                .put(&cover_key(event.book_id, blob_id, size), data)
+                .await
+            {
+                self.delete_blobs(event.book_id, blob_id).await;
+                return Err(e);
+            }
+        }
+
+        let res = sqlx::query!(
+            r#"
+                INSERT INTO book_covers (book_id, blob_id, content_type, width, height)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (book_id) DO UPDATE
+                SET blob_id = EXCLUDED.blob_id,
+                    content_type = EXCLUDED.content_type,
+                    width = EXCLUDED.width,
+                    height = EXCLUDED.height
+            "#,
+            event.book_id as _,
+            blob_id,
+            event.content_type,
+            event.width as i32,
+            event.height as i32
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError);
+        let res = match res {
+            Ok(_) => tx.commit().await.map_err(AppError::TransactionError),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = res {
+            self.delete_blobs(event.book_id, blob_id).await;
+            return Err(e);
+        }
+
+        if let Some(old_blob_id) = old_blob_id {
+            self.delete_blobs(event.book_id, old_blob_id).await;
+        }
+
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        book_id: BookId,
+        size: BookCoverSize,
+    ) -> AppResult<Option<BookCoverImage>> {
+        let Some(cover) = sqlx::query!(
+            r#"
+                SELECT blob_id, content_type, updated_at
+                FROM book_covers
+                WHERE book_id = $1
+            "#,
+            book_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(data) = self
+            .blob_store
+            .get(&cover_key(book_id, cover.blob_id, size))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let content_type = match size {
+            BookCoverSize::Original => cover.content_type,
+            BookCoverSize::Thumbnail => THUMBNAIL_CONTENT_TYPE.into(),
+        };
+
+        Ok(Some(BookCoverImage {
+            content_type,
+            data,
+            updated_at: cover.updated_at,
+        }))
+    }
+
+    async fn delete(&self, event: DeleteBookCover) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        let blob_id = sqlx::query_scalar!(
+            r#"
+                DELETE FROM book_covers
+                WHERE book_id = $1
+                RETURNING blob_id
+            "#,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book cover not found".into()))?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        // 登録情報の削除が確定してから画像を削除する
+        self.delete_blobs(event.book_id, blob_id).await;
+
+        Ok(())
+    }
+}
+
+impl BookCoverRepositoryImpl {
+    async fn delete_blobs(&self, book_id: BookId, blob_id: Uuid) {
+        delete_cover_blobs(self.blob_store.as_ref(), book_id, blob_id).await
+    }
+}
+
+/// 表紙画像を削除する。登録情報から参照されなくなった画像の後片付けのため、
+/// 削除に失敗しても記録するだけでエラーにはしない。
+pub(crate) async fn delete_cover_blobs(blob_store: &dyn BlobStore, book_id: BookId, blob_id: Uuid) {
+    for size in [BookCoverSize::Original, BookCoverSize::Thumbnail] {
+        let key = cover_key(book_id, blob_id, size);
+        if let Err(e) = blob_store.delete(&key).await {
+            tracing::warn!(key, error = %e, "failed to delete unreferenced book cover");
+        }
+    }
+}
+
+fn cover_key(book_id: BookId, blob_id: Uuid, size: BookCoverSize) -> String {
+    let name = match size {
+        BookCoverSize::Original => "original",
+        BookCoverSize::Thumbnail => "thumbnail",
+    };
+    format!("covers/{book_id}/{blob_id}/{name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::blob_store::LocalFsBlobStore;
+    use crate::repository::book::BookRepositoryImpl;
+    use kernel::model::book::event::{DeleteBook, PurgeBook};
+    use kernel::model::id::UserId;
+    use kernel::repository::book::BookRepository;
+    use std::str::FromStr;
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_manage_book_cover(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let blob_store = Arc::new(LocalFsBlobStore::new(dir.path()));
+        let repo =
+            BookCoverRepositoryImpl::new(ConnectionPool::new(pool.clone()), blob_store.clone());
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()), blob_store);
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let update = |requested_user, is_admin| UpdateBookCover {
+            book_id,
+            requested_user,
+            is_admin,
+            content_type: "image/png".into(),
+            width: 400,
+            height: 600,
+            original: b"original".to_vec(),
+            thumbnail: b"thumbnail".to_vec(),
+        };
+
+        assert!(repo.find(book_id, BookCoverSize::Original).await?.is_none());
+
+        // 所有者以外は登録できない
+        let res = repo.update(update(UserId::new(), false)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        repo.update(update(owner, false)).await?;
+        let original = repo.find(book_id, BookCoverSize::Original).await?.unwrap();
+        assert_eq!(original.content_type, "image/png");
+        assert_eq!(original.data, b"original");
+        let thumbnail = repo.find(book_id, BookCoverSize::Thumbnail).await?.unwrap();
+        assert_eq!(thumbnail.content_type, "image/jpeg");
+        assert_eq!(thumbnail.data, b"thumbnail");
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(
+            book.cover.map(|c| c.content_type).as_deref(),
+            Some("image/png")
+        );
+
+        // 更新すると新しい画像に置き換わり、古い画像はコミット後に削除される
+        repo.update(UpdateBookCover {
+            original: b"updated".to_vec(),
+            ..update(owner, false)
+        })
+        .await?;
+        let original = repo.find(book_id, BookCoverSize::Original).await?.unwrap();
+        assert_eq!(original.data, b"updated");
+        let cover_dir = dir.path().join(format!("covers/{book_id}"));
+        assert_eq!(count_files(&cover_dir)?, 2);
+
+        // 管理者は所有者以外の蔵書の表紙画像も削除でき、監査ログに残る
+        let admin = UserId::new();
+        let res = repo
+            .delete(DeleteBookCover {
+                book_id,
+                requested_user: admin,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.delete(DeleteBookCover {
+            book_id,
+            requested_user: admin,
+            is_admin: true,
+        })
+        .await?;
+        let logs = book_repo.find_audit_logs(book_id).await?;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].operated_by.id, admin);
+        assert!(
+            repo.find(book_id, BookCoverSize::Thumbnail)
+                .await?
+                .is_none()
+        );
+        assert!(
+            book_repo
+                .find_by_id(book_id)
+                .await?
+                .unwrap()
+                .cover
+                .is_none()
+        );
+        assert_eq!(count_files(&cover_dir)?, 0);
+
+        // 蔵書を完全に削除すると、表紙画像もコミット後に削除される
+        repo.update(update(owner, false)).await?;
+        assert_eq!(count_files(&cover_dir)?, 2);
+        book_repo
+            .delete(DeleteBook {
+                book_id,
+                requested_user: owner,
+                is_admin: false,
+                force: false,
+            })
+            .await?;
+        book_repo
+            .purge(PurgeBook {
+                book_id,
+                requested_user: owner,
+            })
+            .await?;
+        assert_eq!(count_files(&cover_dir)?, 0);
+
+        Ok(())
+    }
+
+    fn count_files(dir: &std::path::Path) -> std::io::Result<usize> {
+        let mut count = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            count += if path.is_dir() {
+                count_files(&path)?
+            } else {
+                1
+            };
+        }
+        Ok(count)
+    }
+}
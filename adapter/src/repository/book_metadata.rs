@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use kernel::model::book::BookMetadata;
+use kernel::model::isbn::Isbn;
+use kernel::repository::book_metadata::BookMetadataProvider;
+use serde::Deserialize;
+use shared::error::{AppError, AppResult};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// openBD (https://openbd.jp/) の API から書誌情報を取得する。
+pub struct OpenBdBookMetadataProvider {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl OpenBdBookMetadataProvider {
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(endpoint: String) -> AppResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Self::TIMEOUT)
+            .build()
+            .map_err(|e| AppError::ExternalServiceError(e.to_string()))?;
+        Ok(Self { client, endpoint })
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for OpenBdBookMetadataProvider {
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        let external_error = |e: reqwest::Error| {
+            AppError::ExternalServiceError(format!(
+                "openBD から書誌情報を取得できませんでした: {e}"
+            ))
+        };
+
+        // 該当する書誌情報がない場合は `[null]` が返る
+        let records = self
+            .client
+            .get(&self.endpoint)
+            .query(&[("isbn", isbn.as_str())])
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(external_error)?
+            .json::<Vec<Option<OpenBdRecord>>>()
+            .await
+            .map_err(external_error)?;
+
+        Ok(records
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|record| record.into_metadata(isbn.clone())))
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenBdRecord {
+    summary: OpenBdSummary,
+    onix: Option<OpenBdOnix>,
+}
+
+#[derive(Deserialize)]
+struct OpenBdSummary {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    author: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OpenBdOnix {
+    collateral_detail: Option<OpenBdCollateralDetail>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OpenBdCollateralDetail {
+    #[serde(default)]
+    text_content: Vec<OpenBdTextContent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OpenBdTextContent {
+    text_type: String,
+    text: String,
+}
+
+impl OpenBdRecord {
+    fn into_metadata(self, isbn: Isbn) -> BookMetadata {
+        // 内容紹介 (03) を優先し、なければ短い紹介文 (02) を説明とする
+        let texts = self
+            .onix
+            .and_then(|onix| onix.collateral_detail)
+            .map(|detail| detail.text_content)
+            .unwrap_or_default();
+        let description = ["03", "02"]
+            .into_iter()
+            .find_map(|text_type| texts.iter().find(|t| t.text_type == text_type))
+            .map(|t| t.text.clone())
+            .unwrap_or_default();
+
+        BookMetadata {
+            isbn,
+            title: self.summary.title,
+            author: self.summary.author,
+            description,
+        }
+    }
+}
+
+/// ローカルの JSON ファイルから書誌情報を取得する。オフライン環境やテストでの利用を想定している。
+/// ファイルは `isbn`, `title`, `author`, `description` を持つオブジェクトの配列とする。
+pub struct JsonFileBookMetadataProvider {
+    records: HashMap<Isbn, BookMetadata>,
+}
+
+impl JsonFileBookMetadataProvider {
+    pub fn from_path(path: impl AsRef<Path>) -> AppResult<Self> {
+        let path = path.as_ref();
+        let file_error = |e: &dyn std::fmt::Display| {
+            AppError::ExternalServiceError(format!(
+                "書誌情報ファイル ({}) を読み込めませんでした: {e}",
+                path.display()
+            ))
+        };
+
+        let json = std::fs::read_to_string(path).map_err(|e| file_error(&e))?;
+        let records = serde_json::from_str::<Vec<BookMetadataRecord>>(&json)
+            .map_err(|e| file_error(&e))?
+            .into_iter()
+            .map(|r| {
+                let metadata = BookMetadata::from(r);
+                (metadata.isbn.clone(), metadata)
+            })
+            .collect();
+
+        Ok(Self { records })
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for JsonFileBookMetadataProvider {
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        Ok(self.records.get(isbn).cloned())
+    }
+}
+
+#[derive(Deserialize)]
+struct BookMetadataRecord {
+    isbn: Isbn,
+    title: String,
+    author: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl From<BookMetadataRecord> for BookMetadata {
+    fn from(value: BookMetadataRecord) -> Self {
+        let BookMetadataRecord {
+            isbn,
+            title,
+            author,
+            description,
+        } = value;
+        Self {
+            isbn,
+            title,
+            author,
+            description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_openbd_response() -> anyhow::Result<()> {
+        let json = r#"[
+            {
+                "onix": {
+                    "CollateralDetail": {
+                        "TextContent": [
+                            {"TextType": "02", "ContentAudience": "00", "Text": "短い紹介文"},
+                            {"TextType": "03", "ContentAudience": "00", "Text": "内容紹介"},
+                            {"TextType": "04", "ContentAudience": "00", "Text": "目次"}
+                        ]
+                    }
+                },
+                "summary": {
+                    "isbn": "9784798061702",
+                    "title": "実践Rustプログラミング入門",
+                    "publisher": "秀和システム",
+                    "author": "初田直也／著"
+                }
+            },
+            null
+        ]"#;
+        let isbn = Isbn::from_str("9784798061702")?;
+        let records = serde_json::from_str::<Vec<Option<OpenBdRecord>>>(json)?;
+        let metadata = records
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|r| r.into_metadata(isbn.clone()))
+            .unwrap();
+
+        assert_eq!(metadata.isbn, isbn);
+        assert_eq!(metadata.title, "実践Rustプログラミング入門");
+        assert_eq!(metadata.author, "初田直也／著");
+        assert_eq!(metadata.description, "内容紹介");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_isbn_from_json_file() -> anyhow::Result<()> {
+        let provider = JsonFileBookMetadataProvider::from_path(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/repository/fixtures/book_metadata.json"
+        ))?;
+
+        // ISBN-10 で登録されていても ISBN-13 で引ける
+        let res = provider
+            .find_by_isbn(&Isbn::from_str("978-4-7980-6170-2")?)
+            .await?
+            .unwrap();
+        assert_eq!(res.title, "実践Rustプログラミング入門");
+        assert_eq!(res.description, "");
+
+        let res = provider
+            .find_by_isbn(&Isbn::from_str("9784065369579")?)
+            .await?;
+        assert!(res.is_none());
+
+        Ok(())
+    }
+}
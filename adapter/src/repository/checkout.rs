@@ -1,13 +1,23 @@
 use crate::database::ConnectionPool;
-use crate::database::model::checkout::{CheckoutRow, CheckoutStateRow, ReturnedCheckoutRow};
+use crate::database::model::checkout::{
+    AvailableCopyRow, BookConditionRow, CheckoutHistoryRow, CheckoutRow, CheckoutStateRow,
+};
+use crate::repository::book::{authorize_book_owner, change_book_status, status_label};
 use async_trait::async_trait;
 use derive_new::new;
-use kernel::model::checkout::Checkout;
-use kernel::model::checkout::event::{CreateCheckout, UpdateReturned};
-use kernel::model::id::{BookId, CheckoutId, UserId};
+use kernel::model::book::BookStatus;
+use kernel::model::book::revision::BookRevisionAction;
+use kernel::model::checkout::event::{CreateCheckout, ReportLost, UpdateReturned};
+use kernel::model::checkout::{
+    BookConditionReport, Checkout, CheckoutListOptions, CheckoutOutcome,
+};
+use kernel::model::id::{BookCopyId, BookId, CheckoutId, UserId};
+use kernel::model::isbn::Isbn;
+use kernel::model::list::{Cursor, CursorDirection, CursorPaginatedList};
 use kernel::repository::checkout::CheckoutRepository;
 use shared::error::{AppError, AppResult};
 use sqlx::Postgres;
+use std::str::FromStr;
 
 #[derive(new)]
 pub struct CheckoutRepositoryImpl {
@@ -21,24 +31,53 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
 
         self.set_transaction_serializable(&mut tx).await?;
 
-        {
+        // 貸出中でも紛失中でもない現物のうち、登録の古いものから 1 冊を選んで貸し出す。
+        // 同時に除籍されないよう、蔵書の行を共有ロックする。
+        let copy_id = {
             let res = sqlx::query_as!(
-                CheckoutStateRow,
+                AvailableCopyRow,
                 r#"
                 SELECT
                     b.book_id,
-                    c.checkout_id AS "checkout_id?: CheckoutId",
-                    NULL AS "user_id?: UserId"
+                    (
+                        SELECT bc.copy_id
+                        FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+                        AND bc.lost_at IS NULL
+                        ORDER BY bc.created_at, bc.barcode
+                        LIMIT 1
+                    ) AS "copy_id?: BookCopyId",
+                    EXISTS (
+                        SELECT 1 FROM checkouts AS c
+                        WHERE c.book_id = b.book_id AND c.user_id = $2
+                    ) AS "already_checked_out!",
+                    b.status
                 FROM books AS b
-                LEFT OUTER JOIN checkouts AS c USING(book_id)
-                WHERE book_id = $1;
+                WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
+                FOR SHARE OF b;
             "#,
-                event.book_id as _
+                event.book_id as _,
+                event.checked_out_by as _
             )
             .fetch_optional(&mut *tx)
             .await
             .map_err(AppError::SpecificOperationError)?;
 
+            // 修理中・紛失・貸出停止の蔵書は貸し出さない
+            if let Some(row) = &res {
+                let status = BookStatus::from_str(&row.status)
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+                if status != BookStatus::Available {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "書籍 ({}) は{}のため貸し出せません。",
+                        event.book_id,
+                        status_label(status)
+                    )));
+                }
+            }
+
             match res {
                 None => {
                     return Err(AppError::EntityNotFound(format!(
@@ -46,29 +85,39 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                         event.book_id,
                     )));
                 }
-                Some(CheckoutStateRow {
-                    checkout_id: Some(_),
+                Some(AvailableCopyRow {
+                    already_checked_out: true,
                     ..
                 }) => {
                     return Err(AppError::UnprocessableEntity(format!(
-                        "書籍 ({}) に対する貸出が既に存在します。",
+                        "書籍 ({}) はすでに貸出を受けています。",
+                        event.book_id
+                    )));
+                }
+                Some(AvailableCopyRow {
+                    copy_id: Some(copy_id),
+                    ..
+                }) => copy_id,
+                Some(AvailableCopyRow { copy_id: None, .. }) => {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "書籍 ({}) の貸出可能な現物がありません。",
                         event.book_id
                     )));
                 }
-                _ => {}
             }
-        }
+        };
 
         let checkout_id = CheckoutId::new();
         let res = sqlx::query!(
             r#"
                 INSERT INTO checkouts
-                (checkout_id, book_id, user_id, checked_out_at)
-                VALUES ($1, $2, $3, $4)
+                (checkout_id, book_id, copy_id, user_id, checked_out_at)
+                VALUES ($1, $2, $3, $4, $5)
                 ;
             "#,
             checkout_id as _,
             event.book_id as _,
+            copy_id as _,
             event.checked_out_by as _,
             event.checked_out_at
         )
@@ -100,10 +149,12 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                         c.checkout_id AS "checkout_id?: CheckoutId",
                         c.user_id AS "user_id?: UserId"
                     FROM books AS b
-                    LEFT OUTER JOIN checkouts AS c USING(book_id)
-                    WHERE book_id = $1
+                    LEFT OUTER JOIN checkouts AS c
+                        ON c.book_id = b.book_id AND c.checkout_id = $2
+                    WHERE b.book_id = $1
                 "#,
-                event.book_id as _
+                event.book_id as _,
+                event.checkout_id as _
             )
             .fetch_optional(&mut *tx)
             .await
@@ -117,33 +168,42 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                     )));
                 }
                 Some(CheckoutStateRow {
-                    checkout_id: Some(c),
+                    checkout_id: Some(_),
                     user_id: Some(u),
                     ..
-                }) if (c, u) != (event.checkout_id, event.returned_by) => {
+                }) if u == event.returned_by => {}
+                Some(_) => {
                     return Err(AppError::UnprocessableEntity(format!(
                         "指定の貸出 (ID({})), ユーザー ({}), 書籍({})) は返却できません。 ",
                         event.checkout_id, event.returned_by, event.book_id,
                     )));
                 }
-                _ => {}
             }
         }
 
         let res = sqlx::query!(
             r#"
                 INSERT INTO returned_checkouts
-                (checkout_id, book_id, user_id, checked_out_at, returned_at)
-                SELECT checkout_id, book_id, user_id, checked_out_at, $2
+                (checkout_id, book_id, copy_id, user_id, checked_out_at, returned_at, returned_location_id,
+                 condition, condition_note)
+                SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, $2, $3, $4, $5
                 FROM checkouts
                 WHERE checkout_id = $1
             "#,
             event.checkout_id as _,
             event.returned_at,
+            event.returned_location as _,
+            event.condition.as_ref().map(|c| c.condition.as_ref()),
+            event.condition.as_ref().and_then(|c| c.note.as_deref()),
         )
         .execute(&mut *tx)
         .await
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified location not found".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
 
         if res.rows_affected() < 1 {
             return Err(AppError::NoRowsAffectedError(
@@ -151,6 +211,28 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
             ));
         }
 
+        // 配架場所と異なる拠点で返却された現物は、配架場所へ戻すまで返却された場所にあるものとして記録する。
+        // 返却された場所が不明な場合や、配架場所が決まっていない場合は配架済みとして扱う。
+        sqlx::query!(
+            r#"
+                UPDATE book_copies AS bc
+                SET current_location_id = CASE
+                    WHEN $2::uuid IS NULL OR b.location_id IS NULL THEN NULL
+                    WHEN location_branch(b.location_id) = location_branch($2) THEN NULL
+                    ELSE $2
+                END
+                FROM checkouts AS c, books AS b
+                WHERE c.checkout_id = $1
+                AND bc.copy_id = c.copy_id
+                AND b.book_id = bc.book_id
+            "#,
+            event.checkout_id as _,
+            event.returned_location as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
         let res = sqlx::query!(
             r#"
                 DELETE FROM checkouts WHERE checkout_id = $1
@@ -172,75 +254,252 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         Ok(())
     }
 
-    async fn find_unreturned_all(&self) -> AppResult<Vec<Checkout>> {
-        sqlx::query_as!(
-            CheckoutRow,
+    async fn report_lost(&self, event: ReportLost) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        self.set_transaction_serializable(&mut tx).await?;
+
+        let row = sqlx::query!(
             r#"
                 SELECT
-                    c.checkout_id,
-                    c.book_id,
-                    c.user_id,
-                    c.checked_out_at,
-                    b.title,
-                    b.author,
-                    b.isbn
-                FROM checkouts AS c
-                INNER JOIN books AS b USING(book_id)
-                ORDER BY c.checked_out_at ASC
-                ;
-            "#
+                    b.user_id AS "owner_id: UserId",
+                    b.status,
+                    c.user_id AS "borrower_id?: UserId",
+                    c.copy_id AS "copy_id?: BookCopyId"
+                FROM books AS b
+                LEFT OUTER JOIN checkouts AS c
+                    ON c.book_id = b.book_id AND c.checkout_id = $2
+                WHERE b.book_id = $1
+                FOR UPDATE OF b
+            "#,
+            event.book_id as _,
+            event.checkout_id as _
         )
-        .fetch_all(self.db.inner_ref())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| {
+            AppError::EntityNotFound(format!("書籍 ({}) が見つかりませんでした。", event.book_id))
+        })?;
+
+        let (Some(borrower_id), Some(copy_id)) = (row.borrower_id, row.copy_id) else {
+            return Err(AppError::UnprocessableEntity(format!(
+                "指定の貸出 ({}) は貸出中ではありません。",
+                event.checkout_id
+            )));
+        };
+        // 借りたユーザー本人以外は、蔵書の所有者か管理者のみが届け出られる。
+        // 弁償金は借りたユーザー本人には記録させない。
+        if event.requested_user != borrower_id || event.replacement_charge.is_some() {
+            authorize_book_owner(
+                &mut tx,
+                event.book_id,
+                row.owner_id,
+                event.requested_user,
+                event.is_admin,
+                BookRevisionAction::ChangeStatus,
+            )
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO returned_checkouts
+                (checkout_id, book_id, copy_id, user_id, checked_out_at, returned_at, outcome)
+                SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, $2, $3
+                FROM checkouts
+                WHERE checkout_id = $1
+            "#,
+            event.checkout_id as _,
+            event.reported_at,
+            CheckoutOutcome::Lost.as_ref()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if let Some(amount) = event.replacement_charge {
+            sqlx::query!(
+                r#"
+                    INSERT INTO replacement_charges (checkout_id, book_id, user_id, amount, charged_by)
+                    VALUES ($1, $2, $3, $4, $5)
+                "#,
+                event.checkout_id as _,
+                event.book_id as _,
+                borrower_id as _,
+                amount,
+                event.requested_user as _
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        }
+
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM checkouts WHERE checkout_id = $1
+            "#,
+            event.checkout_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::NoRowsAffectedError(
+                "No checkout record has been deleted".into(),
+            ));
+        }
+
+        // 紛失は現物ごとに記録し、貸し出せる現物が残っている間は蔵書の状態を変えない
+        sqlx::query!(
+            r#"
+                UPDATE book_copies SET lost_at = $2 WHERE copy_id = $1
+            "#,
+            copy_id as _,
+            event.reported_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let usable_copies = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM book_copies
+                WHERE book_id = $1
+                AND lost_at IS NULL
+            "#,
+            event.book_id as _
+        )
+        .fetch_one(&mut *tx)
         .await
-        .map(|rows| rows.into_iter().map(Checkout::from).collect())
-        .map_err(AppError::SpecificOperationError)
+        .map_err(AppError::SpecificOperationError)?;
+
+        let current = BookStatus::from_str(&row.status)
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+        if usable_copies == 0 && current != BookStatus::Lost {
+            if !current.can_transition_to(BookStatus::Lost) {
+                return Err(AppError::UnprocessableEntity(format!(
+                    "書籍 ({}) は{}のため、紛失にできません。",
+                    event.book_id,
+                    status_label(current)
+                )));
+            }
+            change_book_status(
+                &mut tx,
+                event.book_id,
+                current,
+                BookStatus::Lost,
+                Some("貸出中に紛失"),
+                event.requested_user,
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
     }
 
-    async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>> {
-        sqlx::query_as!(
-            CheckoutRow,
+    async fn find_unreturned_all(
+        &self,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        self.find_unreturned(None, options).await
+    }
+
+    async fn find_unreturned_by_user_id(
+        &self,
+        user_id: UserId,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        self.find_unreturned(Some(user_id), options).await
+    }
+
+    async fn find_history_by_book_id(
+        &self,
+        book_id: BookId,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        let CheckoutListOptions { limit, cursor } = options;
+
+        // 未返却の貸出と返却済みの貸出をまとめて、貸出日時の新しい順に並べる。
+        // カーソル指定時は、カーソルが指す貸出の (貸出日時, 貸出ID) をキーに前後を取得する。
+        let checkouts: Vec<Checkout> = sqlx::query_as!(
+            CheckoutHistoryRow,
             r#"
+                WITH histories AS (
+                    SELECT checkout_id, book_id, copy_id, user_id, checked_out_at,
+                           NULL::timestamptz AS returned_at, NULL::varchar AS outcome
+                    FROM checkouts
+                    UNION ALL
+                    SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, returned_at,
+                           outcome
+                    FROM returned_checkouts
+                )
                 SELECT
-                    c.checkout_id,
-                    c.book_id,
-                    c.user_id,
-                    c.checked_out_at,
+                    h.checkout_id AS "checkout_id!",
+                    h.book_id AS "book_id!",
+                    h.copy_id AS "copy_id: BookCopyId",
+                    h.user_id AS "user_id!",
+                    h.checked_out_at AS "checked_out_at!",
+                    h.returned_at,
+                    h.outcome,
+                    rc.amount AS "replacement_charge?",
                     b.title,
                     b.author,
-                    b.isbn
-                FROM checkouts AS c
+                    b.isbn AS "isbn: Isbn"
+                FROM histories AS h
                 INNER JOIN books AS b USING(book_id)
-                WHERE c.user_id = $1
-                ORDER BY c.checked_out_at ASC
-                ;
+                LEFT OUTER JOIN replacement_charges AS rc USING(checkout_id)
+                WHERE h.book_id = $1
+                AND (
+                    $2::uuid IS NULL
+                    OR (NOT $3 AND (h.checked_out_at, h.checkout_id) < (
+                        SELECT checked_out_at, checkout_id FROM histories WHERE checkout_id = $2
+                    ))
+                    OR ($3 AND (h.checked_out_at, h.checkout_id) > (
+                        SELECT checked_out_at, checkout_id FROM histories WHERE checkout_id = $2
+                    ))
+                )
+                ORDER BY
+                    CASE WHEN $3 THEN h.checked_out_at END ASC,
+                    CASE WHEN $3 THEN h.checkout_id END ASC,
+                    h.checked_out_at DESC,
+                    h.checkout_id DESC
+                LIMIT $4
             "#,
-            user_id as _
+            book_id as _,
+            cursor.as_ref().map(|c| c.key),
+            is_prev(cursor.as_ref()),
+            limit.map(|l| l + 1)
         )
         .fetch_all(self.db.inner_ref())
         .await
-        .map(|rows| rows.into_iter().map(Checkout::from).collect())
-        .map_err(AppError::SpecificOperationError)
-    }
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Checkout::try_from)
+        .collect::<AppResult<_>>()?;
 
-    async fn find_history_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>> {
-        let checkout: Option<Checkout> = self.find_unreturned_by_book_id(book_id).await?;
+        Ok(into_paginated_list(checkouts, limit, cursor))
+    }
 
-        let mut checkout_histories: Vec<Checkout> = sqlx::query_as!(
-            ReturnedCheckoutRow,
+    async fn find_condition_history(&self, book_id: BookId) -> AppResult<Vec<BookConditionReport>> {
+        sqlx::query_as!(
+            BookConditionRow,
             r#"
                 SELECT
-                    rc.checkout_id,
-                    rc.book_id,
-                    rc.user_id,
-                    rc.checked_out_at,
-                    rc.returned_at,
-                    b.title,
-                    b.author,
-                    b.isbn
-                FROM returned_checkouts AS rc
-                INNER JOIN books AS b USING(book_id)
-                WHERE rc.book_id = $1
-                ORDER BY rc.checked_out_at DESC
+                    checkout_id,
+                    copy_id AS "copy_id?: BookCopyId",
+                    user_id,
+                    returned_at,
+                    condition AS "condition!",
+                    condition_note
+                FROM returned_checkouts
+                WHERE book_id = $1
+                AND condition IS NOT NULL
+                ORDER BY returned_at DESC, checkout_id
             "#,
             book_id as _
         )
@@ -248,14 +507,8 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         .await
         .map_err(AppError::SpecificOperationError)?
         .into_iter()
-        .map(Checkout::from)
-        .collect();
-
-        if let Some(co) = checkout {
-            checkout_histories.insert(0, co);
-        }
-
-        Ok(checkout_histories)
+        .map(BookConditionReport::try_from)
+        .collect()
     }
 }
 
@@ -272,29 +525,313 @@ impl CheckoutRepositoryImpl {
         Ok(())
     }
 
-    async fn find_unreturned_by_book_id(&self, book_id: BookId) -> AppResult<Option<Checkout>> {
-        let res = sqlx::query_as!(
+    /// 未返却の貸出情報を、貸出日時の古い順に取得する。
+    /// カーソル指定時は、カーソルが指す貸出の (貸出日時, 貸出ID) をキーに前後を取得する。
+    /// カーソルが指す貸出がすでに返却されていても、返却済みの貸出から位置を特定する。
+    async fn find_unreturned(
+        &self,
+        user_id: Option<UserId>,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        let CheckoutListOptions { limit, cursor } = options;
+
+        let checkouts: Vec<Checkout> = sqlx::query_as!(
             CheckoutRow,
             r#"
-            SELECT
-                c.checkout_id,
-                c.book_id,
-                c.user_id,
-                c.checked_out_at,
-                b.title,
-                b.author,
-                b.isbn
-            FROM checkouts AS c
-            INNER JOIN books AS b USING(book_id)
-            WHERE c.book_id = $1;
-        "#,
-            book_id as _
+                WITH cursor_checkout AS (
+                    SELECT checked_out_at, checkout_id FROM checkouts WHERE checkout_id = $2
+                    UNION ALL
+                    SELECT checked_out_at, checkout_id FROM returned_checkouts WHERE checkout_id = $2
+                )
+                SELECT
+                    c.checkout_id,
+                    c.book_id,
+                    c.copy_id,
+                    c.user_id,
+                    c.checked_out_at,
+                    b.title,
+                    b.author,
+                    b.isbn AS "isbn: Isbn"
+                FROM checkouts AS c
+                INNER JOIN books AS b USING(book_id)
+                WHERE ($1::uuid IS NULL OR c.user_id = $1)
+                AND (
+                    $2::uuid IS NULL
+                    OR (NOT $3 AND (c.checked_out_at, c.checkout_id) > (SELECT * FROM cursor_checkout))
+                    OR ($3 AND (c.checked_out_at, c.checkout_id) < (SELECT * FROM cursor_checkout))
+                )
+                ORDER BY
+                    CASE WHEN $3 THEN c.checked_out_at END DESC,
+                    CASE WHEN $3 THEN c.checkout_id END DESC,
+                    c.checked_out_at ASC,
+                    c.checkout_id ASC
+                LIMIT $4
+                ;
+            "#,
+            user_id as _,
+            cursor.as_ref().map(|c| c.key),
+            is_prev(cursor.as_ref()),
+            limit.map(|l| l + 1)
         )
-        .fetch_optional(self.db.inner_ref())
+        .fetch_all(self.db.inner_ref())
         .await
         .map_err(AppError::SpecificOperationError)?
-        .map(Checkout::from);
+        .into_iter()
+        .map(Checkout::from)
+        .collect();
+
+        Ok(into_paginated_list(checkouts, limit, cursor))
+    }
+}
+
+fn is_prev(cursor: Option<&Cursor>) -> bool {
+    matches!(cursor, Some(c) if c.direction == CursorDirection::Prev)
+}
+
+/// 取得した貸出情報をページに詰める。`limit` が未指定の場合はすべてを 1 ページとして返す。
+fn into_paginated_list(
+    mut items: Vec<Checkout>,
+    limit: Option<i64>,
+    cursor: Option<Cursor>,
+) -> CursorPaginatedList<Checkout> {
+    match limit {
+        Some(limit) => {
+            if is_prev(cursor.as_ref()) {
+                items.reverse();
+            }
+            CursorPaginatedList::from_items(items, limit, cursor.as_ref(), |c, d| {
+                Cursor::new(c.id.raw(), d)
+            })
+        }
+        None => CursorPaginatedList {
+            items,
+            next_cursor: None,
+            prev_cursor: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::ConnectionPool;
+    use crate::repository::book::BookRepositoryImpl;
+    use crate::repository::checkout::CheckoutRepositoryImpl;
+    use kernel::model::book::BookStatus;
+    use kernel::model::book::event::{ChangeBookStatus, CreateBookCopy, MarkBookFound};
+    use kernel::model::checkout::event::{
+        CreateCheckout, ReportCondition, ReportLost, UpdateReturned,
+    };
+    use kernel::model::checkout::{BookCondition, CheckoutListOptions, CheckoutOutcome};
+    use kernel::model::id::{BookId, CheckoutId, UserId};
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use kernel::repository::checkout::CheckoutRepository;
+    use shared::error::AppError;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_find_history_with_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let checkout_ids = (1..=4)
+            .map(|i| CheckoutId::from_str(&format!("a1b0c0d0-0000-4000-8000-00000000000{i}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let all = repo
+            .find_history_by_book_id(book_id, CheckoutListOptions::default())
+            .await?;
+        let ids = all.items.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, checkout_ids);
+        assert!(all.items[0].returned_at.is_none());
+        assert!(all.next_cursor.is_none());
+
+        let first = repo
+            .find_history_by_book_id(
+                book_id,
+                CheckoutListOptions {
+                    limit: Some(3),
+                    cursor: None,
+                },
+            )
+            .await?;
+        let ids = first.items.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, checkout_ids[..3]);
 
-        Ok(res)
+        let second = repo
+            .find_history_by_book_id(
+                book_id,
+                CheckoutListOptions {
+                    limit: Some(3),
+                    cursor: first.next_cursor,
+                },
+            )
+            .await?;
+        let ids = second.items.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, checkout_ids[3..]);
+        assert!(second.next_cursor.is_none());
+
+        let back = repo
+            .find_history_by_book_id(
+                book_id,
+                CheckoutListOptions {
+                    limit: Some(3),
+                    cursor: second.prev_cursor,
+                },
+            )
+            .await?;
+        let ids = back.items.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids, checkout_ids[..3]);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_return_with_condition_and_status(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let change_status = |status: BookStatus| {
+            book_repo.change_status(ChangeBookStatus {
+                book_id,
+                status,
+                reason: None,
+                requested_user: owner,
+                is_admin: false,
+            })
+        };
+
+        // 修理中の蔵書は貸し出せない
+        change_status(BookStatus::InRepair).await?;
+        let res = repo
+            .create(CreateCheckout::new(book_id, owner, chrono::Utc::now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        change_status(BookStatus::Available).await?;
+        repo.create(CreateCheckout::new(book_id, owner, chrono::Utc::now()))
+            .await?;
+        let checkout = repo
+            .find_unreturned_by_user_id(owner, CheckoutListOptions::default())
+            .await?
+            .items
+            .remove(0);
+        repo.update_returned(UpdateReturned::new(
+            checkout.id,
+            book_id,
+            owner,
+            chrono::Utc::now(),
+            None,
+            Some(ReportCondition::new(
+                BookCondition::Damaged,
+                Some("表紙が破れている".into()),
+            )),
+        ))
+        .await?;
+
+        let history = repo.find_condition_history(book_id).await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].checkout_id, checkout.id);
+        assert_eq!(history[0].condition, BookCondition::Damaged);
+        assert_eq!(history[0].note.as_deref(), Some("表紙が破れている"));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_report_lost_and_found(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let checkout_id = CheckoutId::from_str("a1b0c0d0-0000-4000-8000-000000000001")?;
+        let report_lost = || ReportLost {
+            checkout_id,
+            book_id,
+            requested_user: owner,
+            is_admin: false,
+            reported_at: chrono::Utc::now(),
+            replacement_charge: Some(1500),
+        };
+
+        // 別の現物が残っている間は、紛失した現物だけが貸し出せなくなる
+        book_repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await?;
+        repo.report_lost(report_lost()).await?;
+
+        // 貸出は紛失として終了し、弁償金が記録される
+        let history = repo
+            .find_history_by_book_id(book_id, CheckoutListOptions::default())
+            .await?;
+        let lost = history
+            .items
+            .iter()
+            .find(|c| c.id == checkout_id)
+            .expect("lost checkout must remain in history");
+        assert!(lost.returned_at.is_some());
+        assert_eq!(lost.outcome, Some(CheckoutOutcome::Lost));
+        assert_eq!(lost.replacement_charge, Some(1500));
+
+        // 同じ貸出を二度紛失扱いにはできない
+        let res = repo.report_lost(report_lost()).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let book = book_repo.find_by_id(book_id).await?.expect("book exists");
+        assert_eq!(book.status, BookStatus::Available);
+        assert_eq!((book.total_copies, book.available_copies), (2, 1));
+        let copies = book_repo.find_copies(book_id).await?;
+        let lost_copy = copies[0].id;
+        assert!(copies[0].lost_at.is_some());
+        assert!(copies[1].lost_at.is_none());
+
+        // 残りの現物が貸し出され、それも紛失すると蔵書が紛失の状態になる
+        repo.create(CreateCheckout::new(book_id, owner, chrono::Utc::now()))
+            .await?;
+        let book = book_repo.find_by_id(book_id).await?.expect("book exists");
+        assert_eq!(book.checkouts[0].copy_id, copies[1].id);
+        repo.report_lost(ReportLost {
+            checkout_id: book.checkouts[0].checkout_id,
+            replacement_charge: None,
+            ..report_lost()
+        })
+        .await?;
+        let book = book_repo.find_by_id(book_id).await?.expect("book exists");
+        assert_eq!(book.status, BookStatus::Lost);
+        assert_eq!(book.available_copies, 0);
+
+        // 紛失中の蔵書は貸し出せない
+        let res = repo
+            .create(CreateCheckout::new(book_id, owner, chrono::Utc::now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 現物が 1 冊見つかると、蔵書は貸出可能に戻る
+        let mark_found = || MarkBookFound {
+            book_id,
+            copy_id: lost_copy,
+            requested_user: owner,
+        };
+        book_repo.mark_found(mark_found()).await?;
+        let book = book_repo.find_by_id(book_id).await?.expect("book exists");
+        assert_eq!(book.status, BookStatus::Available);
+        assert_eq!(book.available_copies, 1);
+
+        let res = book_repo.mark_found(mark_found()).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        Ok(())
     }
 }
@@ -0,0 +1,37 @@
+INSERT INTO checkouts (checkout_id,
+                       book_id,
+                       copy_id,
+                       user_id,
+                       checked_out_at)
+VALUES ('a1b0c0d0-0000-4000-8000-000000000001',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'c0b1e5d0-0000-4000-8000-000000000001',
+        '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
+        '2024-10-04 10:00:00+09')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO returned_checkouts (checkout_id,
+                                book_id,
+                                copy_id,
+                                user_id,
+                                checked_out_at,
+                                returned_at)
+VALUES ('a1b0c0d0-0000-4000-8000-000000000002',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'c0b1e5d0-0000-4000-8000-000000000001',
+        '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
+        '2024-10-03 10:00:00+09',
+        '2024-10-03 18:00:00+09'),
+       ('a1b0c0d0-0000-4000-8000-000000000003',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'c0b1e5d0-0000-4000-8000-000000000001',
+        '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
+        '2024-10-02 10:00:00+09',
+        '2024-10-02 18:00:00+09'),
+       ('a1b0c0d0-0000-4000-8000-000000000004',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'c0b1e5d0-0000-4000-8000-000000000001',
+        '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
+        '2024-10-01 10:00:00+09',
+        '2024-10-01 18:00:00+09')
+ON CONFLICT DO NOTHING;
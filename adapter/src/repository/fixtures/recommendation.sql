@@ -0,0 +1,63 @@
+INSERT INTO users(user_id, name, email, password_hash, role_id)
+SELECT u.user_id::uuid
+     , u.name
+     , u.email
+     , 'atodehenkou'
+     , r.role_id
+FROM roles AS r
+CROSS JOIN (VALUES ('d1e0a0b0-0000-4000-8000-000000000001', 'Borrower One', 'borrower.one@example.com'),
+                   ('d1e0a0b0-0000-4000-8000-000000000002', 'Borrower Two', 'borrower.two@example.com'),
+                   ('d1e0a0b0-0000-4000-8000-000000000003', 'Borrower Three', 'borrower.three@example.com'))
+    AS u(user_id, name, email)
+WHERE r.name = 'User'
+ON CONFLICT DO NOTHING;
+
+-- 1 人目: 実践Rust・ゼロから学ぶRust
+-- 2 人目: 実践Rust・ゼロから学ぶRust・RustによるWebアプリケーション開発（同じ本を 2 回）
+-- 3 人目: 実践Rust・RustによるWebアプリケーション開発
+INSERT INTO returned_checkouts (checkout_id,
+                                book_id,
+                                user_id,
+                                checked_out_at,
+                                returned_at)
+VALUES ('e2f0a0b0-0000-4000-8000-000000000001',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'd1e0a0b0-0000-4000-8000-000000000001',
+        '2024-10-01 10:00:00+09',
+        '2024-10-02 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000002',
+        'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
+        'd1e0a0b0-0000-4000-8000-000000000001',
+        '2024-10-03 10:00:00+09',
+        '2024-10-04 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000003',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'd1e0a0b0-0000-4000-8000-000000000002',
+        '2024-10-05 10:00:00+09',
+        '2024-10-06 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000004',
+        'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
+        'd1e0a0b0-0000-4000-8000-000000000002',
+        '2024-10-07 10:00:00+09',
+        '2024-10-08 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000005',
+        '17afb850-c786-49c5-a303-a3a443a2212c',
+        'd1e0a0b0-0000-4000-8000-000000000002',
+        '2024-10-09 10:00:00+09',
+        '2024-10-10 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000006',
+        '17afb850-c786-49c5-a303-a3a443a2212c',
+        'd1e0a0b0-0000-4000-8000-000000000002',
+        '2024-10-11 10:00:00+09',
+        '2024-10-12 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000007',
+        '9890736e-a4e4-461a-a77d-eac3517ef11b',
+        'd1e0a0b0-0000-4000-8000-000000000003',
+        '2024-10-13 10:00:00+09',
+        '2024-10-14 10:00:00+09'),
+       ('e2f0a0b0-0000-4000-8000-000000000008',
+        '17afb850-c786-49c5-a303-a3a443a2212c',
+        'd1e0a0b0-0000-4000-8000-000000000003',
+        '2024-10-15 10:00:00+09',
+        '2024-10-16 10:00:00+09')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,435 @@
+use crate::database::ConnectionPool;
+use crate::database::model::location::LocationRow;
+use crate::repository::book::lock_book_owner;
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::id::LocationId;
+use kernel::model::location::event::{
+    CreateLocation, ShelveBookCopy, UpdateBookLocation, UpdateLocation,
+};
+use kernel::model::location::{Location, LocationKind};
+use kernel::repository::location::LocationRepository;
+use shared::error::{AppError, AppResult};
+use std::str::FromStr;
+
+#[derive(new)]
+pub struct LocationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl LocationRepository for LocationRepositoryImpl {
+    async fn create(&self, event: CreateLocation) -> AppResult<Location> {
+        match (event.kind.parent_kind(), event.parent_id) {
+            (None, None) => {}
+            (None, Some(_)) => {
+                return Err(AppError::UnprocessableEntity(
+                    "拠点には親の場所を指定できません。".into(),
+                ));
+            }
+            (Some(parent_kind), None) => {
+                return Err(AppError::UnprocessableEntity(format!(
+                    "{}の親には{}を指定してください。",
+                    kind_label(event.kind),
+                    kind_label(parent_kind)
+                )));
+            }
+            (Some(parent_kind), Some(parent_id)) => {
+                let kind = self.find_kind(parent_id).await?.ok_or_else(|| {
+                    AppError::EntityNotFound("specified parent location not found".into())
+                })?;
+                if kind != parent_kind {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "{}の親には{}を指定してください。",
+                        kind_label(event.kind),
+                        kind_label(parent_kind)
+                    )));
+                }
+            }
+        }
+
+        let row = sqlx::query_as!(
+            LocationRow,
+            r#"
+                INSERT INTO locations (parent_id, kind, name)
+                VALUES ($1, $2, $3)
+                RETURNING
+                    location_id,
+                    parent_id AS "parent_id?: LocationId",
+                    kind,
+                    name
+            "#,
+            event.parent_id as _,
+            event.kind.as_ref(),
+            event.name
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            // 確認後に親の場所が削除された
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified parent location not found".into())
+            }
+            e => duplicated_name_error(e, &event.name),
+        })?;
+
+        Location::try_from(row)
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Location>> {
+        sqlx::query_as!(
+            LocationRow,
+            r#"
+                WITH RECURSIVE tree AS (
+                    SELECT location_id, parent_id, kind, name, ARRAY[lower(name)] AS path
+                    FROM locations
+                    WHERE parent_id IS NULL
+                    UNION ALL
+                    SELECT l.location_id, l.parent_id, l.kind, l.name, t.path || lower(l.name)
+                    FROM locations AS l
+                    INNER JOIN tree AS t ON l.parent_id = t.location_id
+                )
+                SELECT
+                    location_id AS "location_id!: LocationId",
+                    parent_id AS "parent_id?: LocationId",
+                    kind AS "kind!",
+                    name AS "name!"
+                FROM tree
+                ORDER BY path
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Location::try_from)
+        .collect()
+    }
+
+    async fn find_by_id(&self, location_id: LocationId) -> AppResult<Option<Location>> {
+        sqlx::query_as!(
+            LocationRow,
+            r#"
+                SELECT
+                    location_id,
+                    parent_id AS "parent_id?: LocationId",
+                    kind,
+                    name
+                FROM locations
+                WHERE location_id = $1
+            "#,
+            location_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .map(Location::try_from)
+        .transpose()
+    }
+
+    async fn update(&self, event: UpdateLocation) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                UPDATE locations
+                SET name = $1
+                WHERE location_id = $2
+            "#,
+            event.name,
+            event.location_id as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(|e| duplicated_name_error(e, &event.name))?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified location not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, location_id: LocationId) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM locations WHERE location_id = $1
+            "#,
+            location_id as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::UnprocessableEntity(
+                    "配下の場所または配架された蔵書があるため、削除できません。".into(),
+                )
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified location not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn update_book_location(&self, event: UpdateBookLocation) -> AppResult<()> {
+        if let Some(location_id) = event.location_id {
+            let kind = self
+                .find_kind(location_id)
+                .await?
+                .ok_or_else(|| AppError::EntityNotFound("specified location not found".into()))?;
+            if kind != LocationKind::Shelf {
+                return Err(AppError::UnprocessableEntity(
+                    "配架場所には書架を指定してください。".into(),
+                ));
+            }
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET location_id = $1
+                WHERE book_id = $2
+            "#,
+            event.location_id as _,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            // 確認後に書架が削除された
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified location not found".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn shelve_copy(&self, event: ShelveBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        let res = sqlx::query!(
+            r#"
+                UPDATE book_copies
+                SET current_location_id = NULL
+                WHERE copy_id = $1
+                AND book_id = $2
+            "#,
+            event.copy_id as _,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified book copy not found".into(),
+            ));
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+impl LocationRepositoryImpl {
+    async fn find_kind(&self, location_id: LocationId) -> AppResult<Option<LocationKind>> {
+        sqlx::query_scalar!(
+            r#"
+                SELECT kind FROM locations WHERE location_id = $1
+            "#,
+            location_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .map(|kind| {
+            LocationKind::from_str(&kind)
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+        })
+        .transpose()
+    }
+}
+
+fn kind_label(kind: LocationKind) -> &'static str {
+    match kind {
+        LocationKind::Branch => "拠点",
+        LocationKind::Room => "部屋",
+        LocationKind::Shelf => "書架",
+    }
+}
+
+fn duplicated_name_error(e: sqlx::Error, name: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::UnprocessableEntity(format!("同じ場所に {name} はすでに存在します。"))
+        }
+        e => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::book::BookRepositoryImpl;
+    use crate::repository::checkout::CheckoutRepositoryImpl;
+    use kernel::model::book::BookListOptions;
+    use kernel::model::checkout::CheckoutListOptions;
+    use kernel::model::checkout::event::{CreateCheckout, UpdateReturned};
+    use kernel::model::id::{BookCopyId, BookId, UserId};
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use kernel::repository::checkout::CheckoutRepository;
+    use std::sync::Arc;
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_locations_and_in_transit(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = LocationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let copy_id = BookCopyId::from_str("c0b1e5d0-0000-4000-8000-000000000001")?;
+        let create = |parent_id: Option<LocationId>, kind: LocationKind, name: &str| {
+            repo.create(CreateLocation {
+                parent_id,
+                kind,
+                name: name.into(),
+            })
+        };
+
+        // 階層に合わない親は指定できない
+        let res = create(None, LocationKind::Shelf, "A").await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let main = create(None, LocationKind::Branch, "本館").await?;
+        let annex = create(None, LocationKind::Branch, "別館").await?;
+        let res = create(Some(main.id), LocationKind::Shelf, "A").await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let room = create(Some(main.id), LocationKind::Room, "閲覧室").await?;
+        let shelf = create(Some(room.id), LocationKind::Shelf, "A-1").await?;
+        let res = create(Some(room.id), LocationKind::Shelf, "a-1").await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let names = repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|l| l.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["別館", "本館", "閲覧室", "A-1"]);
+
+        // 書架以外には配架できない
+        let res = repo
+            .update_book_location(UpdateBookLocation {
+                book_id,
+                location_id: Some(room.id),
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        // 所有者以外は配架場所を変更できない
+        let res = repo
+            .update_book_location(UpdateBookLocation {
+                book_id,
+                location_id: Some(shelf.id),
+                requested_user: UserId::new(),
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.update_book_location(UpdateBookLocation {
+            book_id,
+            location_id: Some(shelf.id),
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        let location = book.location.unwrap();
+        assert_eq!(
+            (
+                location.branch.as_str(),
+                location.room.as_str(),
+                location.shelf.as_str()
+            ),
+            ("本館", "閲覧室", "A-1")
+        );
+
+        // 拠点で絞り込むと配下の書架の蔵書が返る
+        let filter = |location: LocationId| BookListOptions {
+            limit: 20,
+            offset: 0,
+            location: Some(location),
+            ..Default::default()
+        };
+        let books = book_repo.find_all(filter(main.id)).await?.items;
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].id, book_id);
+        assert!(book_repo.find_all(filter(annex.id)).await?.items.is_empty());
+
+        // 配下に場所や蔵書がある場所は削除できない
+        let res = repo.delete(shelf.id).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 別の拠点で返却されると回送中になる
+        checkout_repo
+            .create(CreateCheckout::new(book_id, owner, chrono::Utc::now()))
+            .await?;
+        let checkout = checkout_repo
+            .find_unreturned_by_user_id(owner, CheckoutListOptions::default())
+            .await?
+            .items
+            .remove(0);
+        checkout_repo
+            .update_returned(UpdateReturned::new(
+                checkout.id,
+                book_id,
+                owner,
+                chrono::Utc::now(),
+                Some(annex.id),
+                None,
+            ))
+            .await?;
+
+        let copies = book_repo.find_copies(book_id).await?;
+        assert_eq!(
+            copies[0].in_transit_at.as_ref().map(|l| l.id),
+            Some(annex.id)
+        );
+
+        repo.shelve_copy(ShelveBookCopy {
+            book_id,
+            copy_id,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+        let copies = book_repo.find_copies(book_id).await?;
+        assert!(copies[0].in_transit_at.is_none());
+
+        Ok(())
+    }
+}
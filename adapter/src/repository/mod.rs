@@ -1,5 +1,13 @@
 pub mod auth;
+pub mod author;
+pub mod blob_store;
 pub mod book;
+pub mod book_cover;
+pub mod book_metadata;
 pub mod checkout;
 pub mod health;
+pub mod location;
+pub mod recommendation;
+pub mod review;
+pub mod tag;
 pub mod user;
@@ -0,0 +1,254 @@
+use crate::database::ConnectionPool;
+use crate::database::model::recommendation::RecommendedBookRow;
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::book::BookStatus;
+use kernel::model::id::{BookId, UserId};
+use kernel::model::isbn::Isbn;
+use kernel::model::recommendation::RecommendedBook;
+use kernel::repository::recommendation::RecommendationRepository;
+use shared::error::{AppError, AppResult};
+
+#[derive(new)]
+pub struct RecommendationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl RecommendationRepository for RecommendationRepositoryImpl {
+    async fn refresh(&self) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 同時に作り直しが走っても主キーが衝突しないよう、集計中は表をロックする
+        sqlx::query!("LOCK TABLE book_co_borrows IN EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        sqlx::query!("DELETE FROM book_co_borrows")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        // 同じユーザーが借りたことのある蔵書の組を数える。
+        // 同じ蔵書を何度借りても 1 回と数え、除籍した蔵書や削除された蔵書は含めない。
+        sqlx::query!(
+            r#"
+                WITH borrows AS (
+                    SELECT DISTINCT h.user_id, h.book_id
+                    FROM (
+                        SELECT user_id, book_id FROM checkouts
+                        UNION ALL
+                        SELECT user_id, book_id FROM returned_checkouts
+                    ) AS h
+                    INNER JOIN books AS b USING(book_id)
+                    WHERE b.deleted_at IS NULL
+                )
+                INSERT INTO book_co_borrows (book_id, related_book_id, borrower_count)
+                SELECT a.book_id, r.book_id, COUNT(*)
+                FROM borrows AS a
+                INNER JOIN borrows AS r
+                    ON r.user_id = a.user_id AND r.book_id <> a.book_id
+                GROUP BY a.book_id, r.book_id
+            "#
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn find_related(&self, book_id: BookId, limit: i64) -> AppResult<Vec<RecommendedBook>> {
+        let exists = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+            "#,
+            book_id as _
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !exists {
+            return Err(AppError::EntityNotFound("specified book not found".into()));
+        }
+
+        let rows = sqlx::query_as!(
+            RecommendedBookRow,
+            r#"
+                SELECT
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.isbn AS "isbn: Isbn",
+                    r.borrower_count AS score
+                FROM book_co_borrows AS r
+                INNER JOIN books AS b ON b.book_id = r.related_book_id
+                WHERE r.book_id = $1
+                AND b.deleted_at IS NULL
+                AND b.status = ANY($3)
+                ORDER BY r.borrower_count DESC, b.title, b.book_id
+                LIMIT $2
+            "#,
+            book_id as _,
+            limit,
+            &recommendable_statuses()
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().map(RecommendedBook::from).collect())
+    }
+
+    async fn find_for_user(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RecommendedBook>> {
+        // 借りたことのある蔵書それぞれについて一緒に借りられた蔵書を集め、その強さを合計する
+        let rows = sqlx::query_as!(
+            RecommendedBookRow,
+            r#"
+                WITH borrowed AS (
+                    SELECT book_id FROM checkouts WHERE user_id = $1
+                    UNION
+                    SELECT book_id FROM returned_checkouts WHERE user_id = $1
+                )
+                SELECT
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.isbn AS "isbn: Isbn",
+                    SUM(r.borrower_count)::BIGINT AS "score!"
+                FROM book_co_borrows AS r
+                INNER JOIN borrowed AS u ON u.book_id = r.book_id
+                INNER JOIN books AS b ON b.book_id = r.related_book_id
+                WHERE r.related_book_id NOT IN (SELECT book_id FROM borrowed)
+                AND b.user_id <> $1
+                AND b.deleted_at IS NULL
+                AND b.status = ANY($3)
+                GROUP BY b.book_id
+                ORDER BY SUM(r.borrower_count) DESC, b.title, b.book_id
+                LIMIT $2
+            "#,
+            user_id as _,
+            limit,
+            &recommendable_statuses()
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().map(RecommendedBook::from).collect())
+    }
+}
+
+/// 推薦する蔵書の状態。修理中の蔵書は修理が終われば借りられるため含める。
+fn recommendable_statuses() -> Vec<String> {
+    [BookStatus::Available, BookStatus::InRepair]
+        .iter()
+        .map(|status| status.as_ref().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[sqlx::test(fixtures("common", "book", "recommendation"))]
+    async fn test_refresh_and_find_recommendations(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = RecommendationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let practical = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let zero = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let web = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let first = UserId::from_str("d1e0a0b0-0000-4000-8000-000000000001")?;
+        let third = UserId::from_str("d1e0a0b0-0000-4000-8000-000000000003")?;
+
+        // 作り直す前は集計が空なので何も推薦されない
+        assert!(repo.find_related(practical, 10).await?.is_empty());
+
+        repo.refresh().await?;
+        // 何度作り直しても同じ結果になる
+        repo.refresh().await?;
+
+        let related = repo.find_related(zero, 10).await?;
+        let scores = related
+            .iter()
+            .map(|b| (b.book_id, b.score))
+            .collect::<Vec<_>>();
+        assert_eq!(scores, vec![(practical, 2), (web, 1)]);
+
+        let related = repo.find_related(practical, 1).await?;
+        assert_eq!(related.len(), 1);
+
+        let res = repo.find_related(BookId::new(), 10).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 借りたことのある蔵書は除かれ、残りの蔵書の強さは合計される
+        let recommended = repo.find_for_user(first, 10).await?;
+        let scores = recommended
+            .iter()
+            .map(|b| (b.book_id, b.score))
+            .collect::<Vec<_>>();
+        assert_eq!(scores, vec![(web, 3)]);
+
+        let recommended = repo.find_for_user(third, 10).await?;
+        let scores = recommended
+            .iter()
+            .map(|b| (b.book_id, b.score))
+            .collect::<Vec<_>>();
+        assert_eq!(scores, vec![(zero, 3)]);
+
+        // 自分が所有する蔵書は推薦されない
+        sqlx::query!(
+            r#"
+                INSERT INTO checkouts (book_id, copy_id, user_id)
+                VALUES ($1, 'c0b1e5d0-0000-4000-8000-000000000001', $2)
+            "#,
+            practical as _,
+            owner as _
+        )
+        .execute(&pool)
+        .await?;
+        repo.refresh().await?;
+        assert!(repo.find_for_user(owner, 10).await?.is_empty());
+
+        // 貸し出せない状態の蔵書は推薦されない
+        sqlx::query!(
+            r#"
+                UPDATE books SET status = 'lost' WHERE book_id = $1
+            "#,
+            web as _
+        )
+        .execute(&pool)
+        .await?;
+        let related = repo.find_related(zero, 10).await?;
+        assert!(related.iter().all(|b| b.book_id != web));
+
+        // 除籍した蔵書は集計に含めない
+        sqlx::query!(
+            r#"
+                UPDATE books SET deleted_at = CURRENT_TIMESTAMP(3) WHERE book_id = $1
+            "#,
+            zero as _
+        )
+        .execute(&pool)
+        .await?;
+        repo.refresh().await?;
+        let counted = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!" FROM book_co_borrows
+                WHERE book_id = $1 OR related_book_id = $1
+            "#,
+            zero as _
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(counted, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+use crate::database::ConnectionPool;
+use crate::database::model::review::{PaginatedReviewRow, ReviewRow};
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::id::{BookId, ReviewId, UserId};
+use kernel::model::list::PaginatedList;
+use kernel::model::review::event::{CreateReview, DeleteReview, UpdateReview};
+use kernel::model::review::{Review, ReviewListOptions};
+use kernel::repository::review::ReviewRepository;
+use shared::error::{AppError, AppResult};
+use sqlx::PgConnection;
+
+#[derive(new)]
+pub struct ReviewRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl ReviewRepository for ReviewRepositoryImpl {
+    async fn create(&self, event: CreateReview) -> AppResult<Review> {
+        let res = sqlx::query!(
+            r#"
+                SELECT
+                    EXISTS (
+                        SELECT 1 FROM returned_checkouts AS rc
+                        WHERE rc.book_id = b.book_id AND rc.user_id = $2
+                    ) AS "borrowed!"
+                FROM books AS b
+                WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
+            "#,
+            event.book_id as _,
+            event.requested_user as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        if !res.borrowed {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍 ({}) は借りて返却したことがないため、レビューを書けません。",
+                event.book_id
+            )));
+        }
+
+        let row = sqlx::query_as!(
+            ReviewRow,
+            r#"
+                WITH r AS (
+                    INSERT INTO book_reviews (book_id, user_id, rating, comment)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING *
+                )
+                SELECT
+                    r.review_id,
+                    r.book_id,
+                    r.user_id,
+                    u.name AS user_name,
+                    r.rating,
+                    r.comment,
+                    r.created_at,
+                    r.updated_at
+                FROM r
+                INNER JOIN users AS u USING(user_id)
+            "#,
+            event.book_id as _,
+            event.requested_user as _,
+            event.rating,
+            event.comment
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                AppError::UnprocessableEntity(format!(
+                    "書籍 ({}) のレビューはすでに書いています。",
+                    event.book_id
+                ))
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        Ok(Review::from(row))
+    }
+
+    async fn find_by_book_id(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<Review>> {
+        let ReviewListOptions { limit, offset } = options;
+
+        // レビューがないことと蔵書がないことを区別するため、先に蔵書の存在を確認する
+        let exists = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+            "#,
+            book_id as _
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !exists {
+            return Err(AppError::EntityNotFound("specified book not found".into()));
+        }
+
+        let rows = sqlx::query_as!(
+            PaginatedReviewRow,
+            r#"
+                SELECT
+                    COUNT(*) OVER() AS "total!",
+                    r.review_id,
+                    r.book_id,
+                    r.user_id,
+                    u.name AS user_name,
+                    r.rating,
+                    r.comment,
+                    r.created_at,
+                    r.updated_at
+                FROM book_reviews AS r
+                INNER JOIN users AS u USING(user_id)
+                WHERE r.book_id = $1
+                ORDER BY r.created_at DESC, r.review_id
+                LIMIT $2
+                OFFSET $3
+            "#,
+            book_id as _,
+            limit,
+            offset
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let items = rows.into_iter().map(Review::from).collect();
+
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        })
+    }
+
+    async fn update(&self, event: UpdateReview) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_reviewer(
+            &mut tx,
+            event.review_id,
+            event.book_id,
+            event.requested_user,
+            false,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE book_reviews
+                SET rating = $1, comment = $2
+                WHERE review_id = $3
+            "#,
+            event.rating,
+            event.comment,
+            event.review_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, event: DeleteReview) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_reviewer(
+            &mut tx,
+            event.review_id,
+            event.book_id,
+            event.requested_user,
+            event.is_admin,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM book_reviews WHERE review_id = $1
+            "#,
+            event.review_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+/// レビューの行をロックし、操作できるのがレビューを書いたユーザー（`is_admin` の場合は管理者も）であることを確かめる。
+async fn authorize_reviewer(
+    conn: &mut PgConnection,
+    review_id: ReviewId,
+    book_id: BookId,
+    user_id: UserId,
+    is_admin: bool,
+) -> AppResult<()> {
+    let reviewer = sqlx::query_scalar!(
+        r#"
+            SELECT user_id AS "user_id: UserId"
+            FROM book_reviews
+            WHERE review_id = $1
+            AND book_id = $2
+            FOR UPDATE
+        "#,
+        review_id as _,
+        book_id as _
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified review not found".into()))?;
+
+    if reviewer != user_id && !is_admin {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::book::BookRepositoryImpl;
+    use crate::repository::user::UserRepositoryImpl;
+    use kernel::model::book::{BookListOptions, BookSort, BookSortKey};
+    use kernel::model::list::SortOrder;
+    use kernel::model::user::event::CreateUser;
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use kernel::repository::user::UserRepository;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[sqlx::test(fixtures("common", "book", "checkout"))]
+    async fn test_manage_reviews(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = ReviewRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let borrower = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = user_repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?
+            .id;
+        let create = |requested_user, rating| CreateReview {
+            book_id,
+            rating,
+            comment: "読みやすい".into(),
+            requested_user,
+        };
+
+        // 借りて返却したことのない蔵書にはレビューを書けない
+        let res = repo.create(create(other, 3)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let review = repo.create(create(borrower, 4)).await?;
+        assert_eq!(review.reviewer.id, borrower);
+        assert_eq!(review.rating, 4);
+
+        // 同じ蔵書には 1 件までしか書けない
+        let res = repo.create(create(borrower, 5)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // レビューを書いたユーザー以外は更新できない
+        let update = |requested_user| UpdateReview {
+            review_id: review.id,
+            book_id,
+            rating: 2,
+            comment: "".into(),
+            requested_user,
+        };
+        let res = repo.update(update(other)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.update(update(borrower)).await?;
+
+        let options = ReviewListOptions {
+            limit: 20,
+            offset: 0,
+        };
+        let reviews = repo.find_by_book_id(book_id, options).await?;
+        assert_eq!(reviews.total, 1);
+        let res = repo.find_by_book_id(BookId::new(), options).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(reviews.items[0].rating, 2);
+        assert_eq!(reviews.items[0].comment, "");
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.average_rating, Some(2.0));
+        assert_eq!(book.review_count, 1);
+
+        // 評価の高い順では、レビューのない蔵書は後ろに並ぶ
+        let books = book_repo
+            .find_all(BookListOptions {
+                limit: 20,
+                sort: Some(BookSort {
+                    key: BookSortKey::Rating,
+                    order: SortOrder::Desc,
+                }),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(books.items[0].id, book_id);
+        assert!(books.items[1].average_rating.is_none());
+
+        // 管理者は他のユーザーのレビューも削除できる
+        let delete = |requested_user, is_admin| DeleteReview {
+            review_id: review.id,
+            book_id,
+            requested_user,
+            is_admin,
+        };
+        let res = repo.delete(delete(other, false)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.delete(delete(other, true)).await?;
+        let res = repo.delete(delete(borrower, false)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.average_rating, None);
+        assert_eq!(book.review_count, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,465 @@
+use crate::database::ConnectionPool;
+use crate::database::model::tag::TagRow;
+use crate::repository::book::lock_book_owner;
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::model::id::TagId;
+use kernel::model::tag::Tag;
+use kernel::model::tag::event::{AttachTag, CreateTag, DetachTag, MergeTag, UpdateTag};
+use kernel::repository::tag::TagRepository;
+use shared::error::{AppError, AppResult};
+use sqlx::PgConnection;
+
+#[derive(new)]
+pub struct TagRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl TagRepository for TagRepositoryImpl {
+    async fn create(&self, event: CreateTag) -> AppResult<Tag> {
+        let row = sqlx::query_as!(
+            TagRow,
+            r#"
+                INSERT INTO tags (name)
+                VALUES ($1)
+                RETURNING tag_id, name
+            "#,
+            event.name
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(|e| duplicated_name_error(e, &event.name))?;
+
+        Ok(Tag::from(row))
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Tag>> {
+        let tags = sqlx::query_as!(
+            TagRow,
+            r#"
+                SELECT tag_id, name
+                FROM tags
+                ORDER BY lower(name)
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Tag::from)
+        .collect();
+
+        Ok(tags)
+    }
+
+    async fn find_by_id(&self, tag_id: TagId) -> AppResult<Option<Tag>> {
+        let row = sqlx::query_as!(
+            TagRow,
+            r#"
+                SELECT tag_id, name
+                FROM tags
+                WHERE tag_id = $1
+            "#,
+            tag_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(row.map(Tag::from))
+    }
+
+    async fn update(&self, event: UpdateTag) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let res = sqlx::query!(
+            r#"
+                UPDATE tags
+                SET name = $1
+                WHERE tag_id = $2
+            "#,
+            event.name,
+            event.tag_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| duplicated_name_error(e, &event.name))?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound("specified tag not found".into()));
+        }
+
+        bump_tagged_book_versions(&mut tx, event.tag_id).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn merge(&self, event: MergeTag) -> AppResult<()> {
+        if event.source == event.target {
+            return Err(AppError::UnprocessableEntity(
+                "同じタグどうしは統合できません。".into(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        // 統合中に他の操作で削除されないよう、両方のタグの行をロックしてから確認する
+        let locked = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM (
+                    SELECT 1 FROM tags
+                    WHERE tag_id IN ($1, $2)
+                    FOR UPDATE
+                ) AS t
+            "#,
+            event.source as _,
+            event.target as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if locked < 2 {
+            return Err(AppError::EntityNotFound("specified tag not found".into()));
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO book_tags (book_id, tag_id)
+                SELECT book_id, $2
+                FROM book_tags
+                WHERE tag_id = $1
+                ON CONFLICT DO NOTHING
+            "#,
+            event.source as _,
+            event.target as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        bump_tagged_book_versions(&mut tx, event.source).await?;
+
+        // 統合元のタグと蔵書との関連は、外部キーの ON DELETE CASCADE で削除される
+        sqlx::query!(
+            r#"
+                DELETE FROM tags WHERE tag_id = $1
+            "#,
+            event.source as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn attach(&self, event: AttachTag) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO book_tags (book_id, tag_id)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+            "#,
+            event.book_id as _,
+            event.tag_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified tag not found".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn detach(&self, event: DetachTag) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_book_owner(&mut tx, event.book_id, event.requested_user, event.is_admin).await?;
+
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM book_tags
+                WHERE book_id = $1
+                AND tag_id = $2
+            "#,
+            event.book_id as _,
+            event.tag_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified book tag not found".into(),
+            ));
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+/// タグが付いた蔵書の ETag が変わるよう、蔵書の版数を更新する。
+async fn bump_tagged_book_versions(conn: &mut PgConnection, tag_id: TagId) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE books SET version = version + 1
+            WHERE book_id IN (SELECT book_id FROM book_tags WHERE tag_id = $1)
+        "#,
+        tag_id as _
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+fn duplicated_name_error(e: sqlx::Error, name: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            AppError::UnprocessableEntity(format!("タグ ({name}) はすでに存在します。"))
+        }
+        e => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::book::BookRepositoryImpl;
+    use kernel::model::book::BookListOptions;
+    use kernel::model::id::{BookId, UserId};
+    use kernel::repository::blob_store::MockBlobStore;
+    use kernel::repository::book::BookRepository;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_manage_tags(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = TagRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+
+        let rust = repo
+            .create(CreateTag {
+                name: "Rust".into(),
+            })
+            .await?;
+        let db = repo
+            .create(CreateTag {
+                name: "database".into(),
+            })
+            .await?;
+
+        // 大文字・小文字だけが異なる名前のタグは作れない
+        let res = repo
+            .create(CreateTag {
+                name: "rust".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let version = book_repo.find_by_id(book_id).await?.unwrap().version;
+        for tag_id in [rust.id, db.id] {
+            repo.attach(AttachTag {
+                book_id,
+                tag_id,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await?;
+        }
+        // タグを付けると蔵書の版数が更新される
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.version, version + 2);
+
+        // 付いているタグを再度付けても何も起きない
+        repo.attach(AttachTag {
+            book_id,
+            tag_id: rust.id,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+
+        // 所有者以外は付けられないが、管理者は付けられ監査ログに残る
+        let other = UserId::new();
+        let res = repo
+            .attach(AttachTag {
+                book_id,
+                tag_id: rust.id,
+                requested_user: other,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.attach(AttachTag {
+            book_id,
+            tag_id: rust.id,
+            requested_user: other,
+            is_admin: true,
+        })
+        .await?;
+        let logs = book_repo.find_audit_logs(book_id).await?;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].owner_id, owner);
+        assert_eq!(logs[0].operated_by.id, other);
+        // 存在しないタグは付けられない
+        let res = repo
+            .attach(AttachTag {
+                book_id,
+                tag_id: TagId::new(),
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        let names = book
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["database", "Rust"]);
+
+        let filter = |tags: &[&str]| BookListOptions {
+            limit: 20,
+            offset: 0,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        let res = book_repo.find_all(filter(&["RUST", "Database"])).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, book_id);
+        let res = book_repo.find_all(filter(&["Rust", "design"])).await?;
+        assert_eq!(res.total, 0);
+
+        repo.update(UpdateTag {
+            tag_id: db.id,
+            name: "Databases".into(),
+        })
+        .await?;
+        let res = repo
+            .update(UpdateTag {
+                tag_id: db.id,
+                name: "RUST".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        repo.detach(DetachTag {
+            book_id,
+            tag_id: db.id,
+            requested_user: owner,
+            is_admin: false,
+        })
+        .await?;
+        let res = repo
+            .detach(DetachTag {
+                book_id,
+                tag_id: db.id,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.tags, vec![rust.clone()]);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_merge_tags(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = TagRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            Arc::new(MockBlobStore::new()),
+        );
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_ids = [
+            BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?,
+            BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?,
+        ];
+
+        let source = repo
+            .create(CreateTag {
+                name: "rustlang".into(),
+            })
+            .await?;
+        let target = repo
+            .create(CreateTag {
+                name: "Rust".into(),
+            })
+            .await?;
+        // 1 冊目には両方、2 冊目には統合元のタグだけを付ける
+        for (book_id, tag_id) in [
+            (book_ids[0], source.id),
+            (book_ids[0], target.id),
+            (book_ids[1], source.id),
+        ] {
+            repo.attach(AttachTag {
+                book_id,
+                tag_id,
+                requested_user: owner,
+                is_admin: false,
+            })
+            .await?;
+        }
+
+        let res = repo
+            .merge(MergeTag {
+                source: target.id,
+                target: target.id,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let res = repo
+            .merge(MergeTag {
+                source: source.id,
+                target: TagId::new(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        repo.merge(MergeTag {
+            source: source.id,
+            target: target.id,
+        })
+        .await?;
+
+        assert!(repo.find_by_id(source.id).await?.is_none());
+        assert_eq!(repo.find_all().await?, vec![target.clone()]);
+        for book_id in book_ids {
+            let book = book_repo.find_by_id(book_id).await?.unwrap();
+            assert_eq!(book.tags, vec![target.clone()]);
+        }
+
+        Ok(())
+    }
+}
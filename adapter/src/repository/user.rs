@@ -5,9 +5,12 @@ use derive_new::new;
 use kernel::model::id::UserId;
 use kernel::model::role::Role;
 use kernel::model::user::User;
-use kernel::model::user::event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole};
+use kernel::model::user::event::{
+    CreateUser, DeleteUser, UpdateUser, UpdateUserPassword, UpdateUserRole,
+};
 use kernel::repository::user::UserRepository;
 use shared::error::{AppError, AppResult};
+use sqlx::{Postgres, QueryBuilder};
 
 #[derive(new)]
 pub struct UserRepositoryImpl {
@@ -103,6 +106,51 @@ impl UserRepository for UserRepositoryImpl {
         })
     }
 
+    async fn update(&self, event: UpdateUser) -> AppResult<User> {
+        if event.name.is_none() && event.email.is_none() {
+            return self
+                .find_current_user(event.user_id)
+                .await?
+                .ok_or_else(|| AppError::EntityNotFound("Specified user not found".into()));
+        }
+
+        // 指定された列のみを更新する
+        let mut builder = QueryBuilder::<Postgres>::new("UPDATE users SET ");
+        let mut columns = builder.separated(", ");
+        if let Some(name) = event.name {
+            columns.push("name = ").push_bind_unseparated(name);
+        }
+        if let Some(email) = &event.email {
+            columns
+                .push("email = ")
+                .push_bind_unseparated(email.clone());
+        }
+        builder
+            .push(" WHERE user_id = ")
+            .push_bind(event.user_id)
+            .push(" RETURNING user_id");
+
+        let user_id: Option<UserId> = builder
+            .build_query_scalar()
+            .fetch_optional(self.db.inner_ref())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                    AppError::UnprocessableEntity(format!(
+                        "メールアドレス ({}) はすでに使用されています。",
+                        event.email.as_deref().unwrap_or_default()
+                    ))
+                }
+                e => AppError::SpecificOperationError(e),
+            })?;
+
+        let user_id =
+            user_id.ok_or_else(|| AppError::EntityNotFound("Specified user not found".into()))?;
+        self.find_current_user(user_id)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("Specified user not found".into()))
+    }
+
     async fn update_password(&self, event: UpdateUserPassword) -> AppResult<()> {
         let mut tx = self.db.begin().await?;
 
@@ -190,3 +238,54 @@ fn verify_password(password: &str, hash: &str) -> AppResult<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_update_user(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+
+        // 指定しなかった項目は変更されない
+        let user = repo
+            .update(UpdateUser {
+                user_id,
+                name: Some("Updated Name".into()),
+                email: None,
+            })
+            .await?;
+        assert_eq!(user.name, "Updated Name");
+        assert_eq!(user.email, "eleazar.fig@example.com");
+
+        // 他のユーザーのメールアドレスには変更できない
+        let res = repo
+            .update(UpdateUser {
+                user_id,
+                name: None,
+                email: Some(other.email),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let res = repo
+            .update(UpdateUser {
+                user_id: UserId::new(),
+                name: Some("Nobody".into()),
+                email: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+}
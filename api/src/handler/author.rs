@@ -0,0 +1,229 @@
+use crate::{
+    extractor::AuthorizedUser,
+    model::author::{
+        AuthorDetailResponse, AuthorResponse, AuthorsResponse, CreateAuthorRequest,
+        MergeAuthorRequest, MergeAuthorRequestWithId, UpdateAuthorRequest,
+        UpdateAuthorRequestWithId, UpdateBookAuthorsRequest, UpdateBookAuthorsRequestWithIds,
+    },
+};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use garde::Validate;
+use kernel::model::id::{AuthorId, BookId};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/authors",
+        responses(
+            (status = 200, description = "著者の一覧の取得に成功した場合。", body = AuthorsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_author_list(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<AuthorsResponse>> {
+    let items = registry
+        .author_repository()
+        .find_all()
+        .await?
+        .into_iter()
+        .map(AuthorResponse::from)
+        .collect();
+
+    Ok(Json(AuthorsResponse { items }))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/authors",
+        request_body = CreateAuthorRequest,
+        responses(
+            (status = 201, description = "著者の登録に成功した場合。", body = AuthorResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 422, description = "同じ名前の著者がすでにいる場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn register_author(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateAuthorRequest>,
+) -> AppResult<(StatusCode, Json<AuthorResponse>)> {
+    req.validate()?;
+
+    let author = registry.author_repository().create(req.into()).await?;
+
+    Ok((StatusCode::CREATED, Json(author.into())))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/authors/{author_id}",
+        responses(
+            (status = 200, description = "著者と、著者が関わった蔵書の取得に成功した場合。", body = AuthorDetailResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "著者が存在しない場合。")
+        ),
+        params(
+            ("author_id" = String, Path, description = "著者ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_author(
+    _user: AuthorizedUser,
+    Path(author_id): Path<AuthorId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<AuthorDetailResponse>> {
+    let author = registry
+        .author_repository()
+        .find_by_id(author_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified author not found".into()))?;
+
+    Ok(Json(author.into()))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/authors/{author_id}",
+        request_body = UpdateAuthorRequest,
+        responses(
+            (status = 200, description = "著者の名前の変更に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "著者が存在しない場合。"),
+            (status = 422, description = "同じ名前の著者がすでにいる場合。")
+        ),
+        params(
+            ("author_id" = String, Path, description = "著者ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_author(
+    user: AuthorizedUser,
+    Path(author_id): Path<AuthorId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateAuthorRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    req.validate()?;
+
+    registry
+        .author_repository()
+        .update(UpdateAuthorRequestWithId::new(author_id, req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/authors/{author_id}/merge",
+        request_body = MergeAuthorRequest,
+        responses(
+            (status = 200, description = "著者の統合に成功した場合。統合元の著者は削除される。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "統合元または統合先の著者が存在しない場合。"),
+            (status = 422, description = "統合元と統合先に同じ著者を指定した場合。")
+        ),
+        params(
+            ("author_id" = String, Path, description = "統合元の著者ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn merge_author(
+    user: AuthorizedUser,
+    Path(author_id): Path<AuthorId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<MergeAuthorRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .author_repository()
+        .merge(MergeAuthorRequestWithId::new(author_id, req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/authors",
+        request_body = UpdateBookAuthorsRequest,
+        responses(
+            (status = 200, description = "蔵書に関わった著者の置き換えに成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもない場合。"),
+            (status = 404, description = "蔵書または著者が存在しない場合。"),
+            (status = 422, description = "同じ著者を同じ役割で複数回指定した場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_book_authors(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateBookAuthorsRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    registry
+        .author_repository()
+        .update_book_authors(
+            UpdateBookAuthorsRequestWithIds::new(book_id, user.id(), user.is_admin(), req).into(),
+        )
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -1,16 +1,47 @@
-use crate::model::book::{UpdateBookRequest, UpdateBookRequestWithIds};
+use crate::model::book::{
+    PatchBookRequest, PatchBookRequestWithIds, UpdateBookRequest, UpdateBookRequestWithIds,
+};
+use crate::model::cover::CoverImageUpload;
 use crate::{
     extractor::AuthorizedUser,
-    model::book::{BookListQuery, BookResponse, CreateBookRequest, PaginatedBookResponse},
+    model::book::{
+        BookAuditLogsResponse, BookCopiesResponse, BookExportQuery, BookImportFormat,
+        BookImportQuery, BookImportResponse, BookListQuery, BookListResponse, BookLookupQuery,
+        BookMetadataResponse, BookRegistrationResponse, BookResponse, BookRevisionsResponse,
+        BookStatusChangeResponse, BookStatusHistoryResponse, ChangeBookStatusRequest,
+        ChangeBookStatusRequestWithIds, CreateBookCopyRequest, CreateBookCopyRequestWithIds,
+        CreateBookRequest, CursorPaginatedBookResponse, DeleteBookQuery, PaginatedBookResponse,
+        ParsedBookImport, TransferBookRequest,
+    },
 };
 use axum::Json;
+use axum::body::{Body, Bytes};
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, IF_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum_extra::TypedHeader;
+use axum_extra::headers::{CacheControl, ETag, HeaderMapExt, IfMatch, IfNoneMatch, LastModified};
 use garde::Validate;
-use kernel::model::book::event::DeleteBook;
-use kernel::model::id::BookId;
+use kernel::model::book::BookCoverSize;
+use kernel::model::book::BookImportStatus;
+use kernel::model::book::BookListOptions;
+use kernel::model::book::BookRegistration;
+use kernel::model::book::BookStatus;
+use kernel::model::book::event::{
+    ChangeBookStatus, DeleteBook, DeleteBookCopy, DeleteBookCover, ImportBooks, MarkBookFound,
+    PurgeBook, RestoreBook, RevertBook, TransferBook,
+};
+use kernel::model::book::outdated_version_error;
+use kernel::model::id::{BookCopyId, BookId, BookRevisionId};
+use kernel::model::isbn::Isbn;
 use registry::AppRegistry;
 use shared::error::{AppError, AppResult};
+use std::time::{Duration, SystemTime};
+use tokio_stream::StreamExt;
+
+/// 表紙画像をキャッシュしてよい期間。画像が更新されると URL が変わるため、長めにしている。
+const COVER_MAX_AGE: Duration = Duration::from_secs(60 * 60 * 24);
 
 #[cfg_attr(
     debug_assertions,
@@ -19,10 +50,13 @@ use shared::error::{AppError, AppResult};
         path = "/api/v1/books",
         request_body = CreateBookRequest,
         responses(
-            (status = 201, description = "蔵書の登録に成功した場合。"),
-            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 201, description = "蔵書の登録に成功した場合。", body = BookRegistrationResponse),
+            (status = 200, description = "`addCopy` を指定し、重複する既存の蔵書に現物を追加した場合。", body = BookRegistrationResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合（ISBN のチェックディジットが誤っている場合を含む）。"),
             (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
-            (status = 422, description = "リクエストした蔵書の登録に失敗した場合。")
+            (status = 409, description = "重複する蔵書がすでに登録されている場合。レスポンスの `existingId` に既存の蔵書の ID を含む。"),
+            (status = 422, description = "書名または著者を省略したが、ISBN に対応する書誌情報が見つからなかった場合。"),
+            (status = 502, description = "書誌情報の取得に失敗した場合。")
         )
     )
 )]
@@ -36,14 +70,108 @@ pub async fn register_book(
     user: AuthorizedUser,
     State(registry): State<AppRegistry>,
     Json(req): Json<CreateBookRequest>,
-) -> AppResult<StatusCode> {
+) -> AppResult<(StatusCode, Json<BookRegistrationResponse>)> {
     req.validate()?;
+    let req = if req.needs_metadata() {
+        let isbn = req.isbn.parse::<Isbn>()?;
+        let metadata = registry
+            .book_metadata_provider()
+            .find_by_isbn(&isbn)
+            .await?
+            .ok_or_else(|| {
+                AppError::UnprocessableEntity(format!(
+                    "ISBN ({isbn}) の書誌情報が見つかりませんでした。書名と著者を指定してください。"
+                ))
+            })?;
+        req.fill_with(metadata)
+    } else {
+        req
+    };
+    let create_book = req.try_into()?;
 
-    registry
+    let (status, id) = match registry
         .book_repository()
-        .create(req.into(), user.id())
-        .await
-        .map(|_| StatusCode::CREATED)
+        .create(create_book, user.id())
+        .await?
+    {
+        BookRegistration::Created(id) => (StatusCode::CREATED, id),
+        BookRegistration::CopyAdded(id) => (StatusCode::OK, id),
+    };
+
+    Ok((status, Json(BookRegistrationResponse { id })))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/import",
+        request_body(
+            content(
+                (String = "text/csv"),
+                (String = "application/jsonl")
+            ),
+            description = "CSV（1 行目はヘッダー: title, author, isbn, description, copies）または JSON Lines"
+        ),
+        responses(
+            (status = 200, description = "一括登録を実行した場合。各行の結果を返す。", body = BookImportResponse),
+            (status = 400, description = "データを読み込めなかった場合、または Content-Type がサポートされていない場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 422, description = "atomic=true で、不備のある行や登録に失敗した行があったため何も登録しなかった場合。失敗した行の結果を返す。", body = BookImportResponse)
+        ),
+        params(
+            ("atomic" = Option<bool>, Query, description = "true の場合は、1 行でも登録できなければ何も登録しない")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, body),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn import_books(
+    user: AuthorizedUser,
+    Query(query): Query<BookImportQuery>,
+    State(registry): State<AppRegistry>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<(StatusCode, Json<BookImportResponse>)> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let format = BookImportFormat::from_content_type(content_type)?;
+    let ParsedBookImport {
+        books,
+        mut failures,
+    } = ParsedBookImport::parse(format, &body)?;
+
+    if query.atomic && !failures.is_empty() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(BookImportResponse::from(failures)),
+        ));
+    }
+
+    let event = ImportBooks {
+        books,
+        requested_user: user.id(),
+        atomic: query.atomic,
+    };
+    let results = registry.book_repository().import(event).await?;
+    let rolled_back = query.atomic
+        && results
+            .iter()
+            .any(|r| matches!(r.status, BookImportStatus::Failed(_)));
+    failures.extend(results);
+
+    let status = if rolled_back {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(BookImportResponse::from(failures))))
 }
 
 #[cfg_attr(
@@ -52,30 +180,160 @@ pub async fn register_book(
         get,
         path = "/api/v1/books",
         responses(
-            (status = 200, description = "蔵書一覧の取得に成功した場合。", body=PaginatedBookResponse),
+            (status = 200, description = "蔵書一覧の取得に成功した場合。", body=BookListResponse),
             (status = 400, description = "指定されたクエリの値に不備があった場合。"),
             (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
         ),
         params(
             ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定"),
-            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置")
+            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置"),
+            ("q" = Option<String>, Query, description = "書名・著者名・ISBN・説明を対象とした検索キーワード（空白区切りで AND 検索）"),
+            ("author" = Option<String>, Query, description = "著者名（部分一致）"),
+            ("ownerId" = Option<String>, Query, description = "所有者のユーザーID"),
+            ("locationId" = Option<String>, Query, description = "配架場所の ID。拠点や部屋を指定した場合は、その配下の書架に配架された蔵書に絞り込む"),
+            ("availability" = Option<crate::model::book::BookAvailability>, Query, description = "貸出状況。checked_out は現物がすべて貸出中の蔵書、unavailable は修理中・紛失・貸出停止の蔵書"),
+            ("tags" = Option<String>, Query, description = "カンマ区切りのタグ名（大文字・小文字を区別しない）。指定したすべてのタグが付いた蔵書に絞り込む"),
+            ("createdFrom" = Option<String>, Query, format = DateTime, description = "登録日時の下限（この日時を含む）"),
+            ("createdTo" = Option<String>, Query, format = DateTime, description = "登録日時の上限（この日時を含まない）"),
+            ("deleted" = Option<bool>, Query, description = "true の場合は除籍した蔵書の一覧を取得する。管理者以外は自身が所有する蔵書のみが対象になる"),
+            ("sort" = Option<crate::model::book::BookSortKey>, Query, description = "並び替えの基準。rating はレビューの評価の平均で、レビューのない蔵書は最も低いものとして扱う。未指定の場合はキーワード検索時は関連度順、それ以外は登録日時順"),
+            ("order" = Option<crate::model::book::SortOrder>, Query, description = "並び順。sort を指定した場合のみ指定できる。未指定の場合は登録日時と評価は降順、それ以外は昇順"),
+            ("cursor" = Option<String>, Query, description = "カーソル方式でページングする場合に、前回のレスポンスの nextCursor または prevCursor を指定する。空文字列の場合は最初のページを返す")
         )
     )
 )]
-#[tracing::instrument(skip(_user, registry), fields(user_id=_user.user.id.to_string()))]
+#[tracing::instrument(skip(user, registry), fields(user_id=user.user.id.to_string()))]
 pub async fn show_book_list(
-    _user: AuthorizedUser,
+    user: AuthorizedUser,
     Query(query): Query<BookListQuery>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<PaginatedBookResponse>> {
+) -> AppResult<Json<BookListResponse>> {
     query.validate()?;
 
+    let cursor = query.cursor()?;
+    let mut options = BookListOptions::from(query);
+    // 除籍した蔵書は、管理者以外には自身が所有するものだけを見せる
+    if options.deleted && !user.is_admin() {
+        options.owner = Some(user.id());
+    }
+
+    match cursor {
+        Some(cursor) => {
+            let limit = options.limit;
+            registry
+                .book_repository()
+                .find_all_with_cursor(options, cursor)
+                .await
+                .map(|list| CursorPaginatedBookResponse::new(limit, list))
+                .map(BookListResponse::Cursor)
+                .map(Json)
+        }
+        None => registry
+            .book_repository()
+            .find_all(options)
+            .await
+            .map(PaginatedBookResponse::from)
+            .map(BookListResponse::Offset)
+            .map(Json),
+    }
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/export",
+        responses(
+            (status = 200, description = "蔵書を指定の形式で出力する。出力中にエラーが発生した場合は、末尾に `#error` で始まるエラーの目印（JSON Lines は `error` を持つ行）を出力して中断する。", content(
+                (String = "text/csv"),
+                (String = "application/jsonl"),
+                (Vec<u8> = "application/marc"),
+                (String = "application/marcxml+xml")
+            )),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+        ),
+        params(
+            ("format" = crate::model::book::BookExportFormat, Query, description = "出力形式"),
+            ("q" = Option<String>, Query, description = "書名・著者名・ISBN・説明を対象とした検索キーワード（空白区切りで AND 検索）"),
+            ("author" = Option<String>, Query, description = "著者名（部分一致）"),
+            ("ownerId" = Option<String>, Query, description = "所有者のユーザーID"),
+            ("locationId" = Option<String>, Query, description = "配架場所の ID。拠点や部屋を指定した場合は、その配下の書架に配架された蔵書に絞り込む"),
+            ("availability" = Option<crate::model::book::BookAvailability>, Query, description = "貸出状況。checked_out は現物がすべて貸出中の蔵書、unavailable は修理中・紛失・貸出停止の蔵書"),
+            ("tags" = Option<String>, Query, description = "カンマ区切りのタグ名。指定したすべてのタグが付いた蔵書に絞り込む")
+        )
+    )
+)]
+#[tracing::instrument(skip(_user, registry), fields(user_id = %_user.user.id.to_string()))]
+pub async fn export_books(
+    _user: AuthorizedUser,
+    Query(query): Query<BookExportQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    let format = query.format;
+    let books = registry.book_repository().stream_all(query.into());
+
+    // ヘッダーは送信済みのため、エラーが発生した場合は末尾にエラーの目印を出力して中断する
+    let mut failed = false;
+    let body = tokio_stream::once(format.header())
+        .chain(books.map(move |book| book.and_then(|book| format.encode(book))))
+        .chain(tokio_stream::once(Ok(format.footer())))
+        .map_while(move |chunk| {
+            if failed {
+                return None;
+            }
+            Some(chunk.unwrap_or_else(|e| {
+                tracing::error!(error.message = %e, "Failed to export books");
+                failed = true;
+                format.error_marker()
+            }))
+        })
+        .map(Ok::<_, AppError>);
+    let headers = [
+        (CONTENT_TYPE, format.content_type().to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!(r#"attachment; filename="{}""#, format.file_name()),
+        ),
+    ];
+
+    Ok((headers, Body::from_stream(body)).into_response())
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/lookup",
+        responses(
+            (status = 200, description = "書誌情報の取得に成功した場合。", body = BookMetadataResponse),
+            (status = 400, description = "ISBN の形式が正しくない場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "ISBN に対応する書誌情報が見つからなかった場合。"),
+            (status = 502, description = "書誌情報の取得に失敗した場合。")
+        ),
+        params(
+            ("isbn" = String, Query, description = "ISBN-10 または ISBN-13（ハイフンの有無は問わない）")
+        )
+    )
+)]
+#[tracing::instrument(skip(_user, registry), fields(user_id = %_user.user.id.to_string()))]
+pub async fn lookup_book_metadata(
+    _user: AuthorizedUser,
+    Query(query): Query<BookLookupQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookMetadataResponse>> {
+    let isbn = query.isbn.parse::<Isbn>()?;
+
     registry
-        .book_repository()
-        .find_all(query.into())
-        .await
-        .map(PaginatedBookResponse::from)
+        .book_metadata_provider()
+        .find_by_isbn(&isbn)
+        .await?
+        .map(BookMetadataResponse::from)
         .map(Json)
+        .ok_or_else(|| {
+            AppError::EntityNotFound(format!("ISBN ({isbn}) の書誌情報が見つかりませんでした。"))
+        })
 }
 
 #[cfg_attr(
@@ -83,6 +341,12 @@ pub async fn show_book_list(
     utoipa::path(
         get,
         path = "/api/v1/books/{book_id}",
+        responses(
+            (status = 200, description = "蔵書の取得に成功した場合。", body = BookResponse,
+                headers(("ETag" = String, description = "蔵書の版数。更新時に If-Match ヘッダーで指定する"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。")
+        ),
         params(
             ("book_id" = String, Path, description = "蔵書ID")
         )
@@ -96,17 +360,15 @@ pub async fn show_book(
     _user: AuthorizedUser,
     Path(book_id): Path<BookId>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<BookResponse>> {
+) -> AppResult<(TypedHeader<ETag>, Json<BookResponse>)> {
     tracing::info!("ここにログを追加した");
-    registry
+    let book = registry
         .book_repository()
         .find_by_id(book_id)
-        .await
-        .and_then(|bc| match bc {
-            Some(bc) => Ok(Json(bc.into())),
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("not found".into()))?;
 
-            None => Err(AppError::EntityNotFound("not found".into())),
-        })
+    Ok((TypedHeader(book_etag(book.version)?), Json(book.into())))
 }
 
 #[cfg_attr(
@@ -114,8 +376,20 @@ pub async fn show_book(
     utoipa::path(
         put,
         path = "/api/v1/books/{book_id}",
+        request_body = UpdateBookRequest,
+        responses(
+            (status = 200, description = "蔵書の更新に成功した場合。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 412, description = "If-Match ヘッダーの ETag が蔵書の現在の版数と一致しない場合。"),
+            (status = 428, description = "If-Match ヘッダーが指定されていない場合。")
+        ),
         params(
-            ("book_id" = String, Path, description = "蔵書ID")
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("If-Match" = String, Header, description = "蔵書の取得時に返された ETag")
         )
     )
 )]
@@ -126,17 +400,105 @@ pub async fn show_book(
 pub async fn update_book(
     user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
     State(registry): State<AppRegistry>,
     Json(req): Json<UpdateBookRequest>,
-) -> AppResult<StatusCode> {
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
     req.validate()?;
-    let update_book = UpdateBookRequestWithIds::new(book_id, user.id(), req);
+    let version = matched_book_version(&registry, &user, book_id, &headers).await?;
+    let update_book =
+        UpdateBookRequestWithIds::new(book_id, user.id(), user.is_admin(), version, req)
+            .try_into()?;
 
-    registry
+    let version = registry.book_repository().update(update_book).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        patch,
+        path = "/api/v1/books/{book_id}",
+        request_body(content = PatchBookRequest, content_type = "application/merge-patch+json"),
+        responses(
+            (status = 200, description = "蔵書の部分更新に成功した場合。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 412, description = "If-Match ヘッダーの ETag が蔵書の現在の版数と一致しない場合。"),
+            (status = 428, description = "If-Match ヘッダーが指定されていない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("If-Match" = String, Header, description = "蔵書の取得時に返された ETag")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn patch_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    headers: HeaderMap,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<PatchBookRequest>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    req.validate()?;
+    let version = matched_book_version(&registry, &user, book_id, &headers).await?;
+    let patch_book =
+        PatchBookRequestWithIds::new(book_id, user.id(), user.is_admin(), version, req)
+            .try_into()?;
+
+    let version = registry.book_repository().patch(patch_book).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+/// 蔵書の版数を表す ETag
+fn book_etag(version: i64) -> AppResult<ETag> {
+    format!("\"{version}\"")
+        .parse::<ETag>()
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+}
+
+/// `If-Match` ヘッダーが蔵書の現在の版数と一致することを確かめ、その版数を返す。
+/// 確認後に更新された場合は、版数を条件にした更新の時点で検出する。
+async fn matched_book_version(
+    registry: &AppRegistry,
+    user: &AuthorizedUser,
+    book_id: BookId,
+    headers: &HeaderMap,
+) -> AppResult<i64> {
+    let book = registry
         .book_repository()
-        .update(update_book.into())
-        .await
-        .map(|_| StatusCode::OK)
+        .find_by_id(book_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+    // 更新できないユーザーには、版数の一致に関わらず 403 を返す
+    if book.owner.id != user.id() && !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    // ヘッダーがない場合も `IfMatch` としては解釈できてしまうため、先に有無を確かめる
+    if !headers.contains_key(IF_MATCH) {
+        return Err(AppError::PreconditionRequired(
+            "蔵書を更新するには、If-Match ヘッダーに取得時の ETag を指定してください。".into(),
+        ));
+    }
+    let if_match = headers.typed_get::<IfMatch>().ok_or_else(|| {
+        AppError::BadRequest("If-Match ヘッダーの形式が正しくありません。".into())
+    })?;
+
+    if !if_match.precondition_passes(&book_etag(book.version)?) {
+        return Err(outdated_version_error(book_id));
+    }
+
+    Ok(book.version)
 }
 
 #[cfg_attr(
@@ -144,8 +506,16 @@ pub async fn update_book(
     utoipa::path(
         delete,
         path = "/api/v1/books/{book_id}",
+        responses(
+            (status = 200, description = "蔵書の除籍に成功した場合。除籍した蔵書は復元できる。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、強制除籍または所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない、またはすでに除籍されている場合。"),
+            (status = 422, description = "貸出中の現物があり、強制除籍が指定されていない場合。")
+        ),
         params(
-            ("book_id" = String, Path, description = "蔵書ID")
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("force" = Option<bool>, Query, description = "true の場合は強制除籍する（管理者のみ）。所有者によらず除籍し、貸出中の貸出は返却済みとして記録する")
         )
     )
 )]
@@ -156,11 +526,18 @@ pub async fn update_book(
 pub async fn delete_book(
     user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    Query(query): Query<DeleteBookQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<StatusCode> {
+    if query.force && !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
     let delete_book = DeleteBook {
         book_id,
         requested_user: user.id(),
+        is_admin: user.is_admin(),
+        force: query.force,
     };
     registry
         .book_repository()
@@ -168,3 +545,720 @@ pub async fn delete_book(
         .await
         .map(|_| StatusCode::OK)
 }
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/restore",
+        responses(
+            (status = 200, description = "除籍した蔵書の復元に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "除籍した蔵書が存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn restore_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let restore_book = RestoreBook {
+        book_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .book_repository()
+        .restore(restore_book)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/revisions",
+        responses(
+            (status = 200, description = "蔵書の変更履歴の取得に成功した場合。", body = BookRevisionsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_book_revisions(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookRevisionsResponse>> {
+    registry
+        .book_repository()
+        .find_revisions(book_id)
+        .await
+        .map(BookRevisionsResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/audit-logs",
+        responses(
+            (status = 200, description = "管理者が所有者以外の蔵書を操作した記録の取得に成功した場合。完全に削除した蔵書の記録も取得できる。", body = BookAuditLogsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn show_book_audit_logs(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookAuditLogsResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .book_repository()
+        .find_audit_logs(book_id)
+        .await
+        .map(BookAuditLogsResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/revisions/{revision_id}/revert",
+        responses(
+            (status = 200, description = "蔵書の書誌情報を変更履歴の時点の内容に戻した場合。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書または変更履歴が存在しない、または蔵書が除籍されている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("revision_id" = String, Path, description = "変更履歴ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn revert_book(
+    user: AuthorizedUser,
+    Path((book_id, revision_id)): Path<(BookId, BookRevisionId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    let revert_book = RevertBook {
+        book_id,
+        revision_id,
+        requested_user: user.id(),
+    };
+    let version = registry.book_repository().revert(revert_book).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/transfer",
+        request_body = TransferBookRequest,
+        responses(
+            (status = 200, description = "蔵書の所有者の譲渡に成功した場合。貸出中の貸出はそのまま引き継がれる。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "譲渡先のユーザーが存在しない、またはすでに蔵書の所有者である場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn transfer_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<TransferBookRequest>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    let transfer_book = TransferBook {
+        book_id,
+        new_owner: req.new_owner_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    let version = registry.book_repository().transfer(transfer_book).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/purge",
+        responses(
+            (status = 200, description = "除籍した蔵書を貸出履歴とともに完全に削除した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "蔵書が除籍されていない、または貸出中の現物がある場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn purge_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .book_repository()
+        .purge(PurgeBook {
+            book_id,
+            requested_user: user.id(),
+        })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/status",
+        request_body = ChangeBookStatusRequest,
+        responses(
+            (status = 200, description = "蔵書の状態の変更に成功した場合。貸出停止（suspended）は除籍とは異なり、蔵書を表示したまま貸出の対象から外す。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "現在の状態から指定の状態に変更できない、または貸出中の現物がある蔵書を貸出可能以外の状態にしようとした場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn change_book_status(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ChangeBookStatusRequest>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    req.validate()?;
+
+    let event = ChangeBookStatusRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+    let version = registry
+        .book_repository()
+        .change_status(event.into())
+        .await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/repair",
+        responses(
+            (status = 200, description = "蔵書を修理中にできた場合。状態を in_repair に変更するのと同じ。修理中の蔵書は貸し出せない。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "現在の状態から修理中に変更できない、または貸出中の現物がある場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn start_book_repair(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    update_book_repair(user, book_id, registry, BookStatus::InRepair).await
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/repair",
+        responses(
+            (status = 200, description = "蔵書の修理中を解除できた場合。状態を available に変更するのと同じ。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "蔵書が修理中でない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn finish_book_repair(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    update_book_repair(user, book_id, registry, BookStatus::Available).await
+}
+
+/// `/repair` は状態の変更 (`PUT /books/{book_id}/status`) の別名として残している
+async fn update_book_repair(
+    user: AuthorizedUser,
+    book_id: BookId,
+    registry: AppRegistry,
+    status: BookStatus,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    let event = ChangeBookStatus {
+        book_id,
+        status,
+        reason: None,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    let version = registry.book_repository().change_status(event).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}/found",
+        responses(
+            (status = 200, description = "紛失した現物を貸し出せる状態に戻した場合。紛失の状態だった蔵書は貸出可能に戻る。",
+                headers(("ETag" = String, description = "更新後の蔵書の版数"))),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書または現物が存在しない場合。"),
+            (status = 422, description = "現物が紛失していない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("copy_id" = String, Path, description = "現物ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn mark_book_found(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, BookCopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<(StatusCode, TypedHeader<ETag>)> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    let mark_found = MarkBookFound {
+        book_id,
+        copy_id,
+        requested_user: user.id(),
+    };
+    let version = registry.book_repository().mark_found(mark_found).await?;
+
+    Ok((StatusCode::OK, TypedHeader(book_etag(version)?)))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/status-history",
+        responses(
+            (status = 200, description = "蔵書の状態の変更履歴の取得に成功した場合。", body = BookStatusHistoryResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない、または除籍されている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_book_status_history(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookStatusHistoryResponse>> {
+    let items = registry
+        .book_repository()
+        .find_status_history(book_id)
+        .await?
+        .into_iter()
+        .map(BookStatusChangeResponse::from)
+        .collect();
+
+    Ok(Json(BookStatusHistoryResponse { items }))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/copies",
+        responses(
+            (status = 200, description = "蔵書の現物一覧の取得に成功した場合。", body = BookCopiesResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない、または除籍されている場合。"),
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_book_copies(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookCopiesResponse>> {
+    registry
+        .book_repository()
+        .find_copies(book_id)
+        .await
+        .map(BookCopiesResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/copies",
+        request_body = CreateBookCopyRequest,
+        responses(
+            (status = 201, description = "現物の追加に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "指定のバーコードがすでに使用されている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn add_book_copy(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateBookCopyRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    registry
+        .book_repository()
+        .add_copy(
+            CreateBookCopyRequestWithIds::new(book_id, user.id(), user.is_admin(), req).into(),
+        )
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}",
+        responses(
+            (status = 200, description = "現物の削除に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書または現物が存在しない場合。"),
+            (status = 422, description = "現物が貸出中の場合、または蔵書の最後の現物である場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("copy_id" = String, Path, description = "現物ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn delete_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, BookCopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_copy = DeleteBookCopy {
+        book_id,
+        copy_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .book_repository()
+        .delete_copy(delete_copy)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/cover",
+        request_body(content = Vec<u8>, content_type = "image/jpeg", description = "JPEG・PNG・WebP の画像（5 MiB まで）"),
+        responses(
+            (status = 200, description = "表紙画像の登録に成功した場合。"),
+            (status = 400, description = "画像の形式がサポートされていない、Content-Type と一致しない、または読み込めない場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 413, description = "画像のファイルサイズが上限を超えている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, headers, body),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn upload_book_cover(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<StatusCode> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let user_id = user.id();
+    let is_admin = user.is_admin();
+
+    // 画像の展開と縮小は CPU を使うため、非同期処理のスレッドを塞がないよう別スレッドで行う
+    let event = tokio::task::spawn_blocking(move || {
+        CoverImageUpload::parse(&content_type, body.to_vec())?
+            .into_event(book_id, user_id, is_admin)
+    })
+    .await
+    .map_err(|e| AppError::ConversionEntityError(e.to_string()))??;
+
+    registry
+        .book_cover_repository()
+        .update(event)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/cover",
+        responses(
+            (status = 200, description = "表紙画像を返す。"),
+            (status = 304, description = "If-None-Match で指定された画像から変更されていない場合。"),
+            (status = 404, description = "表紙画像が登録されていない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(skip(registry, if_none_match))]
+pub async fn show_book_cover(
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    if_none_match: Option<TypedHeader<IfNoneMatch>>,
+) -> AppResult<Response> {
+    book_cover_response(registry, book_id, BookCoverSize::Original, if_none_match).await
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/cover/thumbnail",
+        responses(
+            (status = 200, description = "表紙画像を縮小した JPEG 画像を返す。"),
+            (status = 304, description = "If-None-Match で指定された画像から変更されていない場合。"),
+            (status = 404, description = "表紙画像が登録されていない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(skip(registry, if_none_match))]
+pub async fn show_book_cover_thumbnail(
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    if_none_match: Option<TypedHeader<IfNoneMatch>>,
+) -> AppResult<Response> {
+    book_cover_response(registry, book_id, BookCoverSize::Thumbnail, if_none_match).await
+}
+
+/// 表紙画像を、キャッシュ用のヘッダーを付けて返す。
+/// 表紙画像は `img` 要素から参照されるため、認証なしで取得できるようにしている。
+async fn book_cover_response(
+    registry: AppRegistry,
+    book_id: BookId,
+    size: BookCoverSize,
+    if_none_match: Option<TypedHeader<IfNoneMatch>>,
+) -> AppResult<Response> {
+    let cover = registry
+        .book_cover_repository()
+        .find(book_id, size)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified book cover not found".into()))?;
+
+    let size_name = match size {
+        BookCoverSize::Original => "original",
+        BookCoverSize::Thumbnail => "thumbnail",
+    };
+    let etag = format!("\"{size_name}-{}\"", cover.updated_at.timestamp_millis())
+        .parse::<ETag>()
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+    let cache_headers = (
+        TypedHeader(
+            CacheControl::new()
+                .with_public()
+                .with_max_age(COVER_MAX_AGE),
+        ),
+        TypedHeader(etag.clone()),
+        TypedHeader(LastModified::from(SystemTime::from(cover.updated_at))),
+    );
+
+    if let Some(TypedHeader(if_none_match)) = if_none_match
+        && !if_none_match.precondition_passes(&etag)
+    {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [(CONTENT_TYPE, cover.content_type)],
+        cover.data,
+    )
+        .into_response())
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/cover",
+        responses(
+            (status = 200, description = "表紙画像の削除に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない、または表紙画像が登録されていない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn delete_book_cover(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_cover = DeleteBookCover {
+        book_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .book_cover_repository()
+        .delete(delete_cover)
+        .await
+        .map(|_| StatusCode::OK)
+}
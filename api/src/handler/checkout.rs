@@ -1,9 +1,13 @@
 use crate::extractor::AuthorizedUser;
-use crate::model::checkout::CheckoutsResponse;
+use crate::model::checkout::{
+    BookConditionResponse, BookConditionsResponse, CheckoutListQuery, CheckoutsResponse,
+    ReportLostRequest, ReportLostRequestWithIds, ReturnBookRequest, ReturnBookRequestWithIds,
+};
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use kernel::model::checkout::event::{CreateCheckout, UpdateReturned};
+use garde::Validate;
+use kernel::model::checkout::event::CreateCheckout;
 use kernel::model::id::{BookId, CheckoutId};
 use registry::AppRegistry;
 use shared::error::AppResult;
@@ -40,6 +44,7 @@ pub async fn checkout_book(
     utoipa::path(
         put,
         path = "/api/v1/books/{book_id}/checkouts/{checkout_id}/returned",
+        request_body(content = Option<ReturnBookRequest>, description = "返却を受け付けた場所と返却時の蔵書の状態。省略できる"),
         params(
             ("book_id" = String, description = "蔵書ID"),
             ("checkout_id" = String, description = "チェックアウトID")
@@ -54,8 +59,13 @@ pub async fn return_book(
     user: AuthorizedUser,
     Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
     State(registry): State<AppRegistry>,
+    req: Option<Json<ReturnBookRequest>>,
 ) -> AppResult<StatusCode> {
-    let update_returned = UpdateReturned::new(checkout_id, book_id, user.id(), chrono::Utc::now());
+    let Json(req) = req.unwrap_or_default();
+    req.validate()?;
+
+    let update_returned =
+        ReturnBookRequestWithIds::new(checkout_id, book_id, user.id(), req).try_into()?;
 
     registry
         .checkout_repository()
@@ -64,18 +74,74 @@ pub async fn return_book(
         .map(|_| StatusCode::OK)
 }
 
-#[cfg_attr(debug_assertions, utoipa::path(get, path = "/api/v1/books/checkouts"))]
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/checkouts/{checkout_id}/lost",
+        request_body(content = Option<ReportLostRequest>, description = "借りたユーザーに求める弁償金の額。省略できる"),
+        responses(
+            (status = 200, description = "紛失の届け出に成功した場合。貸出は紛失として終了し、貸し出した現物は見つかるまで貸し出されない。貸し出せる現物が残っていない場合は、蔵書が紛失の状態になる。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "借りたユーザー・蔵書の所有者・管理者以外が届け出た場合、または所有者・管理者以外が弁償金を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "指定の貸出が貸出中でない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("checkout_id" = String, Path, description = "チェックアウトID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn report_lost_book(
+    user: AuthorizedUser,
+    Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
+    State(registry): State<AppRegistry>,
+    req: Option<Json<ReportLostRequest>>,
+) -> AppResult<StatusCode> {
+    let Json(req) = req.unwrap_or_default();
+    req.validate()?;
+
+    let report_lost =
+        ReportLostRequestWithIds::new(checkout_id, book_id, user.id(), user.is_admin(), req);
+
+    registry
+        .checkout_repository()
+        .report_lost(report_lost.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/checkouts",
+        params(
+            ("limit" = Option<i64>, Query, description = "一度に取得する貸出情報数の上限値。未指定の場合はすべてを返す"),
+            ("cursor" = Option<String>, Query, description = "前回のレスポンスの nextCursor または prevCursor")
+        )
+    )
+)]
 #[tracing::instrument(
     skip(_user, registry),
     fields(user_id = %_user.user.id.to_string())
 )]
 pub async fn show_checked_out_list(
     _user: AuthorizedUser,
+    Query(query): Query<CheckoutListQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
+    query.validate()?;
+
     registry
         .checkout_repository()
-        .find_unreturned_all()
+        .find_unreturned_all(query.try_into()?)
         .await
         .map(CheckoutsResponse::from)
         .map(Json)
@@ -87,7 +153,9 @@ pub async fn show_checked_out_list(
         get,
         path = "/api/v1/books/{book_id}/checkout-history",
         params(
-            ("book_id" = String, description = "蔵書ID")
+            ("book_id" = String, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "一度に取得する貸出履歴数の上限値。未指定の場合はすべてを返す"),
+            ("cursor" = Option<String>, Query, description = "前回のレスポンスの nextCursor または prevCursor")
         )
     )
 )]
@@ -98,12 +166,49 @@ pub async fn show_checked_out_list(
 pub async fn checkout_history(
     _user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    Query(query): Query<CheckoutListQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
+    query.validate()?;
+
     registry
         .checkout_repository()
-        .find_history_by_book_id(book_id)
+        .find_history_by_book_id(book_id, query.try_into()?)
         .await
         .map(CheckoutsResponse::from)
         .map(Json)
 }
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/conditions",
+        responses(
+            (status = 200, description = "返却時に記録された蔵書の状態の取得に成功した場合。", body = BookConditionsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_book_condition_history(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookConditionsResponse>> {
+    let items = registry
+        .checkout_repository()
+        .find_condition_history(book_id)
+        .await?
+        .into_iter()
+        .map(BookConditionResponse::from)
+        .collect();
+
+    Ok(Json(BookConditionsResponse { items }))
+}
@@ -0,0 +1,265 @@
+use crate::{
+    extractor::AuthorizedUser,
+    model::location::{
+        CreateLocationRequest, LocationResponse, LocationsResponse, ShelveBookCopyRequestWithIds,
+        UpdateBookLocationRequest, UpdateBookLocationRequestWithIds, UpdateLocationRequest,
+        UpdateLocationRequestWithId,
+    },
+};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use garde::Validate;
+use kernel::model::id::{BookCopyId, BookId, LocationId};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/locations",
+        responses(
+            (status = 200, description = "場所の一覧の取得に成功した場合。", body = LocationsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_location_list(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<LocationsResponse>> {
+    let items = registry
+        .location_repository()
+        .find_all()
+        .await?
+        .into_iter()
+        .map(LocationResponse::from)
+        .collect();
+
+    Ok(Json(LocationsResponse { items }))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/locations",
+        request_body = CreateLocationRequest,
+        responses(
+            (status = 201, description = "場所の登録に成功した場合。", body = LocationResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "親の場所が存在しない場合。"),
+            (status = 422, description = "親の場所の種類が階層に合わない、または同じ親の下に同じ名前の場所がすでにある場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn register_location(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateLocationRequest>,
+) -> AppResult<(StatusCode, Json<LocationResponse>)> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    req.validate()?;
+
+    let location = registry.location_repository().create(req.into()).await?;
+
+    Ok((StatusCode::CREATED, Json(location.into())))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/locations/{location_id}",
+        responses(
+            (status = 200, description = "場所の取得に成功した場合。", body = LocationResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "場所が存在しない場合。")
+        ),
+        params(
+            ("location_id" = String, Path, description = "場所ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_location(
+    _user: AuthorizedUser,
+    Path(location_id): Path<LocationId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<LocationResponse>> {
+    let location = registry
+        .location_repository()
+        .find_by_id(location_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified location not found".into()))?;
+
+    Ok(Json(location.into()))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/locations/{location_id}",
+        request_body = UpdateLocationRequest,
+        responses(
+            (status = 200, description = "場所の名前の変更に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "場所が存在しない場合。"),
+            (status = 422, description = "同じ親の下に同じ名前の場所がすでにある場合。")
+        ),
+        params(
+            ("location_id" = String, Path, description = "場所ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_location(
+    user: AuthorizedUser,
+    Path(location_id): Path<LocationId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateLocationRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    req.validate()?;
+
+    registry
+        .location_repository()
+        .update(UpdateLocationRequestWithId::new(location_id, req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/locations/{location_id}",
+        responses(
+            (status = 200, description = "場所の削除に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "場所が存在しない場合。"),
+            (status = 422, description = "配下に場所がある、または蔵書が配架されている場合。")
+        ),
+        params(
+            ("location_id" = String, Path, description = "場所ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn delete_location(
+    user: AuthorizedUser,
+    Path(location_id): Path<LocationId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .location_repository()
+        .delete(location_id)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/location",
+        request_body = UpdateBookLocationRequest,
+        responses(
+            (status = 200, description = "蔵書の配架場所の変更に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書または場所が存在しない場合。"),
+            (status = 422, description = "書架以外の場所を指定した場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_book_location(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateBookLocationRequest>,
+) -> AppResult<StatusCode> {
+    registry
+        .location_repository()
+        .update_book_location(
+            UpdateBookLocationRequestWithIds::new(book_id, user.id(), user.is_admin(), req).into(),
+        )
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}/shelve",
+        responses(
+            (status = 200, description = "回送中の現物を配架済みにできた場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書または現物が存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("copy_id" = String, Path, description = "現物ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn shelve_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, BookCopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    registry
+        .location_repository()
+        .shelve_copy(
+            ShelveBookCopyRequestWithIds::new(book_id, copy_id, user.id(), user.is_admin()).into(),
+        )
+        .await
+        .map(|_| StatusCode::OK)
+}
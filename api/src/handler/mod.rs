@@ -1,5 +1,10 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
 pub mod health;
+pub mod location;
+pub mod recommendation;
+pub mod review;
+pub mod tag;
 pub mod user;
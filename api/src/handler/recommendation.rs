@@ -0,0 +1,81 @@
+use crate::{
+    extractor::AuthorizedUser,
+    model::recommendation::{RecommendationQuery, RecommendedBooksResponse},
+};
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use garde::Validate;
+use kernel::model::id::BookId;
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/related",
+        responses(
+            (status = 200, description = "この蔵書を借りたユーザーがほかに借りた蔵書の取得に成功した場合。関連の強い順に並ぶ。", body = RecommendedBooksResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "取得する件数の上限値。1 から 50 まで。未指定の場合は 10")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_related_books(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<RecommendationQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<RecommendedBooksResponse>> {
+    query.validate()?;
+
+    registry
+        .recommendation_repository()
+        .find_related(book_id, query.limit)
+        .await
+        .map(RecommendedBooksResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/users/me/recommendations",
+        responses(
+            (status = 200, description = "ログインユーザーへのおすすめの蔵書の取得に成功した場合。借りたことのある蔵書と自分が所有する蔵書は含まない。", body = RecommendedBooksResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        ),
+        params(
+            ("limit" = Option<i64>, Query, description = "取得する件数の上限値。1 から 50 まで。未指定の場合は 10")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn show_my_recommendations(
+    user: AuthorizedUser,
+    Query(query): Query<RecommendationQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<RecommendedBooksResponse>> {
+    query.validate()?;
+
+    registry
+        .recommendation_repository()
+        .find_for_user(user.id(), query.limit)
+        .await
+        .map(RecommendedBooksResponse::from)
+        .map(Json)
+}
@@ -0,0 +1,168 @@
+use crate::{
+    extractor::AuthorizedUser,
+    model::review::{
+        CreateReviewRequest, CreateReviewRequestWithIds, PaginatedReviewResponse, ReviewListQuery,
+        ReviewResponse, UpdateReviewRequest, UpdateReviewRequestWithIds,
+    },
+};
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use garde::Validate;
+use kernel::model::id::{BookId, ReviewId};
+use kernel::model::review::event::DeleteReview;
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/reviews",
+        responses(
+            (status = 200, description = "蔵書のレビューの一覧の取得に成功した場合。新しい順に並ぶ。", body = PaginatedReviewResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない、または除籍されている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "一度に取得するレビューの件数の上限値。未指定の場合は 20、最大 100"),
+            ("offset" = Option<i64>, Query, description = "取得を開始する位置")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_review_list(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<ReviewListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedReviewResponse>> {
+    query.validate()?;
+
+    registry
+        .review_repository()
+        .find_by_book_id(book_id, query.into())
+        .await
+        .map(PaginatedReviewResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/reviews",
+        request_body = CreateReviewRequest,
+        responses(
+            (status = 201, description = "レビューの登録に成功した場合。", body = ReviewResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "蔵書を借りて返却したことがない、またはすでにレビューを書いている場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn register_review(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateReviewRequest>,
+) -> AppResult<(StatusCode, Json<ReviewResponse>)> {
+    req.validate()?;
+
+    let review = registry
+        .review_repository()
+        .create(CreateReviewRequestWithIds::new(book_id, user.id(), req).into())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(review.into())))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/reviews/{review_id}",
+        request_body = UpdateReviewRequest,
+        responses(
+            (status = 200, description = "レビューの更新に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "レビューを書いたユーザー以外が更新しようとした場合。"),
+            (status = 404, description = "レビューが存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("review_id" = String, Path, description = "レビューID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_review(
+    user: AuthorizedUser,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateReviewRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    registry
+        .review_repository()
+        .update(UpdateReviewRequestWithIds::new(book_id, review_id, user.id(), req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/reviews/{review_id}",
+        responses(
+            (status = 200, description = "レビューの削除に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、他のユーザーのレビューを削除しようとした場合。"),
+            (status = 404, description = "レビューが存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("review_id" = String, Path, description = "レビューID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn delete_review(
+    user: AuthorizedUser,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_review = DeleteReview {
+        review_id,
+        book_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .review_repository()
+        .delete(delete_review)
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -0,0 +1,234 @@
+use crate::{
+    extractor::AuthorizedUser,
+    model::tag::{
+        CreateTagRequest, MergeTagRequest, MergeTagRequestWithId, TagResponse, TagsResponse,
+        UpdateTagRequest, UpdateTagRequestWithId,
+    },
+};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use garde::Validate;
+use kernel::model::id::{BookId, TagId};
+use kernel::model::tag::event::{AttachTag, DetachTag};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/tags",
+        responses(
+            (status = 200, description = "タグの一覧の取得に成功した場合。", body = TagsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(user_id = %_user.user.id.to_string())
+)]
+pub async fn show_tag_list(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<TagsResponse>> {
+    let items = registry
+        .tag_repository()
+        .find_all()
+        .await?
+        .into_iter()
+        .map(TagResponse::from)
+        .collect();
+
+    Ok(Json(TagsResponse { items }))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/tags",
+        request_body = CreateTagRequest,
+        responses(
+            (status = 201, description = "タグの作成に成功した場合。", body = TagResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 422, description = "同じ名前のタグがすでにある場合。")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn register_tag(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateTagRequest>,
+) -> AppResult<(StatusCode, Json<TagResponse>)> {
+    req.validate()?;
+
+    let tag = registry.tag_repository().create(req.into()).await?;
+
+    Ok((StatusCode::CREATED, Json(tag.into())))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/tags/{tag_id}",
+        request_body = UpdateTagRequest,
+        responses(
+            (status = 200, description = "タグの名前の変更に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "タグが存在しない場合。"),
+            (status = 422, description = "同じ名前のタグがすでにある場合。")
+        ),
+        params(
+            ("tag_id" = String, Path, description = "タグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn update_tag(
+    user: AuthorizedUser,
+    Path(tag_id): Path<TagId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateTagRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    req.validate()?;
+
+    registry
+        .tag_repository()
+        .update(UpdateTagRequestWithId::new(tag_id, req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/tags/{tag_id}/merge",
+        request_body = MergeTagRequest,
+        responses(
+            (status = 200, description = "タグの統合に成功した場合。統合元のタグは削除される。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "統合元または統合先のタグが存在しない場合。"),
+            (status = 422, description = "統合元と統合先に同じタグを指定した場合。")
+        ),
+        params(
+            ("tag_id" = String, Path, description = "統合元のタグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn merge_tag(
+    user: AuthorizedUser,
+    Path(tag_id): Path<TagId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<MergeTagRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .tag_repository()
+        .merge(MergeTagRequestWithId::new(tag_id, req).into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/tags/{tag_id}",
+        responses(
+            (status = 200, description = "蔵書へのタグの付与に成功した場合。すでに付いている場合も含む。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書またはタグが存在しない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("tag_id" = String, Path, description = "タグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn attach_book_tag(
+    user: AuthorizedUser,
+    Path((book_id, tag_id)): Path<(BookId, TagId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let attach_tag = AttachTag {
+        book_id,
+        tag_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .tag_repository()
+        .attach(attach_tag)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/tags/{tag_id}",
+        responses(
+            (status = 200, description = "蔵書からのタグの削除に成功した場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、所有者でない蔵書を指定した場合。"),
+            (status = 404, description = "蔵書が存在しない、または蔵書にタグが付いていない場合。")
+        ),
+        params(
+            ("book_id" = String, Path, description = "蔵書ID"),
+            ("tag_id" = String, Path, description = "タグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(user_id = %user.user.id.to_string())
+)]
+pub async fn detach_book_tag(
+    user: AuthorizedUser,
+    Path((book_id, tag_id)): Path<(BookId, TagId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let detach_tag = DetachTag {
+        book_id,
+        tag_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    registry
+        .tag_repository()
+        .detach(detach_tag)
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -1,19 +1,21 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
 use garde::Validate;
-use kernel::model::{id::UserId, user::event::DeleteUser};
+use kernel::model::{book::event::TransferBooks, id::UserId, user::event::DeleteUser};
 use registry::AppRegistry;
 use shared::error::{AppError, AppResult};
 
-use crate::model::checkout::CheckoutsResponse;
+use crate::model::book::{TransferBookRequest, TransferBooksResponse};
+use crate::model::checkout::{CheckoutListQuery, CheckoutsResponse};
 use crate::{
     extractor::AuthorizedUser,
     model::user::{
         CreateUserRequest, UpdateUserPasswordRequest, UpdateUserPasswordRequestWithUserId,
-        UpdateUserRoleRequest, UpdateUserRoleRequestWithUserId, UserResponse, UsersResponse,
+        UpdateUserRequest, UpdateUserRequestWithUserId, UpdateUserRoleRequest,
+        UpdateUserRoleRequestWithUserId, UserResponse, UsersResponse,
     },
 };
 
@@ -108,12 +110,79 @@ pub async fn change_role(
     Ok(StatusCode::OK)
 }
 
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/users/{user_id}/books/transfer",
+        request_body = TransferBookRequest,
+        responses(
+            (status = 200, description = "ユーザーが所有するすべての蔵書を、除籍した蔵書も含めて譲渡した場合。", body = TransferBooksResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 403, description = "管理者以外のユーザーが、他のユーザーを指定した場合。"),
+            (status = 422, description = "譲渡先のユーザーが存在しない、または譲渡元と同じ場合。")
+        ),
+        params(
+            ("user_id" = String, Path, description = "譲渡元のユーザーID")
+        )
+    )
+)]
+#[tracing::instrument(skip(user, registry), fields(user_id = %user.user.id.to_string()))]
+pub async fn transfer_user_books(
+    user: AuthorizedUser,
+    Path(user_id): Path<UserId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<TransferBookRequest>,
+) -> AppResult<Json<TransferBooksResponse>> {
+    let transfer_books = TransferBooks {
+        owner: user_id,
+        new_owner: req.new_owner_id,
+        requested_user: user.id(),
+        is_admin: user.is_admin(),
+    };
+    let transferred = registry
+        .book_repository()
+        .transfer_all(transfer_books)
+        .await?;
+
+    Ok(Json(TransferBooksResponse { transferred }))
+}
+
 #[cfg_attr(debug_assertions, utoipa::path(get, path = "/api/v1/users/me"))]
 #[tracing::instrument(skip(user), fields(user_id = %user.user.id.to_string()))]
 pub async fn get_current_user(user: AuthorizedUser) -> Json<UserResponse> {
     Json(UserResponse::from(user.user))
 }
 
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        patch,
+        path = "/api/v1/users/me",
+        request_body(content = UpdateUserRequest, content_type = "application/merge-patch+json"),
+        responses(
+            (status = 200, description = "ユーザー情報の更新に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 422, description = "メールアドレスがすでに使用されている場合。")
+        )
+    )
+)]
+#[tracing::instrument(skip(user, registry, req), fields(user_id = %user.user.id.to_string()))]
+pub async fn update_current_user(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateUserRequest>,
+) -> AppResult<Json<UserResponse>> {
+    req.validate()?;
+
+    let updated_user = registry
+        .user_repository()
+        .update(UpdateUserRequestWithUserId::new(user.id(), req).try_into()?)
+        .await?;
+    Ok(Json(updated_user.into()))
+}
+
 #[cfg_attr(
     debug_assertions,
     utoipa::path(put, path = "/api/v1/users/me/password")
@@ -135,16 +204,26 @@ pub async fn change_password(
 
 #[cfg_attr(
     debug_assertions,
-    utoipa::path(get, path = "/api/v1/users/me/checkouts")
+    utoipa::path(
+        get,
+        path = "/api/v1/users/me/checkouts",
+        params(
+            ("limit" = Option<i64>, Query, description = "一度に取得する貸出情報数の上限値。未指定の場合はすべてを返す"),
+            ("cursor" = Option<String>, Query, description = "前回のレスポンスの nextCursor または prevCursor")
+        )
+    )
 )]
 #[tracing::instrument(skip(user, registry), fields(user_id = %user.user.id.to_string()))]
 pub async fn get_checkouts(
     user: AuthorizedUser,
+    Query(query): Query<CheckoutListQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
+    query.validate()?;
+
     registry
         .checkout_repository()
-        .find_unreturned_by_user_id(user.id())
+        .find_unreturned_by_user_id(user.id(), query.try_into()?)
         .await
         .map(CheckoutsResponse::from)
         .map(Json)
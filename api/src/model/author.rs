@@ -0,0 +1,241 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::model::author::event::{CreateAuthor, MergeAuthor, UpdateAuthor, UpdateBookAuthors};
+use kernel::model::author::{Author, AuthorDetail, AuthorRole, AuthoredBook, BookAuthor};
+use kernel::model::id::{AuthorId, BookId, UserId};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+
+/// 1 冊の蔵書に関連付けられる著者の数の上限
+const MAX_BOOK_AUTHORS: usize = 32;
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAuthorRequest {
+    #[serde(deserialize_with = "trimmed")]
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: String,
+}
+
+impl From<CreateAuthorRequest> for CreateAuthor {
+    fn from(value: CreateAuthorRequest) -> Self {
+        Self { name: value.name }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAuthorRequest {
+    #[serde(deserialize_with = "trimmed")]
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(new)]
+pub struct UpdateAuthorRequestWithId(AuthorId, UpdateAuthorRequest);
+
+impl From<UpdateAuthorRequestWithId> for UpdateAuthor {
+    fn from(value: UpdateAuthorRequestWithId) -> Self {
+        let UpdateAuthorRequestWithId(author_id, UpdateAuthorRequest { name }) = value;
+        Self { author_id, name }
+    }
+}
+
+/// 著者名の前後の空白を読み込み時に取り除き、空白だけの名前を検証で弾けるようにする。
+fn trimmed<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(|name| name.trim().to_string())
+}
+
+/// パスで指定した著者を `targetAuthorId` の著者に統合するリクエスト
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeAuthorRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub target_author_id: AuthorId,
+}
+
+#[derive(new)]
+pub struct MergeAuthorRequestWithId(AuthorId, MergeAuthorRequest);
+
+impl From<MergeAuthorRequestWithId> for MergeAuthor {
+    fn from(value: MergeAuthorRequestWithId) -> Self {
+        let MergeAuthorRequestWithId(source, MergeAuthorRequest { target_author_id }) = value;
+        Self {
+            source,
+            target: target_author_id,
+        }
+    }
+}
+
+/// 蔵書に関わった著者を置き換えるリクエスト。`items` の順序が著者の並び順になり、著者表記もこの順に作り直される。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBookAuthorsRequest {
+    #[garde(length(min = 1, max = MAX_BOOK_AUTHORS))]
+    pub items: Vec<BookAuthorRequest>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookAuthorRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub author_id: AuthorId,
+    #[serde(default = "default_role")]
+    pub role: BookAuthorRole,
+}
+
+fn default_role() -> BookAuthorRole {
+    BookAuthorRole::Author
+}
+
+#[derive(new)]
+pub struct UpdateBookAuthorsRequestWithIds(BookId, UserId, bool, UpdateBookAuthorsRequest);
+
+impl From<UpdateBookAuthorsRequestWithIds> for UpdateBookAuthors {
+    fn from(value: UpdateBookAuthorsRequestWithIds) -> Self {
+        let UpdateBookAuthorsRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            UpdateBookAuthorsRequest { items },
+        ) = value;
+        Self {
+            book_id,
+            requested_user: user_id,
+            is_admin,
+            authors: items
+                .into_iter()
+                .map(|item| (item.author_id, item.role.into()))
+                .collect(),
+        }
+    }
+}
+
+/// 蔵書における著者の役割
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookAuthorRole {
+    Author,
+    Translator,
+    Editor,
+}
+
+impl From<AuthorRole> for BookAuthorRole {
+    fn from(value: AuthorRole) -> Self {
+        match value {
+            AuthorRole::Author => Self::Author,
+            AuthorRole::Translator => Self::Translator,
+            AuthorRole::Editor => Self::Editor,
+        }
+    }
+}
+
+impl From<BookAuthorRole> for AuthorRole {
+    fn from(value: BookAuthorRole) -> Self {
+        match value {
+            BookAuthorRole::Author => Self::Author,
+            BookAuthorRole::Translator => Self::Translator,
+            BookAuthorRole::Editor => Self::Editor,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorsResponse {
+    pub items: Vec<AuthorResponse>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: AuthorId,
+    pub name: String,
+}
+
+impl From<Author> for AuthorResponse {
+    fn from(value: Author) -> Self {
+        let Author { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorDetailResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: AuthorId,
+    pub name: String,
+    /// 著者が関わった蔵書（書名順）
+    pub books: Vec<AuthoredBookResponse>,
+}
+
+impl From<AuthorDetail> for AuthorDetailResponse {
+    fn from(value: AuthorDetail) -> Self {
+        let AuthorDetail { id, name, books } = value;
+        Self {
+            id,
+            name,
+            books: books.into_iter().map(AuthoredBookResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthoredBookResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookId,
+    pub title: String,
+    pub role: BookAuthorRole,
+}
+
+impl From<AuthoredBook> for AuthoredBookResponse {
+    fn from(value: AuthoredBook) -> Self {
+        let AuthoredBook {
+            book_id,
+            title,
+            role,
+        } = value;
+        Self {
+            id: book_id,
+            title,
+            role: role.into(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookAuthorResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: AuthorId,
+    pub name: String,
+    pub role: BookAuthorRole,
+}
+
+impl From<BookAuthor> for BookAuthorResponse {
+    fn from(value: BookAuthor) -> Self {
+        let BookAuthor { id, name, role } = value;
+        Self {
+            id,
+            name,
+            role: role.into(),
+        }
+    }
+}
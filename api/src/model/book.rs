@@ -1,35 +1,148 @@
+use super::author::BookAuthorResponse;
+use super::location::{BookLocationResponse, LocationResponse};
+use super::marc::{MARCXML_FOOTER, MARCXML_HEADER, MarcRecord};
+use super::merge_patch;
+use super::tag::TagResponse;
 use super::user::{BookOwner, CheckoutUser};
 use chrono::{DateTime, Utc};
 use derive_new::new;
 use garde::Validate;
-use kernel::model::book::event::UpdateBook;
-use kernel::model::book::{Book, BookListOptions, Checkout, event::CreateBook};
-use kernel::model::id::{BookId, CheckoutId, UserId};
-use kernel::model::list::PaginatedList;
+use kernel::model::book::event::{
+    ChangeBookStatus, CreateBookCopy, ImportBook, PatchBook, UpdateBook,
+};
+use kernel::model::book::revision::{
+    BookAuditLog, BookRevision, BookRevisionAction, BookRevisionFields,
+};
+use kernel::model::book::{
+    Book, BookCopy, BookImportResult, BookImportStatus, BookListOptions, BookMetadata, BookSort,
+    BookStatus, BookStatusChange, Checkout, DuplicateBookPolicy, event::CreateBook,
+};
+use kernel::model::id::{
+    BookAuditLogId, BookCopyId, BookId, BookRevisionId, CheckoutId, LocationId, UserId,
+};
+use kernel::model::isbn::Isbn;
+use kernel::model::list::{Cursor, CursorPaginatedList, PaginatedList};
 use serde::{Deserialize, Serialize};
+use shared::error::{AppError, AppResult};
 use utoipa::ToSchema;
 
+/// 蔵書の登録リクエスト。書名または著者を省略した場合は、ISBN から取得した書誌情報で補う。
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[derive(Debug, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBookRequest {
-    #[garde(length(min = 1))]
-    pub title: String,
-    #[garde(length(min = 1))]
-    pub author: String,
-    #[garde(length(min = 1))]
+    #[garde(inner(length(chars, min = 1, max = 255)))]
+    pub title: Option<String>,
+    #[garde(inner(length(chars, min = 1, max = 255)))]
+    pub author: Option<String>,
+    /// ISBN-10 または ISBN-13（ハイフンの有無は問わない）
+    #[garde(skip)]
     pub isbn: String,
     #[garde(skip)]
-    pub description: String,
+    pub description: Option<String>,
+    /// 登録する現物の冊数。未指定の場合は 1 冊
+    #[garde(range(min = 1, max = 100))]
+    pub copies: Option<u32>,
+    /// 重複する蔵書（ISBN が同じ、または書名と著者がよく似ている）があっても別の蔵書として登録する
+    #[garde(skip)]
+    #[serde(default)]
+    pub allow_duplicate: bool,
+    /// 重複する蔵書があれば、その蔵書に `copies` 冊の現物を追加する
+    #[garde(skip)]
+    #[serde(default)]
+    pub add_copy: bool,
 }
 
-impl From<CreateBookRequest> for CreateBook {
-    fn from(value: CreateBookRequest) -> Self {
+impl CreateBookRequest {
+    /// 書誌情報で補う必要がある項目が省略されているか
+    pub fn needs_metadata(&self) -> bool {
+        self.title.is_none() || self.author.is_none()
+    }
+
+    /// 省略された項目を書誌情報で補う。指定された項目はそのまま使う。
+    pub fn fill_with(self, metadata: BookMetadata) -> Self {
+        Self {
+            title: self.title.or(Some(metadata.title)),
+            author: self.author.or(Some(metadata.author)),
+            description: self.description.or(Some(metadata.description)),
+            ..self
+        }
+    }
+}
+
+impl TryFrom<CreateBookRequest> for CreateBook {
+    type Error = AppError;
+
+    fn try_from(value: CreateBookRequest) -> Result<Self, Self::Error> {
         let CreateBookRequest {
             title,
             author,
             isbn,
             description,
+            copies,
+            allow_duplicate,
+            add_copy,
+        } = value;
+        let on_duplicate = match (allow_duplicate, add_copy) {
+            (false, false) => DuplicateBookPolicy::Reject,
+            (true, false) => DuplicateBookPolicy::Allow,
+            (false, true) => DuplicateBookPolicy::AddCopy,
+            (true, true) => {
+                return Err(AppError::BadRequest(
+                    "allowDuplicate と addCopy は同時に指定できません。".into(),
+                ));
+            }
+        };
+        let title = title
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| AppError::BadRequest("書名を指定してください。".into()))?;
+        let author = author
+            .filter(|a| !a.is_empty())
+            .ok_or_else(|| AppError::BadRequest("著者を指定してください。".into()))?;
+        Ok(Self {
+            title,
+            author,
+            isbn: isbn.parse()?,
+            description: description.unwrap_or_default(),
+            copies: copies.unwrap_or(1),
+            on_duplicate,
+        })
+    }
+}
+
+/// 蔵書の登録結果。新しく登録した蔵書、または現物を追加した既存の蔵書の ID を返す。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookRegistrationResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookId,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookLookupQuery {
+    pub isbn: String,
+}
+
+/// ISBN から取得した書誌情報。蔵書の登録リクエストの入力補完に使う。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookMetadataResponse {
+    pub title: String,
+    pub author: String,
+    #[cfg_attr(debug_assertions, schema(value_type = String))]
+    pub isbn: Isbn,
+    pub description: String,
+}
+
+impl From<BookMetadata> for BookMetadataResponse {
+    fn from(value: BookMetadata) -> Self {
+        let BookMetadata {
+            isbn,
+            title,
+            author,
+            description,
         } = value;
         Self {
             title,
@@ -44,24 +157,30 @@ impl From<CreateBookRequest> for CreateBook {
 #[derive(Debug, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateBookRequest {
-    #[garde(length(min = 1))]
+    #[garde(length(chars, min = 1, max = 255))]
     pub title: String,
-    #[garde(length(min = 1))]
+    #[garde(length(chars, min = 1, max = 255))]
     pub author: String,
-    #[garde(length(min = 1))]
+    /// ISBN-10 または ISBN-13（ハイフンの有無は問わない）
+    #[garde(skip)]
     pub isbn: String,
     #[garde(skip)]
     pub description: String,
 }
 
+/// 蔵書の更新リクエストに、対象の蔵書 ID・ユーザー ID・管理者かどうか・`If-Match` で確認した版数を合わせたもの
 #[derive(new)]
-pub struct UpdateBookRequestWithIds(BookId, UserId, UpdateBookRequest);
+pub struct UpdateBookRequestWithIds(BookId, UserId, bool, i64, UpdateBookRequest);
+
+impl TryFrom<UpdateBookRequestWithIds> for UpdateBook {
+    type Error = AppError;
 
-impl From<UpdateBookRequestWithIds> for UpdateBook {
-    fn from(value: UpdateBookRequestWithIds) -> Self {
+    fn try_from(value: UpdateBookRequestWithIds) -> Result<Self, Self::Error> {
         let UpdateBookRequestWithIds(
             book_id,
             user_id,
+            is_admin,
+            version,
             UpdateBookRequest {
                 title,
                 author,
@@ -69,18 +188,93 @@ impl From<UpdateBookRequestWithIds> for UpdateBook {
                 description,
             },
         ) = value;
-        Self {
+        Ok(Self {
             book_id,
             title,
             author,
-            isbn,
+            isbn: isbn.parse()?,
             description,
             requested_user: user_id,
+            is_admin,
+            version,
+        })
+    }
+}
+
+/// 蔵書の部分更新リクエスト（JSON Merge Patch）。指定した項目のみを更新する。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchBookRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(inner(inner(length(chars, min = 1, max = 255))))]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    pub title: Option<Option<String>>,
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(inner(inner(length(chars, min = 1, max = 255))))]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    pub author: Option<Option<String>>,
+    /// ISBN-10 または ISBN-13（ハイフンの有無は問わない）
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(skip)]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    pub isbn: Option<Option<String>>,
+    /// null を指定した場合は空にする
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(skip)]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    pub description: Option<Option<String>>,
+}
+
+/// 蔵書の部分更新リクエストに、対象の蔵書 ID・ユーザー ID・管理者かどうか・`If-Match` で確認した版数を合わせたもの
+#[derive(new)]
+pub struct PatchBookRequestWithIds(BookId, UserId, bool, i64, PatchBookRequest);
+
+impl TryFrom<PatchBookRequestWithIds> for PatchBook {
+    type Error = AppError;
+
+    fn try_from(value: PatchBookRequestWithIds) -> Result<Self, Self::Error> {
+        let PatchBookRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            version,
+            PatchBookRequest {
+                title,
+                author,
+                isbn,
+                description,
+            },
+        ) = value;
+        if title.is_none() && author.is_none() && isbn.is_none() && description.is_none() {
+            return Err(AppError::BadRequest(
+                "更新する項目を指定してください。".into(),
+            ));
         }
+        Ok(Self {
+            book_id,
+            title: merge_patch::required(title, "書名")?,
+            author: merge_patch::required(author, "著者")?,
+            isbn: merge_patch::required(isbn, "ISBN")?
+                .map(|isbn| isbn.parse())
+                .transpose()?,
+            description: description.map(Option::unwrap_or_default),
+            requested_user: user_id,
+            is_admin,
+            version,
+        })
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DeleteBookQuery {
+    /// 管理者による強制除籍。貸出中の貸出は返却済みとして記録する
+    #[serde(default)]
+    pub force: bool,
+}
+
 #[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
 pub struct BookListQuery {
     #[garde(range(min = 0))]
     #[serde(default = "default_limit")]
@@ -88,6 +282,46 @@ pub struct BookListQuery {
     #[garde(range(min = 0))]
     #[serde(default)]
     pub offset: i64,
+    #[garde(length(chars, max = 255))]
+    pub q: Option<String>,
+    #[garde(length(chars, min = 1, max = 255))]
+    pub author: Option<String>,
+    #[garde(skip)]
+    pub owner_id: Option<UserId>,
+    /// 配架場所。拠点や部屋を指定した場合は、その配下の書架に配架された蔵書に絞り込む
+    #[garde(skip)]
+    pub location_id: Option<LocationId>,
+    #[garde(skip)]
+    pub availability: Option<BookAvailability>,
+    /// カンマ区切りのタグ名。指定したすべてのタグが付いた蔵書に絞り込む
+    #[garde(length(chars, max = 1024))]
+    pub tags: Option<String>,
+    #[garde(custom(is_before(&self.created_to)))]
+    pub created_from: Option<DateTime<Utc>>,
+    #[garde(skip)]
+    pub created_to: Option<DateTime<Utc>>,
+    /// `true` の場合は除籍した蔵書の一覧を取得する
+    #[garde(skip)]
+    #[serde(default)]
+    pub deleted: bool,
+    #[garde(skip)]
+    pub sort: Option<BookSortKey>,
+    /// `sort` と合わせて指定する。`sort` を省略した場合は指定できない
+    #[garde(custom(requires_sort(&self.sort)))]
+    pub order: Option<SortOrder>,
+    /// 指定した場合はカーソル方式でページングする。空文字列の場合は最初のページを返す。
+    #[garde(skip)]
+    pub cursor: Option<String>,
+}
+
+impl BookListQuery {
+    /// カーソル方式の場合は、デコードしたカーソル（最初のページでは `None`）を返す。
+    pub fn cursor(&self) -> AppResult<Option<Option<Cursor>>> {
+        self.cursor
+            .as_deref()
+            .map(|c| (!c.is_empty()).then(|| c.parse()).transpose())
+            .transpose()
+    }
 }
 
 const DEFAULT_LIMIT: i64 = 20;
@@ -95,10 +329,136 @@ const fn default_limit() -> i64 {
     DEFAULT_LIMIT
 }
 
+fn is_before(
+    to: &Option<DateTime<Utc>>,
+) -> impl FnOnce(&Option<DateTime<Utc>>, &()) -> garde::Result + '_ {
+    move |from, _| match (from, to) {
+        (Some(from), Some(to)) if from > to => Err(garde::Error::new(
+            "createdFrom must not be later than createdTo",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn requires_sort(
+    sort: &Option<BookSortKey>,
+) -> impl FnOnce(&Option<SortOrder>, &()) -> garde::Result + '_ {
+    move |order, _| match (order, sort) {
+        (Some(_), None) => Err(garde::Error::new("order requires sort")),
+        _ => Ok(()),
+    }
+}
+
+/// カンマ区切りのタグ名を分割する。前後の空白は取り除き、空の要素は無視する。
+fn split_tags(tags: Option<String>) -> Vec<String> {
+    tags.iter()
+        .flat_map(|tags| tags.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 impl From<BookListQuery> for BookListOptions {
     fn from(value: BookListQuery) -> Self {
-        let BookListQuery { limit, offset } = value;
-        Self { limit, offset }
+        let BookListQuery {
+            limit,
+            offset,
+            q,
+            author,
+            owner_id,
+            location_id,
+            availability,
+            tags,
+            created_from,
+            created_to,
+            deleted,
+            sort,
+            order,
+            cursor: _,
+        } = value;
+        let sort = sort.map(|key| {
+            let key = kernel::model::book::BookSortKey::from(key);
+            BookSort {
+                key,
+                order: order.map(Into::into).unwrap_or(key.default_order()),
+            }
+        });
+        Self {
+            limit,
+            offset,
+            query: q.map(|q| q.trim().to_string()).filter(|q| !q.is_empty()),
+            author,
+            owner: owner_id,
+            location: location_id,
+            availability: availability.map(Into::into),
+            tags: split_tags(tags),
+            created_from,
+            created_to,
+            deleted,
+            sort,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookAvailability {
+    /// 貸出可能な現物がある
+    Available,
+    /// 貸出可能な状態の蔵書で、現物がすべて貸出中
+    CheckedOut,
+    /// 修理中・紛失・貸出停止のため貸し出せない
+    Unavailable,
+}
+
+impl From<BookAvailability> for kernel::model::book::BookAvailability {
+    fn from(value: BookAvailability) -> Self {
+        match value {
+            BookAvailability::Available => Self::Available,
+            BookAvailability::CheckedOut => Self::CheckedOut,
+            BookAvailability::Unavailable => Self::Unavailable,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookSortKey {
+    Title,
+    Author,
+    CreatedAt,
+    /// レビューの評価の平均
+    Rating,
+}
+
+impl From<BookSortKey> for kernel::model::book::BookSortKey {
+    fn from(value: BookSortKey) -> Self {
+        match value {
+            BookSortKey::Title => Self::Title,
+            BookSortKey::Author => Self::Author,
+            BookSortKey::CreatedAt => Self::CreatedAt,
+            BookSortKey::Rating => Self::Rating,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl From<SortOrder> for kernel::model::list::SortOrder {
+    fn from(value: SortOrder) -> Self {
+        match value {
+            SortOrder::Asc => Self::Asc,
+            SortOrder::Desc => Self::Desc,
+        }
     }
 }
 
@@ -109,11 +469,42 @@ pub struct BookResponse {
     #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
     pub id: BookId,
     pub title: String,
+    /// 著者表記
     pub author: String,
-    pub isbn: String,
+    /// 蔵書に関わった著者（並び順どおり）
+    pub authors: Vec<BookAuthorResponse>,
+    #[cfg_attr(debug_assertions, schema(value_type = String))]
+    pub isbn: Isbn,
     pub description: String,
     pub owner: BookOwner,
+    pub total_copies: i64,
+    pub available_copies: i64,
+    /// 貸出中の現物の貸出情報。貸出日時の古い順に並ぶ
+    pub checkouts: Vec<BookCheckoutResponse>,
+    /// 最も早く貸し出された現物の貸出情報。貸出中でない場合は null。
+    /// 複本に対応する前のクライアントとの互換性のために残しているため、`checkouts` を使うこと
+    #[cfg_attr(debug_assertions, schema(deprecated))]
     pub checkout: Option<BookCheckoutResponse>,
+    pub tags: Vec<TagResponse>,
+    /// 表紙画像の URL。画像が更新されると URL も変わる
+    pub cover_url: Option<String>,
+    /// 表紙画像の縮小版の URL
+    pub cover_thumbnail_url: Option<String>,
+    /// 配架場所。決まっていない場合は null
+    pub location: Option<BookLocationResponse>,
+    /// 蔵書の状態。貸し出せるのは available の蔵書のみ
+    pub status: BookStatusKind,
+    /// 状態を最後に変更した日時。登録後に一度も変更していない場合は null
+    pub status_changed_at: Option<DateTime<Utc>>,
+    /// 修理中にした日時。修理中でない場合は null。修理中の蔵書は貸し出せない
+    pub in_repair_since: Option<DateTime<Utc>>,
+    /// 除籍した日時。除籍されていない場合は null
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 蔵書の版数。蔵書の取得時に返す ETag と同じ値を表す
+    pub version: i64,
+    /// レビューの評価の平均。レビューがない場合は null
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
 }
 
 impl From<Book> for BookResponse {
@@ -122,19 +513,59 @@ impl From<Book> for BookResponse {
             id,
             title,
             author,
+            authors,
             isbn,
             description,
             owner,
-            checkout,
+            total_copies,
+            available_copies,
+            checkouts,
+            tags,
+            cover,
+            location,
+            status,
+            status_changed_at,
+            deleted_at,
+            version,
+            average_rating,
+            review_count,
         } = value;
+        let checkouts = checkouts
+            .into_iter()
+            .map(BookCheckoutResponse::from)
+            .collect::<Vec<_>>();
+        // キャッシュされた古い画像が使われないよう、更新日時を URL に含める
+        let cover_url = |path: &str| {
+            cover.as_ref().map(|c| {
+                format!(
+                    "/api/v1/books/{id}/{path}?v={}",
+                    c.updated_at.timestamp_millis()
+                )
+            })
+        };
         Self {
             id,
             title,
             author,
+            authors: authors.into_iter().map(BookAuthorResponse::from).collect(),
             isbn,
             description,
             owner: owner.into(),
-            checkout: checkout.map(BookCheckoutResponse::from),
+            total_copies,
+            available_copies,
+            checkout: checkouts.first().cloned(),
+            checkouts,
+            tags: tags.into_iter().map(TagResponse::from).collect(),
+            cover_url: cover_url("cover"),
+            cover_thumbnail_url: cover_url("cover/thumbnail"),
+            location: location.map(BookLocationResponse::from),
+            status: status.into(),
+            status_changed_at,
+            in_repair_since: status_changed_at.filter(|_| status == BookStatus::InRepair),
+            deleted_at,
+            version,
+            average_rating,
+            review_count,
         }
     }
 }
@@ -149,6 +580,41 @@ pub struct PaginatedBookResponse {
     pub items: Vec<BookResponse>,
 }
 
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginatedBookResponse {
+    pub limit: i64,
+    pub items: Vec<BookResponse>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+impl CursorPaginatedBookResponse {
+    pub fn new(limit: i64, list: CursorPaginatedList<Book>) -> Self {
+        let CursorPaginatedList {
+            items,
+            next_cursor,
+            prev_cursor,
+        } = list;
+        Self {
+            limit,
+            items: items.into_iter().map(BookResponse::from).collect(),
+            next_cursor: next_cursor.map(|c| c.to_string()),
+            prev_cursor: prev_cursor.map(|c| c.to_string()),
+        }
+    }
+}
+
+/// 蔵書一覧のレスポンス。ページングの方式によって形式が異なる。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BookListResponse {
+    Offset(PaginatedBookResponse),
+    Cursor(CursorPaginatedBookResponse),
+}
+
 impl From<PaginatedList<Book>> for PaginatedBookResponse {
     fn from(value: PaginatedList<Book>) -> Self {
         let PaginatedList {
@@ -167,11 +633,14 @@ impl From<PaginatedList<Book>> for PaginatedBookResponse {
 }
 
 #[cfg_attr(debug_assertions, derive(ToSchema))]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BookCheckoutResponse {
     #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
     pub id: CheckoutId,
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub copy_id: BookCopyId,
+    pub barcode: String,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
 }
@@ -180,13 +649,804 @@ impl From<Checkout> for BookCheckoutResponse {
     fn from(value: Checkout) -> Self {
         let Checkout {
             checkout_id,
+            copy_id,
+            barcode,
             checked_out_by,
             checked_out_at,
         } = value;
         Self {
             id: checkout_id,
+            copy_id,
+            barcode,
             checked_out_by: checked_out_by.into(),
             checked_out_at,
         }
     }
 }
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBookCopyRequest {
+    /// 未指定の場合は自動で採番する
+    #[garde(inner(length(min = 1, max = 255)))]
+    pub barcode: Option<String>,
+}
+
+#[derive(new)]
+pub struct CreateBookCopyRequestWithIds(BookId, UserId, bool, CreateBookCopyRequest);
+
+impl From<CreateBookCopyRequestWithIds> for CreateBookCopy {
+    fn from(value: CreateBookCopyRequestWithIds) -> Self {
+        let CreateBookCopyRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            CreateBookCopyRequest { barcode },
+        ) = value;
+        Self {
+            book_id,
+            barcode,
+            requested_user: user_id,
+            is_admin,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookCopiesResponse {
+    pub items: Vec<BookCopyResponse>,
+}
+
+impl From<Vec<BookCopy>> for BookCopiesResponse {
+    fn from(value: Vec<BookCopy>) -> Self {
+        Self {
+            items: value.into_iter().map(BookCopyResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookCopyResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookCopyId,
+    pub barcode: String,
+    pub checkout: Option<BookCheckoutResponse>,
+    /// 配架場所と異なる拠点で返却され、配架場所へ戻す途中の場合の現在の場所
+    pub in_transit_at: Option<LocationResponse>,
+    /// 紛失した日時。見つかるまで貸し出されない
+    pub lost_at: Option<DateTime<Utc>>,
+}
+
+impl From<BookCopy> for BookCopyResponse {
+    fn from(value: BookCopy) -> Self {
+        let BookCopy {
+            id,
+            book_id: _,
+            barcode,
+            checkout,
+            in_transit_at,
+            lost_at,
+        } = value;
+        Self {
+            id,
+            barcode,
+            checkout: checkout.map(BookCheckoutResponse::from),
+            in_transit_at: in_transit_at.map(LocationResponse::from),
+            lost_at,
+        }
+    }
+}
+
+/// 蔵書の所有者の譲渡リクエスト
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBookRequest {
+    /// 譲渡先のユーザー ID
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub new_owner_id: UserId,
+}
+
+/// 蔵書の状態
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookStatusKind {
+    /// 貸出可能
+    Available,
+    /// 修理中
+    InRepair,
+    /// 紛失
+    Lost,
+    /// 貸出停止。除籍とは異なり、蔵書は一覧や詳細に表示したまま貸出の対象から外す
+    Suspended,
+}
+
+impl From<BookStatus> for BookStatusKind {
+    fn from(value: BookStatus) -> Self {
+        match value {
+            BookStatus::Available => Self::Available,
+            BookStatus::InRepair => Self::InRepair,
+            BookStatus::Lost => Self::Lost,
+            BookStatus::Suspended => Self::Suspended,
+        }
+    }
+}
+
+impl From<BookStatusKind> for BookStatus {
+    fn from(value: BookStatusKind) -> Self {
+        match value {
+            BookStatusKind::Available => Self::Available,
+            BookStatusKind::InRepair => Self::InRepair,
+            BookStatusKind::Lost => Self::Lost,
+            BookStatusKind::Suspended => Self::Suspended,
+        }
+    }
+}
+
+/// 蔵書の状態の変更リクエスト
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeBookStatusRequest {
+    #[garde(skip)]
+    pub status: BookStatusKind,
+    /// 変更の理由
+    #[garde(inner(length(chars, max = 1000)))]
+    pub reason: Option<String>,
+}
+
+#[derive(new)]
+pub struct ChangeBookStatusRequestWithIds(BookId, UserId, bool, ChangeBookStatusRequest);
+
+impl From<ChangeBookStatusRequestWithIds> for ChangeBookStatus {
+    fn from(value: ChangeBookStatusRequestWithIds) -> Self {
+        let ChangeBookStatusRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            ChangeBookStatusRequest { status, reason },
+        ) = value;
+        Self {
+            book_id,
+            status: status.into(),
+            reason: reason
+                .map(|r| r.trim().to_string())
+                .filter(|r| !r.is_empty()),
+            requested_user: user_id,
+            is_admin,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookStatusHistoryResponse {
+    /// 状態の変更履歴。新しい順に並ぶ
+    pub items: Vec<BookStatusChangeResponse>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookStatusChangeResponse {
+    pub from: BookStatusKind,
+    pub to: BookStatusKind,
+    pub reason: Option<String>,
+    pub changed_by: BookRevisionUserResponse,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<BookStatusChange> for BookStatusChangeResponse {
+    fn from(value: BookStatusChange) -> Self {
+        let BookStatusChange {
+            from,
+            to,
+            reason,
+            changed_by,
+            changed_at,
+        } = value;
+        Self {
+            from: from.into(),
+            to: to.into(),
+            reason,
+            changed_by: BookRevisionUserResponse {
+                id: changed_by.id,
+                name: changed_by.name,
+            },
+            changed_at,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBooksResponse {
+    /// 譲渡した蔵書の冊数。除籍した蔵書を含む
+    pub transferred: u64,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionsResponse {
+    /// 変更履歴。新しい順に並ぶ
+    pub items: Vec<BookRevisionResponse>,
+}
+
+impl From<Vec<BookRevision>> for BookRevisionsResponse {
+    fn from(value: Vec<BookRevision>) -> Self {
+        Self {
+            items: value.into_iter().map(BookRevisionResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookRevisionId,
+    /// 操作後の蔵書の版数
+    pub version: i64,
+    pub action: BookRevisionActionResponse,
+    pub changed_by: BookRevisionUserResponse,
+    /// 変更前の書誌情報。登録・除籍・復元・譲渡・状態の変更では null
+    pub old: Option<BookRevisionFieldsResponse>,
+    /// 操作後の書誌情報
+    pub new: BookRevisionFieldsResponse,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<BookRevision> for BookRevisionResponse {
+    fn from(value: BookRevision) -> Self {
+        let BookRevision {
+            id,
+            book_id: _,
+            version,
+            action,
+            changed_by,
+            old,
+            new,
+            created_at,
+        } = value;
+        Self {
+            id,
+            version,
+            action: action.into(),
+            changed_by: BookRevisionUserResponse {
+                id: changed_by.id,
+                name: changed_by.name,
+            },
+            old: old.map(BookRevisionFieldsResponse::from),
+            new: new.into(),
+            created_at,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookRevisionActionResponse {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Revert,
+    Transfer,
+    ChangeStatus,
+    Purge,
+}
+
+impl From<BookRevisionAction> for BookRevisionActionResponse {
+    fn from(value: BookRevisionAction) -> Self {
+        match value {
+            BookRevisionAction::Create => Self::Create,
+            BookRevisionAction::Update => Self::Update,
+            BookRevisionAction::Delete => Self::Delete,
+            BookRevisionAction::Restore => Self::Restore,
+            BookRevisionAction::Revert => Self::Revert,
+            BookRevisionAction::Transfer => Self::Transfer,
+            BookRevisionAction::ChangeStatus => Self::ChangeStatus,
+            BookRevisionAction::Purge => Self::Purge,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionUserResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: UserId,
+    /// ユーザーが削除されている場合は null
+    pub name: Option<String>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookAuditLogsResponse {
+    /// 管理者が所有者以外の蔵書を操作した記録。新しい順に並ぶ
+    pub items: Vec<BookAuditLogResponse>,
+}
+
+impl From<Vec<BookAuditLog>> for BookAuditLogsResponse {
+    fn from(value: Vec<BookAuditLog>) -> Self {
+        Self {
+            items: value.into_iter().map(BookAuditLogResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookAuditLogResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookAuditLogId,
+    /// 操作した時点の蔵書の所有者
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub owner_id: UserId,
+    pub action: BookRevisionActionResponse,
+    pub operated_by: BookRevisionUserResponse,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<BookAuditLog> for BookAuditLogResponse {
+    fn from(value: BookAuditLog) -> Self {
+        let BookAuditLog {
+            id,
+            book_id: _,
+            owner_id,
+            operated_by,
+            action,
+            created_at,
+        } = value;
+        Self {
+            id,
+            owner_id,
+            action: action.into(),
+            operated_by: BookRevisionUserResponse {
+                id: operated_by.id,
+                name: operated_by.name,
+            },
+            created_at,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionFieldsResponse {
+    pub title: String,
+    pub author: String,
+    #[cfg_attr(debug_assertions, schema(value_type = String))]
+    pub isbn: Isbn,
+    pub description: String,
+}
+
+impl From<BookRevisionFields> for BookRevisionFieldsResponse {
+    fn from(value: BookRevisionFields) -> Self {
+        let BookRevisionFields {
+            title,
+            author,
+            isbn,
+            description,
+        } = value;
+        Self {
+            title,
+            author,
+            isbn,
+            description,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookImportQuery {
+    /// `true` の場合は、1 行でも不備があれば何も登録しない
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// 一括登録で受け付けるデータ形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookImportFormat {
+    /// 1 行目をヘッダー (title, author, isbn, description, copies) とする CSV
+    Csv,
+    /// 1 行に 1 件の `CreateBookRequest` を記述した JSON Lines
+    JsonLines,
+}
+
+impl BookImportFormat {
+    pub fn from_content_type(content_type: &str) -> AppResult<Self> {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match mime.as_str() {
+            "text/csv" => Ok(Self::Csv),
+            "application/jsonl" | "application/x-ndjson" | "application/x-jsonlines" => {
+                Ok(Self::JsonLines)
+            }
+            _ => Err(AppError::BadRequest(format!(
+                "サポートされていない Content-Type です: {content_type}"
+            ))),
+        }
+    }
+}
+
+/// 一括登録のデータを解析した結果。登録できる行と、解析や検証に失敗した行に分ける。
+#[derive(Debug, Default)]
+pub struct ParsedBookImport {
+    pub books: Vec<ImportBook>,
+    pub failures: Vec<BookImportResult>,
+}
+
+impl ParsedBookImport {
+    pub fn parse(format: BookImportFormat, body: &[u8]) -> AppResult<Self> {
+        let parsed = match format {
+            BookImportFormat::Csv => Self::parse_csv(body)?,
+            BookImportFormat::JsonLines => Self::parse_json_lines(body)?,
+        };
+        if parsed.books.is_empty() && parsed.failures.is_empty() {
+            return Err(AppError::BadRequest("登録する蔵書がありません。".into()));
+        }
+        Ok(parsed)
+    }
+
+    fn parse_csv(body: &[u8]) -> AppResult<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body);
+        let headers = reader
+            .headers()
+            .map_err(|e| {
+                AppError::BadRequest(format!("CSV のヘッダーを読み込めませんでした: {e}"))
+            })?
+            .clone();
+
+        let mut parsed = Self::default();
+        for record in reader.records() {
+            match record {
+                Ok(record) => {
+                    let line = record.position().map_or(0, |p| p.line());
+                    let req = record
+                        .deserialize::<CreateBookRequest>(Some(&headers))
+                        .map_err(|e| e.to_string());
+                    parsed.push(line, req);
+                }
+                Err(e) => {
+                    let line = e.position().map_or(0, |p| p.line());
+                    parsed.push(line, Err(e.to_string()));
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn parse_json_lines(body: &[u8]) -> AppResult<Self> {
+        let body = std::str::from_utf8(body)
+            .map_err(|e| AppError::BadRequest(format!("UTF-8 として読み込めませんでした: {e}")))?;
+
+        let mut parsed = Self::default();
+        for (i, line) in body.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let req = serde_json::from_str::<CreateBookRequest>(line).map_err(|e| e.to_string());
+            parsed.push(i as u64 + 1, req);
+        }
+        Ok(parsed)
+    }
+
+    /// 蔵書の登録リクエストと同じ検証を行い、登録できる行か失敗した行として追加する。
+    fn push(&mut self, line: u64, req: Result<CreateBookRequest, String>) {
+        let book = req.and_then(|req| {
+            req.validate().map_err(|e| e.to_string())?;
+            CreateBook::try_from(req).map_err(|e| e.to_string())
+        });
+        match book {
+            Ok(book) => self.books.push(ImportBook { line, book }),
+            Err(message) => self.failures.push(BookImportResult {
+                line,
+                status: BookImportStatus::Failed(message),
+            }),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportResponse {
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// 各行の結果。行番号の順に並ぶ
+    pub rows: Vec<BookImportRowResponse>,
+}
+
+impl From<Vec<BookImportResult>> for BookImportResponse {
+    fn from(mut value: Vec<BookImportResult>) -> Self {
+        value.sort_by_key(|r| r.line);
+        let rows = value
+            .into_iter()
+            .map(BookImportRowResponse::from)
+            .collect::<Vec<_>>();
+        let count =
+            |status: BookImportRowStatus| rows.iter().filter(|r| r.status == status).count();
+        Self {
+            created: count(BookImportRowStatus::Created),
+            skipped: count(BookImportRowStatus::Skipped),
+            failed: count(BookImportRowStatus::Failed),
+            rows,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportRowResponse {
+    pub line: u64,
+    pub status: BookImportRowStatus,
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub book_id: Option<BookId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl From<BookImportResult> for BookImportRowResponse {
+    fn from(value: BookImportResult) -> Self {
+        let BookImportResult { line, status } = value;
+        let (status, book_id, message) = match status {
+            BookImportStatus::Created(book_id) => {
+                (BookImportRowStatus::Created, Some(book_id), None)
+            }
+            BookImportStatus::Skipped(message) => {
+                (BookImportRowStatus::Skipped, None, Some(message))
+            }
+            BookImportStatus::Failed(message) => (BookImportRowStatus::Failed, None, Some(message)),
+        };
+        Self {
+            line,
+            status,
+            book_id,
+            message,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookImportRowStatus {
+    Created,
+    Skipped,
+    Failed,
+}
+
+/// 蔵書のエクスポート条件。並び順は蔵書一覧と同じ。
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookExportQuery {
+    pub format: BookExportFormat,
+    pub q: Option<String>,
+    pub author: Option<String>,
+    pub owner_id: Option<UserId>,
+    pub location_id: Option<LocationId>,
+    pub availability: Option<BookAvailability>,
+    pub tags: Option<String>,
+}
+
+impl From<BookExportQuery> for BookListOptions {
+    fn from(value: BookExportQuery) -> Self {
+        let BookExportQuery {
+            format: _,
+            q,
+            author,
+            owner_id,
+            location_id,
+            availability,
+            tags,
+        } = value;
+        Self {
+            query: q.map(|q| q.trim().to_string()).filter(|q| !q.is_empty()),
+            author,
+            owner: owner_id,
+            location: location_id,
+            availability: availability.map(Into::into),
+            tags: split_tags(tags),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+/// 蔵書の出力形式。
+/// 出力は蔵書を取得しながら送るため、途中でエラーが発生してもステータスコードは 200 のままになる。
+/// その場合は末尾に `error_marker` の内容を出力して中断する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookExportFormat {
+    Csv,
+    Jsonl,
+    /// MARC21 (ISO 2709)
+    Marc,
+    Marcxml,
+}
+
+const EXPORT_ERROR_MESSAGE: &str = "出力中にエラーが発生したため、出力を中断しました。";
+
+const EXPORT_CSV_HEADERS: [&str; 11] = [
+    "id",
+    "title",
+    "author",
+    "isbn",
+    "description",
+    "owner_id",
+    "owner_name",
+    "total_copies",
+    "available_copies",
+    "checkouts",
+    "tags",
+];
+
+impl BookExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Jsonl => "application/jsonl",
+            Self::Marc => "application/marc",
+            Self::Marcxml => "application/marcxml+xml",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Csv => "books.csv",
+            Self::Jsonl => "books.jsonl",
+            Self::Marc => "books.mrc",
+            Self::Marcxml => "books.xml",
+        }
+    }
+
+    /// 先頭に出力する内容
+    pub fn header(&self) -> AppResult<Vec<u8>> {
+        match self {
+            Self::Csv => write_csv_record(EXPORT_CSV_HEADERS),
+            Self::Marcxml => Ok(MARCXML_HEADER.into()),
+            Self::Jsonl | Self::Marc => Ok(Vec::new()),
+        }
+    }
+
+    /// 末尾に出力する内容
+    pub fn footer(&self) -> Vec<u8> {
+        match self {
+            Self::Marcxml => MARCXML_FOOTER.into(),
+            Self::Csv | Self::Jsonl | Self::Marc => Vec::new(),
+        }
+    }
+
+    /// 出力中にエラーが発生した場合に、末尾に出力する内容。
+    /// CSV は 1 列目が `#error` の行、JSON Lines は `error` のみを持つオブジェクト、
+    /// MARCXML は XML コメント（`</collection>` は出力しない）、
+    /// MARC はレコードとして読み込めない `#error` で始まるデータとする。
+    pub fn error_marker(&self) -> Vec<u8> {
+        match self {
+            Self::Csv => format!("#error,{EXPORT_ERROR_MESSAGE}\n").into_bytes(),
+            Self::Jsonl => format!("{{\"error\":\"{EXPORT_ERROR_MESSAGE}\"}}\n").into_bytes(),
+            Self::Marc => format!("#error {EXPORT_ERROR_MESSAGE}\x1d").into_bytes(),
+            Self::Marcxml => format!("<!-- #error {EXPORT_ERROR_MESSAGE} -->\n").into_bytes(),
+        }
+    }
+
+    /// 蔵書 1 件分を出力形式に変換する。
+    pub fn encode(&self, book: Book) -> AppResult<Vec<u8>> {
+        match self {
+            Self::Csv => {
+                // 貸出中の現物は「バーコード/利用者ID/貸出日時」を ; で区切って並べる
+                let checkouts = book
+                    .checkouts
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "{}/{}/{}",
+                            c.barcode,
+                            c.checked_out_by.id,
+                            c.checked_out_at.to_rfc3339()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(";");
+                let tags = book
+                    .tags
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(";");
+                write_csv_record([
+                    book.id.to_string(),
+                    book.title,
+                    book.author,
+                    book.isbn.to_string(),
+                    book.description,
+                    book.owner.id.to_string(),
+                    book.owner.name,
+                    book.total_copies.to_string(),
+                    book.available_copies.to_string(),
+                    checkouts,
+                    tags,
+                ])
+            }
+            Self::Jsonl => {
+                let mut line = serde_json::to_vec(&BookResponse::from(book))
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+                line.push(b'\n');
+                Ok(line)
+            }
+            Self::Marc => Ok(marc_record(book).to_iso2709()),
+            Self::Marcxml => Ok(marc_record(book).to_xml().into_bytes()),
+        }
+    }
+}
+
+fn write_csv_record<I, T>(record: I) -> AppResult<Vec<u8>>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(record)
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+}
+
+fn marc_record(book: Book) -> MarcRecord {
+    let holdings = format!(
+        "所蔵 {} 冊、貸出可能 {} 冊",
+        book.total_copies, book.available_copies
+    );
+    let record = MarcRecord::new()
+        .control_field("001", book.id.to_string())
+        .data_field("020", [' ', ' '], [('a', book.isbn.to_string())])
+        .data_field("100", ['1', ' '], [('a', book.author)])
+        .data_field("245", ['1', '0'], [('a', book.title)])
+        .data_field("520", [' ', ' '], [('a', book.description)])
+        .data_field("561", [' ', ' '], [('a', book.owner.name)])
+        .data_field("590", [' ', ' '], [('a', holdings)]);
+    // タグは統制されていない索引語として出力する
+    let record = book.tags.into_iter().fold(record, |record, tag| {
+        record.data_field("653", [' ', ' '], [('a', tag.name)])
+    });
+    // 貸出中の現物ごとに、バーコードと状態を所蔵情報として出力する
+    book.checkouts.into_iter().fold(record, |record, c| {
+        record.data_field(
+            "876",
+            [' ', ' '],
+            [
+                ('p', c.barcode),
+                ('j', "checked out".into()),
+                ('z', format!("貸出日時 {}", c.checked_out_at.to_rfc3339())),
+            ],
+        )
+    })
+}
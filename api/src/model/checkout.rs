@@ -1,18 +1,233 @@
 use chrono::{DateTime, Utc};
-use kernel::model::checkout::{Checkout, CheckoutBook};
-use kernel::model::id::{BookId, CheckoutId, UserId};
-use serde::Serialize;
+use derive_new::new;
+use garde::Validate;
+use kernel::model::checkout::event::{ReportCondition, ReportLost, UpdateReturned};
+use kernel::model::checkout::{
+    BookCondition, BookConditionReport, Checkout, CheckoutBook, CheckoutListOptions,
+    CheckoutOutcome,
+};
+use kernel::model::id::{BookCopyId, BookId, CheckoutId, LocationId, UserId};
+use kernel::model::isbn::Isbn;
+use kernel::model::list::{Cursor, CursorPaginatedList};
+use serde::{Deserialize, Serialize};
+use shared::error::AppError;
+use utoipa::ToSchema;
+
+/// 貸出情報一覧のクエリ。`limit` または `cursor` を指定した場合はカーソル方式でページングし、
+/// どちらも指定しない場合はすべての貸出情報を返す。
+#[derive(Debug, Deserialize, Validate)]
+pub struct CheckoutListQuery {
+    #[garde(range(min = 0))]
+    pub limit: Option<i64>,
+    #[garde(skip)]
+    pub cursor: Option<String>,
+}
+
+const DEFAULT_LIMIT: i64 = 20;
+
+impl TryFrom<CheckoutListQuery> for CheckoutListOptions {
+    type Error = AppError;
+
+    fn try_from(value: CheckoutListQuery) -> Result<Self, Self::Error> {
+        let CheckoutListQuery { limit, cursor } = value;
+        let cursor = cursor
+            .filter(|c| !c.is_empty())
+            .map(|c| c.parse::<Cursor>())
+            .transpose()?;
+        let limit = match (limit, &cursor) {
+            (None, Some(_)) => Some(DEFAULT_LIMIT),
+            (limit, _) => limit,
+        };
+        Ok(Self { limit, cursor })
+    }
+}
+
+/// 返却リクエスト。`locationId` には返却を受け付けた場所を指定する。省略した場合は配架場所に返却されたものとみなす。
+/// `condition` を指定すると、返却時の蔵書の状態を備考とともに記録する。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnBookRequest {
+    #[garde(skip)]
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    pub location_id: Option<LocationId>,
+    #[garde(skip)]
+    pub condition: Option<ConditionGrade>,
+    #[garde(inner(length(chars, max = 1000)))]
+    pub note: Option<String>,
+}
+
+#[derive(new)]
+pub struct ReturnBookRequestWithIds(CheckoutId, BookId, UserId, ReturnBookRequest);
+
+impl TryFrom<ReturnBookRequestWithIds> for UpdateReturned {
+    type Error = AppError;
+
+    fn try_from(value: ReturnBookRequestWithIds) -> Result<Self, Self::Error> {
+        let ReturnBookRequestWithIds(
+            checkout_id,
+            book_id,
+            user_id,
+            ReturnBookRequest {
+                location_id,
+                condition,
+                note,
+            },
+        ) = value;
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        let condition = match (condition, note) {
+            (Some(condition), note) => Some(ReportCondition::new(condition.into(), note)),
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err(AppError::BadRequest(
+                    "備考を記録する場合は蔵書の状態を指定してください。".into(),
+                ));
+            }
+        };
+        Ok(UpdateReturned::new(
+            checkout_id,
+            book_id,
+            user_id,
+            Utc::now(),
+            location_id,
+            condition,
+        ))
+    }
+}
+
+/// 紛失の届け出リクエスト。`replacementCharge` には借りたユーザーに求める弁償金の額を指定する。
+/// 弁償金を記録できるのは蔵書の所有者と管理者のみ。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Default, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportLostRequest {
+    #[garde(inner(range(min = 1)))]
+    pub replacement_charge: Option<i64>,
+}
+
+#[derive(new)]
+pub struct ReportLostRequestWithIds(CheckoutId, BookId, UserId, bool, ReportLostRequest);
+
+impl From<ReportLostRequestWithIds> for ReportLost {
+    fn from(value: ReportLostRequestWithIds) -> Self {
+        let ReportLostRequestWithIds(
+            checkout_id,
+            book_id,
+            user_id,
+            is_admin,
+            ReportLostRequest { replacement_charge },
+        ) = value;
+        Self {
+            checkout_id,
+            book_id,
+            requested_user: user_id,
+            is_admin,
+            reported_at: Utc::now(),
+            replacement_charge,
+        }
+    }
+}
+
+/// 返却時の蔵書の状態
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionGrade {
+    /// 良好
+    Good,
+    /// 使用感がある
+    Worn,
+    /// 破損している
+    Damaged,
+}
+
+impl From<BookCondition> for ConditionGrade {
+    fn from(value: BookCondition) -> Self {
+        match value {
+            BookCondition::Good => Self::Good,
+            BookCondition::Worn => Self::Worn,
+            BookCondition::Damaged => Self::Damaged,
+        }
+    }
+}
+
+impl From<ConditionGrade> for BookCondition {
+    fn from(value: ConditionGrade) -> Self {
+        match value {
+            ConditionGrade::Good => Self::Good,
+            ConditionGrade::Worn => Self::Worn,
+            ConditionGrade::Damaged => Self::Damaged,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookConditionsResponse {
+    pub items: Vec<BookConditionResponse>,
+}
+
+/// 返却時に記録された蔵書の状態
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookConditionResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub checkout_id: CheckoutId,
+    /// 返却された現物の ID
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    pub copy_id: Option<BookCopyId>,
+    /// 返却したユーザーの ID
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub reported_by: UserId,
+    pub reported_at: DateTime<Utc>,
+    pub condition: ConditionGrade,
+    pub note: Option<String>,
+}
+
+impl From<BookConditionReport> for BookConditionResponse {
+    fn from(value: BookConditionReport) -> Self {
+        let BookConditionReport {
+            checkout_id,
+            copy_id,
+            reported_by,
+            reported_at,
+            condition,
+            note,
+        } = value;
+        Self {
+            checkout_id,
+            copy_id,
+            reported_by,
+            reported_at,
+            condition: condition.into(),
+            note,
+        }
+    }
+}
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutsResponse {
     pub items: Vec<CheckoutResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
-impl From<Vec<Checkout>> for CheckoutsResponse {
-    fn from(value: Vec<Checkout>) -> Self {
+impl From<CursorPaginatedList<Checkout>> for CheckoutsResponse {
+    fn from(value: CursorPaginatedList<Checkout>) -> Self {
+        let CursorPaginatedList {
+            items,
+            next_cursor,
+            prev_cursor,
+        } = value;
         Self {
-            items: value.into_iter().map(CheckoutResponse::from).collect(),
+            items: items.into_iter().map(CheckoutResponse::from).collect(),
+            next_cursor: next_cursor.map(|c| c.to_string()),
+            prev_cursor: prev_cursor.map(|c| c.to_string()),
         }
     }
 }
@@ -21,9 +236,16 @@ impl From<Vec<Checkout>> for CheckoutsResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutResponse {
     pub id: CheckoutId,
+    pub copy_id: Option<BookCopyId>,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
     pub returned_at: Option<DateTime<Utc>>,
+    /// 貸出の終わり方。未返却の場合は省略する
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CheckoutOutcomeKind>,
+    /// 紛失した場合に記録された弁償金の額
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_charge: Option<i64>,
     pub book: CheckoutBookResponse,
 }
 
@@ -31,29 +253,51 @@ impl From<Checkout> for CheckoutResponse {
     fn from(value: Checkout) -> Self {
         let Checkout {
             id,
+            copy_id,
             checked_out_by,
             checked_out_at,
             returned_at,
+            outcome,
+            replacement_charge,
             book,
         } = value;
 
         Self {
             id,
+            copy_id,
             checked_out_by,
             checked_out_at,
             returned_at,
+            outcome: outcome.map(CheckoutOutcomeKind::from),
+            replacement_charge,
             book: book.into(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckoutOutcomeKind {
+    Returned,
+    Lost,
+}
+
+impl From<CheckoutOutcome> for CheckoutOutcomeKind {
+    fn from(value: CheckoutOutcome) -> Self {
+        match value {
+            CheckoutOutcome::Returned => Self::Returned,
+            CheckoutOutcome::Lost => Self::Lost,
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutBookResponse {
     pub id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
 }
 
 impl From<CheckoutBook> for CheckoutBookResponse {
@@ -0,0 +1,152 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use kernel::model::book::event::UpdateBookCover;
+use kernel::model::id::{BookId, UserId};
+use shared::error::{AppError, AppResult};
+use std::io::Cursor;
+
+/// アップロードできる表紙画像のファイルサイズの上限 (5 MiB)
+pub const MAX_COVER_IMAGE_SIZE: usize = 5 * 1024 * 1024;
+/// 表紙画像の幅・高さの上限。展開後のサイズが極端に大きい画像を拒否するために使う。
+const MAX_COVER_DIMENSION: u32 = 4096;
+/// 縮小画像が収まる大きさ
+const THUMBNAIL_WIDTH: u32 = 240;
+const THUMBNAIL_HEIGHT: u32 = 360;
+const THUMBNAIL_JPEG_QUALITY: u8 = 85;
+
+/// アップロードされた表紙画像。Content-Type と内容の形式が一致し、画像として読み込めることを確認済み。
+pub struct CoverImageUpload {
+    content_type: &'static str,
+    image: DynamicImage,
+    data: Vec<u8>,
+}
+
+impl CoverImageUpload {
+    /// JPEG・PNG・WebP の画像を受け付ける。
+    pub fn parse(content_type: &str, data: Vec<u8>) -> AppResult<Self> {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let (content_type, format) = match mime.as_str() {
+            "image/jpeg" => ("image/jpeg", ImageFormat::Jpeg),
+            "image/png" => ("image/png", ImageFormat::Png),
+            "image/webp" => ("image/webp", ImageFormat::WebP),
+            _ => {
+                return Err(AppError::BadRequest(format!(
+                    "サポートされていない Content-Type です: {content_type}"
+                )));
+            }
+        };
+
+        if image::guess_format(&data).ok() != Some(format) {
+            return Err(AppError::BadRequest(
+                "画像の形式が Content-Type と一致しません。".into(),
+            ));
+        }
+
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(MAX_COVER_DIMENSION);
+        limits.max_image_height = Some(MAX_COVER_DIMENSION);
+        let mut reader = ImageReader::with_format(Cursor::new(&data), format);
+        reader.limits(limits);
+        let image = reader
+            .decode()
+            .map_err(|e| AppError::BadRequest(format!("画像を読み込めませんでした: {e}")))?;
+
+        Ok(Self {
+            content_type,
+            image,
+            data,
+        })
+    }
+
+    /// 縮小画像を生成し、表紙画像の登録イベントに変換する。
+    pub fn into_event(
+        self,
+        book_id: BookId,
+        user_id: UserId,
+        is_admin: bool,
+    ) -> AppResult<UpdateBookCover> {
+        let Self {
+            content_type,
+            image,
+            data,
+        } = self;
+        Ok(UpdateBookCover {
+            book_id,
+            requested_user: user_id,
+            is_admin,
+            content_type: content_type.into(),
+            width: image.width(),
+            height: image.height(),
+            original: data,
+            thumbnail: thumbnail(&image)?,
+        })
+    }
+}
+
+/// 縦横比を保ったまま縮小した JPEG 画像。元画像が十分に小さい場合は拡大しない。
+fn thumbnail(image: &DynamicImage) -> AppResult<Vec<u8>> {
+    let resized = if image.width() > THUMBNAIL_WIDTH || image.height() > THUMBNAIL_HEIGHT {
+        image.thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+    } else {
+        image.clone()
+    };
+
+    let mut buf = Vec::new();
+    JpegEncoder::new_with_quality(&mut buf, THUMBNAIL_JPEG_QUALITY)
+        .encode_image(&resized.to_rgb8())
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        RgbImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_parse_and_make_thumbnail() -> anyhow::Result<()> {
+        let upload = CoverImageUpload::parse("image/png", png(600, 900))?;
+        let event = upload.into_event(BookId::new(), UserId::new(), false)?;
+        assert_eq!(event.content_type, "image/png");
+        assert_eq!((event.width, event.height), (600, 900));
+
+        let thumbnail = image::load_from_memory(&event.thumbnail)?;
+        assert_eq!(image::guess_format(&event.thumbnail)?, ImageFormat::Jpeg);
+        assert_eq!((thumbnail.width(), thumbnail.height()), (240, 360));
+
+        // 小さい画像は拡大しない
+        let upload = CoverImageUpload::parse("image/png; charset=binary", png(100, 50))?;
+        let event = upload.into_event(BookId::new(), UserId::new(), false)?;
+        let thumbnail = image::load_from_memory(&event.thumbnail)?;
+        assert_eq!((thumbnail.width(), thumbnail.height()), (100, 50));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reject_invalid_image() {
+        // Content-Type がサポート外
+        assert!(CoverImageUpload::parse("image/gif", b"GIF89a".to_vec()).is_err());
+        // Content-Type と内容の形式が一致しない
+        assert!(CoverImageUpload::parse("image/jpeg", png(10, 10)).is_err());
+        // 画像として読み込めない
+        let mut broken = png(10, 10);
+        broken.truncate(32);
+        assert!(CoverImageUpload::parse("image/png", broken).is_err());
+        // 大きすぎる
+        assert!(CoverImageUpload::parse("image/png", png(MAX_COVER_DIMENSION + 1, 1)).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::model::id::{BookCopyId, BookId, LocationId, UserId};
+use kernel::model::location::event::{
+    CreateLocation, ShelveBookCopy, UpdateBookLocation, UpdateLocation,
+};
+use kernel::model::location::{BookLocation, Location, LocationKind};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// 場所の作成リクエスト。部屋の親には拠点を、書架の親には部屋を指定する。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLocationRequest {
+    #[garde(skip)]
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    pub parent_id: Option<LocationId>,
+    #[garde(skip)]
+    pub kind: LocationLevel,
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: String,
+}
+
+impl From<CreateLocationRequest> for CreateLocation {
+    fn from(value: CreateLocationRequest) -> Self {
+        let CreateLocationRequest {
+            parent_id,
+            kind,
+            name,
+        } = value;
+        Self {
+            parent_id,
+            kind: kind.into(),
+            name: name.trim().to_string(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLocationRequest {
+    #[garde(length(chars, min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(new)]
+pub struct UpdateLocationRequestWithId(LocationId, UpdateLocationRequest);
+
+impl From<UpdateLocationRequestWithId> for UpdateLocation {
+    fn from(value: UpdateLocationRequestWithId) -> Self {
+        let UpdateLocationRequestWithId(location_id, UpdateLocationRequest { name }) = value;
+        Self {
+            location_id,
+            name: name.trim().to_string(),
+        }
+    }
+}
+
+/// 蔵書の配架場所の変更リクエスト。`locationId` に null を指定すると配架場所を解除する。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBookLocationRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    pub location_id: Option<LocationId>,
+}
+
+#[derive(new)]
+pub struct UpdateBookLocationRequestWithIds(BookId, UserId, bool, UpdateBookLocationRequest);
+
+impl From<UpdateBookLocationRequestWithIds> for UpdateBookLocation {
+    fn from(value: UpdateBookLocationRequestWithIds) -> Self {
+        let UpdateBookLocationRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            UpdateBookLocationRequest { location_id },
+        ) = value;
+        Self {
+            book_id,
+            location_id,
+            requested_user: user_id,
+            is_admin,
+        }
+    }
+}
+
+#[derive(new)]
+pub struct ShelveBookCopyRequestWithIds(BookId, BookCopyId, UserId, bool);
+
+impl From<ShelveBookCopyRequestWithIds> for ShelveBookCopy {
+    fn from(value: ShelveBookCopyRequestWithIds) -> Self {
+        let ShelveBookCopyRequestWithIds(book_id, copy_id, user_id, is_admin) = value;
+        Self {
+            book_id,
+            copy_id,
+            requested_user: user_id,
+            is_admin,
+        }
+    }
+}
+
+/// 場所の階層
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationLevel {
+    Branch,
+    Room,
+    Shelf,
+}
+
+impl From<LocationKind> for LocationLevel {
+    fn from(value: LocationKind) -> Self {
+        match value {
+            LocationKind::Branch => Self::Branch,
+            LocationKind::Room => Self::Room,
+            LocationKind::Shelf => Self::Shelf,
+        }
+    }
+}
+
+impl From<LocationLevel> for LocationKind {
+    fn from(value: LocationLevel) -> Self {
+        match value {
+            LocationLevel::Branch => Self::Branch,
+            LocationLevel::Room => Self::Room,
+            LocationLevel::Shelf => Self::Shelf,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationsResponse {
+    pub items: Vec<LocationResponse>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: LocationId,
+    /// 親の場所の ID。拠点の場合は null
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, format = Uuid))]
+    pub parent_id: Option<LocationId>,
+    pub kind: LocationLevel,
+    pub name: String,
+}
+
+impl From<Location> for LocationResponse {
+    fn from(value: Location) -> Self {
+        let Location {
+            id,
+            parent_id,
+            kind,
+            name,
+        } = value;
+        Self {
+            id,
+            parent_id,
+            kind: kind.into(),
+            name,
+        }
+    }
+}
+
+/// 蔵書の配架場所（書架）と、書架が属する部屋・拠点の名前
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookLocationResponse {
+    /// 書架の ID
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: LocationId,
+    pub branch: String,
+    pub room: String,
+    pub shelf: String,
+}
+
+impl From<BookLocation> for BookLocationResponse {
+    fn from(value: BookLocation) -> Self {
+        let BookLocation {
+            id,
+            branch,
+            room,
+            shelf,
+        } = value;
+        Self {
+            id,
+            branch,
+            room,
+            shelf,
+        }
+    }
+}
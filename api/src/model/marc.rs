@@ -0,0 +1,211 @@
+/// MARC21 の書誌レコード。ISO 2709 形式と MARCXML 形式で出力できる。
+#[derive(Debug, Default)]
+pub struct MarcRecord {
+    fields: Vec<MarcField>,
+}
+
+#[derive(Debug)]
+enum MarcField {
+    Control {
+        tag: &'static str,
+        value: String,
+    },
+    Data {
+        tag: &'static str,
+        indicators: [char; 2],
+        subfields: Vec<(char, String)>,
+    },
+}
+
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+const FIELD_TERMINATOR: u8 = 0x1E;
+const RECORD_TERMINATOR: u8 = 0x1D;
+const LEADER_LEN: usize = 24;
+
+pub const MARCXML_HEADER: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    "\n",
+    r#"<collection xmlns="http://www.loc.gov/MARC21/slim">"#,
+    "\n"
+);
+pub const MARCXML_FOOTER: &str = "</collection>\n";
+
+impl MarcRecord {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn control_field(mut self, tag: &'static str, value: impl Into<String>) -> Self {
+        self.fields.push(MarcField::Control {
+            tag,
+            value: value.into(),
+        });
+        self
+    }
+
+    /// データフィールドを追加する。値が空のサブフィールドは出力しない。
+    pub fn data_field(
+        mut self,
+        tag: &'static str,
+        indicators: [char; 2],
+        subfields: impl IntoIterator<Item = (char, String)>,
+    ) -> Self {
+        let subfields = subfields
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .collect::<Vec<_>>();
+        if !subfields.is_empty() {
+            self.fields.push(MarcField::Data {
+                tag,
+                indicators,
+                subfields,
+            });
+        }
+        self
+    }
+
+    /// ISO 2709 形式（文字コードは UTF-8）に変換する。
+    pub fn to_iso2709(&self) -> Vec<u8> {
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        for field in &self.fields {
+            let start = data.len();
+            match field {
+                MarcField::Control { value, .. } => data.extend_from_slice(value.as_bytes()),
+                MarcField::Data {
+                    indicators,
+                    subfields,
+                    ..
+                } => {
+                    for indicator in indicators {
+                        data.extend_from_slice(indicator.encode_utf8(&mut [0; 4]).as_bytes());
+                    }
+                    for (code, value) in subfields {
+                        data.push(SUBFIELD_DELIMITER);
+                        data.extend_from_slice(code.encode_utf8(&mut [0; 4]).as_bytes());
+                        data.extend_from_slice(value.as_bytes());
+                    }
+                }
+            }
+            data.push(FIELD_TERMINATOR);
+            directory.extend_from_slice(
+                format!("{}{:04}{:05}", field.tag(), data.len() - start, start).as_bytes(),
+            );
+        }
+        directory.push(FIELD_TERMINATOR);
+
+        let base_address = LEADER_LEN + directory.len();
+        let record_len = base_address + data.len() + 1;
+        let mut record = leader(record_len, base_address).into_bytes();
+        record.append(&mut directory);
+        record.append(&mut data);
+        record.push(RECORD_TERMINATOR);
+        record
+    }
+
+    /// MARCXML の `record` 要素に変換する。
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<record>");
+        xml.push_str(&format!("<leader>{}</leader>", leader(0, 0)));
+        for field in &self.fields {
+            match field {
+                MarcField::Control { tag, value } => {
+                    xml.push_str(&format!(
+                        r#"<controlfield tag="{tag}">{}</controlfield>"#,
+                        escape_xml(value)
+                    ));
+                }
+                MarcField::Data {
+                    tag,
+                    indicators: [ind1, ind2],
+                    subfields,
+                } => {
+                    xml.push_str(&format!(
+                        r#"<datafield tag="{tag}" ind1="{ind1}" ind2="{ind2}">"#
+                    ));
+                    for (code, value) in subfields {
+                        xml.push_str(&format!(
+                            r#"<subfield code="{code}">{}</subfield>"#,
+                            escape_xml(value)
+                        ));
+                    }
+                    xml.push_str("</datafield>");
+                }
+            }
+        }
+        xml.push_str("</record>\n");
+        xml
+    }
+}
+
+impl MarcField {
+    fn tag(&self) -> &'static str {
+        match self {
+            Self::Control { tag, .. } | Self::Data { tag, .. } => tag,
+        }
+    }
+}
+
+/// 新規の図書 (nam) を表し、文字コードを UCS/Unicode とするリーダー
+fn leader(record_len: usize, base_address: usize) -> String {
+    format!("{record_len:05}nam a22{base_address:05}   4500")
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 で使えない制御文字は除く
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> MarcRecord {
+        MarcRecord::new()
+            .control_field("001", "book-1")
+            .data_field("245", ['1', '0'], [('a', "実践Rust".to_string())])
+            .data_field("520", [' ', ' '], [('a', String::new())])
+    }
+
+    #[test]
+    fn test_to_iso2709() {
+        let bytes = record().to_iso2709();
+        let title = "実践Rust".len();
+
+        // リーダー + ディレクトリ (2 件 + 終端) + データ + レコード終端
+        let base_address = 24 + 12 * 2 + 1;
+        let data_len = ("book-1".len() + 1) + (2 + 2 + title + 1);
+        assert_eq!(bytes.len(), base_address + data_len + 1);
+        assert_eq!(&bytes[..5], format!("{:05}", bytes.len()).as_bytes());
+        assert_eq!(&bytes[12..17], format!("{base_address:05}").as_bytes());
+        assert_eq!(
+            &bytes[24..48],
+            format!("001000700000245{:04}00007", 2 + 2 + title + 1).as_bytes()
+        );
+        assert_eq!(bytes[base_address - 1], FIELD_TERMINATOR);
+        assert_eq!(bytes.last(), Some(&RECORD_TERMINATOR));
+    }
+
+    #[test]
+    fn test_to_xml() {
+        let xml = MarcRecord::new()
+            .data_field("245", ['1', '0'], [('a', "R&D <入門>".to_string())])
+            .to_xml();
+        assert!(xml.contains(
+            r#"<datafield tag="245" ind1="1" ind2="0"><subfield code="a">R&amp;D &lt;入門&gt;</subfield></datafield>"#
+        ));
+        assert!(!xml.contains("520"));
+    }
+}
@@ -0,0 +1,24 @@
+//! JSON Merge Patch (RFC 7396) 形式のリクエストを扱うための補助関数
+
+use serde::{Deserialize, Deserializer};
+use shared::error::{AppError, AppResult};
+
+/// JSON Merge Patch の項目を読み込む。
+/// 項目がない場合は `None`、`null` の場合は `Some(None)`、値がある場合は `Some(Some(value))` になる。
+/// `#[serde(default, deserialize_with = "merge_patch::field")]` と組み合わせて使う。
+pub fn field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 削除できない項目に `null` が指定された場合はエラーにする。
+pub fn required<T>(field: Option<Option<T>>, name: &str) -> AppResult<Option<T>> {
+    match field {
+        Some(None) => Err(AppError::BadRequest(format!("{name}は削除できません。"))),
+        Some(value) => Ok(value),
+        None => Ok(None),
+    }
+}
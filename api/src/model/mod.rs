@@ -1,4 +1,12 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod cover;
+pub mod location;
+pub mod marc;
+pub mod merge_patch;
+pub mod recommendation;
+pub mod review;
+pub mod tag;
 pub mod user;
@@ -0,0 +1,70 @@
+use garde::Validate;
+use kernel::model::id::BookId;
+use kernel::model::isbn::Isbn;
+use kernel::model::recommendation::RecommendedBook;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RecommendationQuery {
+    #[garde(range(min = 1, max = MAX_LIMIT))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+const DEFAULT_LIMIT: i64 = 10;
+const MAX_LIMIT: i64 = 50;
+const fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedBooksResponse {
+    pub items: Vec<RecommendedBookResponse>,
+}
+
+impl From<Vec<RecommendedBook>> for RecommendedBooksResponse {
+    fn from(value: Vec<RecommendedBook>) -> Self {
+        Self {
+            items: value
+                .into_iter()
+                .map(RecommendedBookResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedBookResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: BookId,
+    pub title: String,
+    pub author: String,
+    #[cfg_attr(debug_assertions, schema(value_type = String))]
+    pub isbn: Isbn,
+    /// 推薦の強さ。大きいほど関連が強い
+    pub score: i64,
+}
+
+impl From<RecommendedBook> for RecommendedBookResponse {
+    fn from(value: RecommendedBook) -> Self {
+        let RecommendedBook {
+            book_id,
+            title,
+            author,
+            isbn,
+            score,
+        } = value;
+        Self {
+            id: book_id,
+            title,
+            author,
+            isbn,
+            score,
+        }
+    }
+}
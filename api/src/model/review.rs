@@ -0,0 +1,156 @@
+use super::user::Reviewer;
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use kernel::model::id::{BookId, ReviewId, UserId};
+use kernel::model::list::PaginatedList;
+use kernel::model::review::event::{CreateReview, UpdateReview};
+use kernel::model::review::{Review, ReviewListOptions};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewRequest {
+    /// 1 から 5 までの評価
+    #[garde(range(min = 1, max = 5))]
+    pub rating: i16,
+    #[garde(length(chars, max = 2048))]
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(new)]
+pub struct CreateReviewRequestWithIds(BookId, UserId, CreateReviewRequest);
+
+impl From<CreateReviewRequestWithIds> for CreateReview {
+    fn from(value: CreateReviewRequestWithIds) -> Self {
+        let CreateReviewRequestWithIds(book_id, user_id, CreateReviewRequest { rating, comment }) =
+            value;
+        Self {
+            book_id,
+            rating,
+            comment,
+            requested_user: user_id,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReviewRequest {
+    /// 1 から 5 までの評価
+    #[garde(range(min = 1, max = 5))]
+    pub rating: i16,
+    #[garde(length(chars, max = 2048))]
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(new)]
+pub struct UpdateReviewRequestWithIds(BookId, ReviewId, UserId, UpdateReviewRequest);
+
+impl From<UpdateReviewRequestWithIds> for UpdateReview {
+    fn from(value: UpdateReviewRequestWithIds) -> Self {
+        let UpdateReviewRequestWithIds(
+            book_id,
+            review_id,
+            user_id,
+            UpdateReviewRequest { rating, comment },
+        ) = value;
+        Self {
+            review_id,
+            book_id,
+            rating,
+            comment,
+            requested_user: user_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReviewListQuery {
+    #[garde(range(min = 0, max = MAX_LIMIT))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+const fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+impl From<ReviewListQuery> for ReviewListOptions {
+    fn from(value: ReviewListQuery) -> Self {
+        let ReviewListQuery { limit, offset } = value;
+        Self { limit, offset }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedReviewResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<ReviewResponse>,
+}
+
+impl From<PaginatedList<Review>> for PaginatedReviewResponse {
+    fn from(value: PaginatedList<Review>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = value;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(ReviewResponse::from).collect(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: ReviewId,
+    pub reviewer: Reviewer,
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Review> for ReviewResponse {
+    fn from(value: Review) -> Self {
+        let Review {
+            id,
+            book_id: _,
+            reviewer,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        Self {
+            id,
+            reviewer: reviewer.into(),
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::model::id::TagId;
+use kernel::model::tag::Tag;
+use kernel::model::tag::event::{CreateTag, MergeTag, UpdateTag};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagRequest {
+    #[garde(length(chars, min = 1, max = 64))]
+    pub name: String,
+}
+
+impl From<CreateTagRequest> for CreateTag {
+    fn from(value: CreateTagRequest) -> Self {
+        Self {
+            name: value.name.trim().to_string(),
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagRequest {
+    #[garde(length(chars, min = 1, max = 64))]
+    pub name: String,
+}
+
+#[derive(new)]
+pub struct UpdateTagRequestWithId(TagId, UpdateTagRequest);
+
+impl From<UpdateTagRequestWithId> for UpdateTag {
+    fn from(value: UpdateTagRequestWithId) -> Self {
+        let UpdateTagRequestWithId(tag_id, UpdateTagRequest { name }) = value;
+        Self {
+            tag_id,
+            name: name.trim().to_string(),
+        }
+    }
+}
+
+/// パスで指定したタグを `targetTagId` のタグに統合するリクエスト
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTagRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub target_tag_id: TagId,
+}
+
+#[derive(new)]
+pub struct MergeTagRequestWithId(TagId, MergeTagRequest);
+
+impl From<MergeTagRequestWithId> for MergeTag {
+    fn from(value: MergeTagRequestWithId) -> Self {
+        let MergeTagRequestWithId(source, MergeTagRequest { target_tag_id }) = value;
+        Self {
+            source,
+            target: target_tag_id,
+        }
+    }
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagsResponse {
+    pub items: Vec<TagResponse>,
+}
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagResponse {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: TagId,
+    pub name: String,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(value: Tag) -> Self {
+        let Tag { id, name } = value;
+        Self { id, name }
+    }
+}
@@ -5,9 +5,12 @@ use kernel::model::{
     role::Role,
     user::{
         User,
-        event::{CreateUser, UpdateUserPassword, UpdateUserRole},
+        event::{CreateUser, UpdateUser, UpdateUserPassword, UpdateUserRole},
     },
 };
+use shared::error::AppError;
+
+use super::merge_patch;
 use serde::{Deserialize, Serialize};
 use strum::VariantNames;
 use utoipa::ToSchema;
@@ -70,6 +73,42 @@ impl From<User> for UserResponse {
     }
 }
 
+/// ユーザー情報の部分更新リクエスト（JSON Merge Patch）。指定した項目のみを更新する。
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserRequest {
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(inner(inner(length(min = 1))))]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    name: Option<Option<String>>,
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>))]
+    #[garde(inner(inner(email)))]
+    #[serde(default, deserialize_with = "merge_patch::field")]
+    email: Option<Option<String>>,
+}
+
+#[derive(new)]
+pub struct UpdateUserRequestWithUserId(UserId, UpdateUserRequest);
+
+impl TryFrom<UpdateUserRequestWithUserId> for UpdateUser {
+    type Error = AppError;
+
+    fn try_from(value: UpdateUserRequestWithUserId) -> Result<Self, Self::Error> {
+        let UpdateUserRequestWithUserId(user_id, UpdateUserRequest { name, email }) = value;
+        if name.is_none() && email.is_none() {
+            return Err(AppError::BadRequest(
+                "更新する項目を指定してください。".into(),
+            ));
+        }
+        Ok(Self {
+            user_id,
+            name: merge_patch::required(name, "名前")?,
+            email: merge_patch::required(email, "メールアドレス")?,
+        })
+    }
+}
+
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[derive(Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
@@ -163,7 +202,7 @@ impl From<kernel::model::user::BookOwner> for BookOwner {
 }
 
 #[cfg_attr(debug_assertions, derive(ToSchema))]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutUser {
     #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
@@ -177,3 +216,19 @@ impl From<kernel::model::user::CheckoutUser> for CheckoutUser {
         Self { id, name }
     }
 }
+
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reviewer {
+    #[cfg_attr(debug_assertions, schema(value_type = String, format = Uuid))]
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::Reviewer> for Reviewer {
+    fn from(value: kernel::model::user::Reviewer) -> Self {
+        let kernel::model::user::Reviewer { id, name } = value;
+        Self { id, name }
+    }
+}
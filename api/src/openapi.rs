@@ -11,25 +11,140 @@ use crate::{handler, model};
         handler::health::health_check_db,
         handler::book::show_book_list,
         handler::book::show_book,
+        handler::book::lookup_book_metadata,
         handler::book::register_book,
+        handler::book::import_books,
+        handler::book::export_books,
         handler::book::update_book,
+        handler::book::patch_book,
         handler::book::delete_book,
+        handler::book::restore_book,
+        handler::book::purge_book,
+        handler::book::show_book_revisions,
+        handler::book::show_book_audit_logs,
+        handler::book::revert_book,
+        handler::book::transfer_book,
+        handler::book::change_book_status,
+        handler::book::start_book_repair,
+        handler::book::finish_book_repair,
+        handler::book::show_book_status_history,
+        handler::book::mark_book_found,
+        handler::book::show_book_copies,
+        handler::book::add_book_copy,
+        handler::book::delete_book_copy,
+        handler::book::upload_book_cover,
+        handler::book::show_book_cover,
+        handler::book::show_book_cover_thumbnail,
+        handler::book::delete_book_cover,
+        handler::author::show_author_list,
+        handler::author::register_author,
+        handler::author::show_author,
+        handler::author::update_author,
+        handler::author::merge_author,
+        handler::author::update_book_authors,
+        handler::location::show_location_list,
+        handler::location::register_location,
+        handler::location::show_location,
+        handler::location::update_location,
+        handler::location::delete_location,
+        handler::location::update_book_location,
+        handler::location::shelve_book_copy,
+        handler::recommendation::show_related_books,
+        handler::recommendation::show_my_recommendations,
+        handler::tag::show_tag_list,
+        handler::tag::register_tag,
+        handler::tag::update_tag,
+        handler::tag::merge_tag,
+        handler::tag::attach_book_tag,
+        handler::tag::detach_book_tag,
+        handler::review::show_review_list,
+        handler::review::register_review,
+        handler::review::update_review,
+        handler::review::delete_review,
         handler::checkout::checkout_book,
         handler::checkout::return_book,
+        handler::checkout::report_lost_book,
         handler::checkout::show_checked_out_list,
         handler::checkout::checkout_history,
+        handler::checkout::show_book_condition_history,
         handler::user::get_current_user,
+        handler::user::update_current_user,
+        handler::user::transfer_user_books,
         handler::auth::login,
         handler::auth::logout
     ),
     components(schemas(
         model::book::CreateBookRequest,
+        model::book::BookRegistrationResponse,
         model::book::UpdateBookRequest,
+        model::book::PatchBookRequest,
         model::book::BookResponse,
         model::book::PaginatedBookResponse,
+        model::book::CursorPaginatedBookResponse,
+        model::book::BookListResponse,
         model::book::BookCheckoutResponse,
+        model::book::BookMetadataResponse,
+        model::book::BookImportResponse,
+        model::book::BookImportRowResponse,
+        model::book::BookImportRowStatus,
+        model::book::BookExportFormat,
+        model::book::CreateBookCopyRequest,
+        model::book::BookCopiesResponse,
+        model::book::BookCopyResponse,
+        model::book::BookRevisionsResponse,
+        model::book::BookRevisionResponse,
+        model::book::BookRevisionActionResponse,
+        model::book::BookRevisionUserResponse,
+        model::book::BookRevisionFieldsResponse,
+        model::book::BookAuditLogsResponse,
+        model::book::BookAuditLogResponse,
+        model::book::TransferBookRequest,
+        model::book::TransferBooksResponse,
+        model::book::BookStatusKind,
+        model::book::ChangeBookStatusRequest,
+        model::book::BookStatusHistoryResponse,
+        model::book::BookStatusChangeResponse,
+        model::book::BookAvailability,
+        model::book::BookSortKey,
+        model::book::SortOrder,
+        model::author::CreateAuthorRequest,
+        model::author::UpdateAuthorRequest,
+        model::author::MergeAuthorRequest,
+        model::author::UpdateBookAuthorsRequest,
+        model::author::BookAuthorRequest,
+        model::author::BookAuthorRole,
+        model::author::AuthorsResponse,
+        model::author::AuthorResponse,
+        model::author::AuthorDetailResponse,
+        model::author::AuthoredBookResponse,
+        model::author::BookAuthorResponse,
+        model::checkout::ReturnBookRequest,
+        model::checkout::ConditionGrade,
+        model::checkout::ReportLostRequest,
+        model::checkout::BookConditionsResponse,
+        model::checkout::BookConditionResponse,
+        model::location::CreateLocationRequest,
+        model::location::UpdateLocationRequest,
+        model::location::UpdateBookLocationRequest,
+        model::location::LocationLevel,
+        model::location::LocationsResponse,
+        model::location::LocationResponse,
+        model::location::BookLocationResponse,
+        model::recommendation::RecommendedBooksResponse,
+        model::recommendation::RecommendedBookResponse,
+        model::tag::CreateTagRequest,
+        model::tag::UpdateTagRequest,
+        model::tag::MergeTagRequest,
+        model::tag::TagsResponse,
+        model::tag::TagResponse,
+        model::review::CreateReviewRequest,
+        model::review::UpdateReviewRequest,
+        model::review::PaginatedReviewResponse,
+        model::review::ReviewResponse,
+        model::user::UpdateUserRequest,
         model::user::BookOwner,
         model::user::CheckoutUser,
+        model::user::Reviewer,
         model::auth::LoginRequest,
         model::auth::AccessTokenResponse,
     ))
@@ -0,0 +1,16 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+use registry::AppRegistry;
+
+use crate::handler::author::{
+    merge_author, register_author, show_author, show_author_list, update_author,
+};
+
+pub fn build_author_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/authors", get(show_author_list).post(register_author))
+        .route("/authors/{author_id}", get(show_author).put(update_author))
+        .route("/authors/{author_id}/merge", post(merge_author))
+}
@@ -1,21 +1,80 @@
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
 };
 use registry::AppRegistry;
 
-use crate::handler::book::{delete_book, register_book, show_book, show_book_list, update_book};
+use crate::handler::author::update_book_authors;
+use crate::handler::book::{
+    add_book_copy, change_book_status, delete_book, delete_book_copy, delete_book_cover,
+    export_books, finish_book_repair, import_books, lookup_book_metadata, mark_book_found,
+    patch_book, purge_book, register_book, restore_book, revert_book, show_book,
+    show_book_audit_logs, show_book_copies, show_book_cover, show_book_cover_thumbnail,
+    show_book_list, show_book_revisions, show_book_status_history, start_book_repair,
+    transfer_book, update_book, upload_book_cover,
+};
 use crate::handler::checkout::{
-    checkout_book, checkout_history, return_book, show_checked_out_list,
+    checkout_book, checkout_history, report_lost_book, return_book, show_book_condition_history,
+    show_checked_out_list,
 };
+use crate::handler::location::{shelve_book_copy, update_book_location};
+use crate::handler::recommendation::show_related_books;
+use crate::handler::review::{delete_review, register_review, show_review_list, update_review};
+use crate::handler::tag::{attach_book_tag, detach_book_tag};
+use crate::model::cover::MAX_COVER_IMAGE_SIZE;
 
 pub fn build_book_routers() -> Router<AppRegistry> {
     let books_routers = Router::new()
         .route("/", post(register_book))
         .route("/", get(show_book_list))
+        .route("/import", post(import_books))
+        .route("/export", get(export_books))
+        .route("/lookup", get(lookup_book_metadata))
         .route("/{book_id}", get(show_book))
         .route("/{book_id}", put(update_book))
-        .route("/{book_id}", delete(delete_book));
+        .route("/{book_id}", patch(patch_book))
+        .route("/{book_id}", delete(delete_book))
+        .route("/{book_id}/restore", post(restore_book))
+        .route("/{book_id}/purge", delete(purge_book))
+        .route("/{book_id}/transfer", post(transfer_book))
+        .route("/{book_id}/status", put(change_book_status))
+        .route(
+            "/{book_id}/repair",
+            put(start_book_repair).delete(finish_book_repair),
+        )
+        .route("/{book_id}/status-history", get(show_book_status_history))
+        .route("/{book_id}/related", get(show_related_books))
+        .route("/{book_id}/revisions", get(show_book_revisions))
+        .route("/{book_id}/audit-logs", get(show_book_audit_logs))
+        .route(
+            "/{book_id}/revisions/{revision_id}/revert",
+            post(revert_book),
+        )
+        .route("/{book_id}/copies", get(show_book_copies))
+        .route("/{book_id}/copies", post(add_book_copy))
+        .route("/{book_id}/copies/{copy_id}", delete(delete_book_copy))
+        .route("/{book_id}/copies/{copy_id}/shelve", post(shelve_book_copy))
+        .route("/{book_id}/copies/{copy_id}/found", post(mark_book_found))
+        .route(
+            "/{book_id}/cover",
+            put(upload_book_cover).layer(DefaultBodyLimit::max(MAX_COVER_IMAGE_SIZE)),
+        )
+        .route("/{book_id}/cover", get(show_book_cover))
+        .route("/{book_id}/cover", delete(delete_book_cover))
+        .route("/{book_id}/cover/thumbnail", get(show_book_cover_thumbnail))
+        .route("/{book_id}/authors", put(update_book_authors))
+        .route("/{book_id}/location", put(update_book_location))
+        .route("/{book_id}/tags/{tag_id}", put(attach_book_tag))
+        .route("/{book_id}/tags/{tag_id}", delete(detach_book_tag))
+        .route(
+            "/{book_id}/reviews",
+            get(show_review_list).post(register_review),
+        )
+        .route(
+            "/{book_id}/reviews/{review_id}",
+            put(update_review).delete(delete_review),
+        );
 
     let checkout_router = Router::new()
         .route("/checkouts", get(show_checked_out_list))
@@ -24,7 +83,12 @@ pub fn build_book_routers() -> Router<AppRegistry> {
             "/{book_id}/checkouts/{checkout_id}/returned",
             put(return_book),
         )
-        .route("/{book_id}/checkout-history", get(checkout_history));
+        .route(
+            "/{book_id}/checkouts/{checkout_id}/lost",
+            post(report_lost_book),
+        )
+        .route("/{book_id}/checkout-history", get(checkout_history))
+        .route("/{book_id}/conditions", get(show_book_condition_history));
 
     Router::new().nest("/books", books_routers.merge(checkout_router))
 }
@@ -0,0 +1,20 @@
+use axum::{Router, routing::get};
+use registry::AppRegistry;
+
+use crate::handler::location::{
+    delete_location, register_location, show_location, show_location_list, update_location,
+};
+
+pub fn build_location_router() -> Router<AppRegistry> {
+    Router::new()
+        .route(
+            "/locations",
+            get(show_location_list).post(register_location),
+        )
+        .route(
+            "/locations/{location_id}",
+            get(show_location)
+                .put(update_location)
+                .delete(delete_location),
+        )
+}
@@ -0,0 +1,14 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+use registry::AppRegistry;
+
+use crate::handler::tag::{merge_tag, register_tag, show_tag_list, update_tag};
+
+pub fn build_tag_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/tags", get(show_tag_list).post(register_tag))
+        .route("/tags/{tag_id}", put(update_tag))
+        .route("/tags/{tag_id}/merge", post(merge_tag))
+}
@@ -1,20 +1,26 @@
 use axum::{
     Router,
-    routing::{delete, get, put},
+    routing::{delete, get, post, put},
 };
 use registry::AppRegistry;
 
+use crate::handler::recommendation::show_my_recommendations;
 use crate::handler::user::{
     change_password, change_role, delete_user, get_checkouts, get_current_user, list_users,
-    register_user,
+    register_user, transfer_user_books, update_current_user,
 };
 
 pub fn build_user_router() -> Router<AppRegistry> {
     Router::new()
-        .route("/users/me", get(get_current_user))
+        .route(
+            "/users/me",
+            get(get_current_user).patch(update_current_user),
+        )
         .route("/users/me/password", put(change_password))
         .route("/users/me/checkouts", get(get_checkouts))
+        .route("/users/me/recommendations", get(show_my_recommendations))
         .route("/users", get(list_users).post(register_user))
         .route("/users/{user_id}", delete(delete_user))
         .route("/users/{user_id}/role", put(change_role))
+        .route("/users/{user_id}/books/transfer", post(transfer_user_books))
 }
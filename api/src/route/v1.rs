@@ -1,5 +1,8 @@
+use crate::route::author::build_author_router;
 use crate::route::book::build_book_routers;
 use crate::route::health::build_health_check_routes;
+use crate::route::location::build_location_router;
+use crate::route::tag::build_tag_router;
 use crate::route::user::build_user_router;
 use axum::Router;
 use registry::AppRegistry;
@@ -8,7 +11,10 @@ pub fn routes() -> Router<AppRegistry> {
     let router = Router::new()
         .merge(build_health_check_routes())
         .merge(build_book_routers())
-        .merge(build_user_router());
+        .merge(build_user_router())
+        .merge(build_tag_router())
+        .merge(build_author_router())
+        .merge(build_location_router());
 
     Router::new().nest("/api/v1", router)
 }
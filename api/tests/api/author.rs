@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::author::{AuthorDetailResponse, BookAuthorRole};
+use kernel::{
+    model::{
+        author::{Author, AuthorDetail, AuthorRole, AuthoredBook},
+        id::{AuthorId, BookId},
+    },
+    repository::author::MockAuthorRepository,
+};
+
+#[rstest]
+#[tokio::test]
+async fn show_author_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let author_id = AuthorId::new();
+    let book_id = BookId::new();
+
+    fixture.expect_author_repository().returning(move || {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_find_by_id()
+            .withf(move |id| *id == author_id)
+            .returning(move |id| {
+                Ok(Some(AuthorDetail {
+                    id,
+                    name: "初田直也".into(),
+                    books: vec![AuthoredBook {
+                        book_id,
+                        title: "実践Rustプログラミング入門".into(),
+                        role: AuthorRole::Author,
+                    }],
+                }))
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/authors/{author_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, AuthorDetailResponse);
+    assert_eq!(result.id, author_id);
+    assert_eq!(result.books.len(), 1);
+    assert_eq!(result.books[0].id, book_id);
+    assert_eq!(result.books[0].role, BookAuthorRole::Author);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_author_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_author_repository().returning(|| {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_find_by_id().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/authors/{}", AuthorId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn update_book_authors_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let author = AuthorId::new();
+    let translator = AuthorId::new();
+
+    fixture.expect_author_repository().returning(move || {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_update_book_authors()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.authors
+                        == [
+                            (author, AuthorRole::Author),
+                            (translator, AuthorRole::Translator),
+                        ]
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    // 役割を省略した場合は著者として扱う
+    let body = format!(
+        r#"{{"items": [{{"authorId": "{author}"}}, {{"authorId": "{translator}", "role": "translator"}}]}}"#
+    );
+    let req = Request::put(v1(&format!("/books/{book_id}/authors")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Request::put(v1(&format!("/authors/{}", AuthorId::new()))), r#"{"name": "初田直也"}"#.to_string())]
+#[case(
+    Request::post(v1(&format!("/authors/{}/merge", AuthorId::new()))),
+    format!(r#"{{"targetAuthorId": "{}"}}"#, AuthorId::new())
+)]
+#[tokio::test]
+async fn manage_author_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+    #[case] req: axum::http::request::Builder,
+    #[case] body: String,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = req.bearer().application_json().body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"name": ""}"#)]
+#[case(r#"{"name": "   "}"#)]
+#[tokio::test]
+async fn register_author_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/authors"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_author_trims_name(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_author_repository().returning(|| {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_create()
+            .withf(|event| event.name == "初田直也")
+            .returning(|event| {
+                Ok(Author {
+                    id: AuthorId::new(),
+                    name: event.name,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/authors"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name": "  初田直也  "}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
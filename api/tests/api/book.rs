@@ -6,18 +6,32 @@ use tower::ServiceExt;
 
 use crate::{
     deserialize_json,
-    helper::{TestRequestExt, fixture, make_router, v1},
+    helper::{TestRequestExt, current_user_id, fixture, fixture_admin, make_router, v1},
+};
+use api::model::book::{
+    BookAuditLogsResponse, BookImportResponse, BookImportRowStatus, BookMetadataResponse,
+    BookRegistrationResponse, BookRevisionActionResponse, BookRevisionsResponse,
+    BookStatusHistoryResponse, BookStatusKind, CursorPaginatedBookResponse, PaginatedBookResponse,
+    TransferBooksResponse,
 };
-use api::model::book::PaginatedBookResponse;
 use kernel::{
     model::{
-        book::Book,
-        id::{BookId, UserId},
-        list::PaginatedList,
-        user::BookOwner,
+        book::{
+            Book, BookAvailability, BookImportResult, BookImportStatus, BookMetadata,
+            BookRegistration, BookSort, BookSortKey, BookStatus, BookStatusChange, Checkout,
+            DuplicateBookPolicy,
+            revision::{
+                BookAuditLog, BookRevision, BookRevisionAction, BookRevisionFields,
+                BookRevisionUser,
+            },
+        },
+        id::{BookAuditLogId, BookCopyId, BookId, BookRevisionId, CheckoutId, UserId},
+        list::{Cursor, CursorPaginatedList, PaginatedList, SortOrder},
+        user::{BookOwner, CheckoutUser},
     },
-    repository::book::MockBookRepository,
+    repository::{book::MockBookRepository, book_metadata::MockBookMetadataProvider},
 };
+use shared::error::AppError;
 
 #[rstest]
 #[case("/books", 20, 0)]
@@ -32,6 +46,7 @@ async fn show_book_list_with_query_200(
     #[case] expected_offset: i64,
 ) -> anyhow::Result<()> {
     let book_id = BookId::new();
+    let checkout_ids = [CheckoutId::new(), CheckoutId::new()];
 
     fixture.expect_book_repository().returning(move || {
         let mut mock = MockBookRepository::new();
@@ -39,14 +54,38 @@ async fn show_book_list_with_query_200(
             let items = vec![Book {
                 id: book_id,
                 title: "RustによるWebアプリケーション開発".to_string(),
-                isbn: "".to_string(),
+                isbn: "9784065369579".parse()?,
                 author: "Yuki Toyoda".to_string(),
+                authors: vec![],
                 description: "RustによるWebアプリケーション開発".to_string(),
                 owner: BookOwner {
                     id: UserId::new(),
                     name: "Yuki Toyoda".to_string(),
                 },
-                checkout: None,
+                total_copies: 2,
+                available_copies: 0,
+                checkouts: checkout_ids
+                    .iter()
+                    .map(|&checkout_id| Checkout {
+                        checkout_id,
+                        copy_id: BookCopyId::new(),
+                        barcode: "BK00000001".into(),
+                        checked_out_by: CheckoutUser {
+                            id: UserId::new(),
+                            name: "borrower".into(),
+                        },
+                        checked_out_at: chrono::Utc::now(),
+                    })
+                    .collect(),
+                tags: vec![],
+                cover: None,
+                location: None,
+                status: BookStatus::Available,
+                status_changed_at: None,
+                deleted_at: None,
+                version: 1,
+                average_rating: None,
+                review_count: 0,
             }];
             Ok(PaginatedList {
                 total: 1,
@@ -67,6 +106,10 @@ async fn show_book_list_with_query_200(
     let result = deserialize_json!(resp, PaginatedBookResponse);
     assert_eq!(result.limit, expected_limit);
     assert_eq!(result.offset, expected_offset);
+    // 互換性のため、最初の貸出情報を `checkout` にも含める
+    let book = &result.items[0];
+    assert_eq!(book.checkouts.len(), 2);
+    assert_eq!(book.checkout.as_ref().map(|c| c.id), Some(checkout_ids[0]));
 
     Ok(())
 }
@@ -74,6 +117,13 @@ async fn show_book_list_with_query_200(
 #[rstest]
 #[case("/books?limit=-1")]
 #[case("/books?offset=-1")]
+#[case("/books?sort=isbn")]
+#[case("/books?sort=title&order=up")]
+#[case("/books?order=asc")]
+#[case("/books?q=Rust&order=asc")]
+#[case("/books?availability=lost")]
+#[case("/books?createdFrom=2024-02-01T00:00:00Z&createdTo=2024-01-01T00:00:00Z")]
+#[case("/books?cursor=invalid")]
 #[tokio::test]
 async fn show_book_list_with_query_400(
     fixture: registry::MockAppRegistryExt,
@@ -87,3 +137,1354 @@ async fn show_book_list_with_query_400(
 
     Ok(())
 }
+
+#[rstest]
+#[case("/books?q=Rust", Some("Rust"))]
+#[case("/books?q=%E3%82%BC%E3%83%AD%20Rust%20", Some("ゼロ Rust"))]
+#[case("/books?q=%20", None)]
+#[tokio::test]
+async fn show_book_list_with_keyword_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_query: Option<&'static str>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.query.as_deref() == expected_query)
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?availability=available", BookAvailability::Available)]
+#[case("/books?availability=checked_out", BookAvailability::CheckedOut)]
+#[case("/books?availability=unavailable", BookAvailability::Unavailable)]
+#[tokio::test]
+async fn show_book_list_with_availability_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_availability: BookAvailability,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.availability == Some(expected_availability))
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books", false, false)]
+#[case("/books?deleted=true", true, true)]
+#[tokio::test]
+async fn show_deleted_book_list_only_owned_for_non_admin(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_deleted: bool,
+    #[case] expected_owner_filter: bool,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| {
+                opt.deleted == expected_deleted && opt.owner.is_some() == expected_owner_filter
+            })
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?tags=Rust", &["Rust"])]
+#[case("/books?tags=Rust,%20database%20,", &["Rust", "database"])]
+#[case("/books?tags=", &[])]
+#[tokio::test]
+async fn show_book_list_with_tags_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_tags: &'static [&'static str],
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.tags == expected_tags)
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?sort=title", BookSortKey::Title, SortOrder::Asc)]
+#[case("/books?sort=author&order=desc", BookSortKey::Author, SortOrder::Desc)]
+#[case("/books?sort=created_at", BookSortKey::CreatedAt, SortOrder::Desc)]
+#[case("/books?sort=rating", BookSortKey::Rating, SortOrder::Desc)]
+#[tokio::test]
+async fn show_book_list_with_sort_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_key: BookSortKey,
+    #[case] expected_order: SortOrder,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| {
+                opt.sort
+                    == Some(BookSort {
+                        key: expected_key,
+                        order: expected_order,
+                    })
+            })
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?cursor=", None)]
+#[case("/books?limit=2&cursor=bjo5ODkwNzM2ZWE0ZTQ0NjFhYTc3ZGVhYzM1MTdlZjExYg", Some(Cursor::next("9890736e-a4e4-461a-a77d-eac3517ef11b".parse::<BookId>().unwrap().raw())))]
+#[tokio::test]
+async fn show_book_list_with_cursor_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_cursor: Option<Cursor>,
+) -> anyhow::Result<()> {
+    let next_key = BookId::new().raw();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected_cursor = expected_cursor.clone();
+        mock.expect_find_all_with_cursor()
+            .withf(move |_, cursor| *cursor == expected_cursor)
+            .returning(move |_, _| {
+                Ok(CursorPaginatedList {
+                    items: vec![],
+                    next_cursor: Some(Cursor::next(next_key)),
+                    prev_cursor: None,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, CursorPaginatedBookResponse);
+    assert_eq!(result.next_cursor, Some(Cursor::next(next_key).to_string()));
+    assert!(result.prev_cursor.is_none());
+
+    Ok(())
+}
+
+#[rstest]
+#[case("978-4-7980-6170-2", "9784798061702")]
+#[case("4798061700", "9784798061702")]
+#[tokio::test]
+async fn register_book_201(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+    #[case] expected_isbn: &'static str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .withf(move |event, _| event.isbn.as_str() == expected_isbn)
+            .returning(|_, _| Ok(BookRegistration::Created(BookId::new())));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "author": "初田直也他",
+        "isbn": isbn,
+        "description": "",
+    });
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("9784798061703", "チェックディジット")]
+#[case("4798061701", "チェックディジット")]
+#[case("Test ISBN", "形式")]
+#[tokio::test]
+async fn register_book_with_invalid_isbn_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+    #[case] expected_message: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "author": "初田直也他",
+        "isbn": isbn,
+        "description": "",
+    });
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    let result = deserialize_json!(resp, serde_json::Value);
+    assert!(
+        result["message"]
+            .as_str()
+            .unwrap()
+            .contains(expected_message)
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_duplicated_book_409(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let existing_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .withf(|event, _| event.on_duplicate == DuplicateBookPolicy::Reject)
+            .returning(move |_, _| {
+                Err(AppError::Conflict {
+                    message: "同じ蔵書がすでに登録されています。".into(),
+                    existing_id: existing_id.to_string(),
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "author": "初田直也他",
+        "isbn": "9784798061702",
+    });
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CONFLICT);
+
+    let result = deserialize_json!(resp, serde_json::Value);
+    assert_eq!(result["existingId"], existing_id.to_string());
+
+    Ok(())
+}
+
+#[rstest]
+#[case(serde_json::json!({"addCopy": true}), axum::http::StatusCode::OK)]
+#[case(serde_json::json!({"allowDuplicate": true}), axum::http::StatusCode::CREATED)]
+#[case(
+    serde_json::json!({"addCopy": true, "allowDuplicate": true}),
+    axum::http::StatusCode::BAD_REQUEST
+)]
+#[tokio::test]
+async fn register_book_with_duplicate_flags(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] flags: serde_json::Value,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .returning(move |event, _| match event.on_duplicate {
+                DuplicateBookPolicy::AddCopy => Ok(BookRegistration::CopyAdded(book_id)),
+                _ => Ok(BookRegistration::Created(book_id)),
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let mut body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "author": "初田直也他",
+        "isbn": "9784798061702",
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(flags.as_object().unwrap().clone());
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+
+    if expected_status.is_success() {
+        let result = deserialize_json!(resp, BookRegistrationResponse);
+        assert_eq!(result.id, book_id);
+    }
+
+    Ok(())
+}
+
+#[rstest]
+#[case("978-4-7980-6170-2", axum::http::StatusCode::OK)]
+#[case("9784065369579", axum::http::StatusCode::NOT_FOUND)]
+#[tokio::test]
+async fn lookup_book_metadata(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn().returning(|isbn| {
+            Ok((isbn.as_str() == "9784798061702").then(|| BookMetadata {
+                isbn: isbn.clone(),
+                title: "実践Rustプログラミング入門".into(),
+                author: "初田直也他".into(),
+                description: "".into(),
+            }))
+        });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/lookup?isbn={isbn}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+
+    if expected_status.is_success() {
+        let result = deserialize_json!(resp, BookMetadataResponse);
+        assert_eq!(result.isbn.as_str(), "9784798061702");
+        assert_eq!(result.title, "実践Rustプログラミング入門");
+    }
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_book_with_isbn_only_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn().returning(|isbn| {
+            Ok(Some(BookMetadata {
+                isbn: isbn.clone(),
+                title: "実践Rustプログラミング入門".into(),
+                author: "初田直也他".into(),
+                description: "書誌情報の説明".into(),
+            }))
+        });
+        Arc::new(mock)
+    });
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .withf(|event, _| {
+                event.title == "実践Rustプログラミング入門"
+                    && event.author == "初田直也他"
+                    && event.description == "指定した説明"
+            })
+            .returning(|_, _| Ok(BookRegistration::Created(BookId::new())));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    // 指定した項目は書誌情報で上書きしない
+    let body = serde_json::json!({
+        "isbn": "9784798061702",
+        "description": "指定した説明",
+    });
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_book_with_unknown_isbn_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({ "isbn": "9784798061702" });
+    let req = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(
+    "text/csv",
+    "title,author,isbn,description,copies\nRust Book,Author A,9784798061702,,2\n,Author B,9784065301951,,\nInvalid,Author C,978479806170,,\n",
+    [2, 3, 4]
+)]
+#[case(
+    "application/jsonl",
+    "{\"title\":\"Rust Book\",\"author\":\"Author A\",\"isbn\":\"9784798061702\",\"copies\":2}\n{\"title\":\"\",\"author\":\"Author B\",\"isbn\":\"9784065301951\"}\n\n{\"title\":\"Invalid\",\"author\":\"Author C\",\"isbn\":\"978479806170\"}\n",
+    [1, 2, 4]
+)]
+#[tokio::test]
+async fn import_books_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] content_type: &str,
+    #[case] body: &'static str,
+    #[case] expected_lines: [u64; 3],
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_import()
+            .withf(move |event| {
+                event.books.len() == 1
+                    && event.books[0].line == expected_lines[0]
+                    && event.books[0].book.copies == 2
+                    && !event.atomic
+            })
+            .returning(|event| {
+                Ok(event
+                    .books
+                    .into_iter()
+                    .map(|b| BookImportResult {
+                        line: b.line,
+                        status: BookImportStatus::Created(BookId::new()),
+                    })
+                    .collect())
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/books/import"))
+        .bearer()
+        .header("Content-Type", content_type)
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!(result.created, 1);
+    assert_eq!(result.failed, 2);
+    let lines = result
+        .rows
+        .iter()
+        .map(|r| (r.line, r.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        vec![
+            (expected_lines[0], BookImportRowStatus::Created),
+            (expected_lines[1], BookImportRowStatus::Failed),
+            (expected_lines[2], BookImportRowStatus::Failed),
+        ]
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn import_books_atomic_422(fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    // 不備のある行があれば、リポジトリを呼び出さずに返す。書名が長すぎる行も不備として扱う
+    let app: axum::Router = make_router(fixture);
+
+    let long_title = "x".repeat(256);
+    let req = Request::post(v1("/books/import?atomic=true"))
+        .bearer()
+        .header("Content-Type", "text/csv; charset=utf-8")
+        .body(Body::from(format!(
+            "title,author,isbn\nRust Book,Author A,9784798061702\nInvalid,Author C,978479806170\n{long_title},Author D,9784065301951\n"
+        )))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!(result.created, 0);
+    assert_eq!(result.failed, 2);
+    assert_eq!(result.rows[0].line, 3);
+    assert_eq!(result.rows[1].line, 4);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn import_books_atomic_422_for_failed_row(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    // 登録に失敗した行があれば、リポジトリはすべてを取り消して失敗した行だけを返す
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_import()
+            .withf(|event| event.atomic && event.books.len() == 2)
+            .returning(|_| {
+                Ok(vec![BookImportResult {
+                    line: 3,
+                    status: BookImportStatus::Failed("登録に失敗しました。".into()),
+                }])
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/books/import?atomic=true"))
+        .bearer()
+        .header("Content-Type", "text/csv")
+        .body(Body::from(
+            "title,author,isbn\nRust Book,Author A,9784798061702\nRust Book 2,Author B,9784065301951\n",
+        ))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!(result.created, 0);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.rows[0].line, 3);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("application/json", "[]")]
+#[case("text/csv", "")]
+#[case("text/csv", "title,author,isbn\n")]
+#[tokio::test]
+async fn import_books_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] content_type: &str,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/books/import"))
+        .bearer()
+        .header("Content-Type", content_type)
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(
+    "csv",
+    "text/csv; charset=utf-8",
+    "id,title,author,isbn,description,owner_id,owner_name,total_copies,available_copies,checkouts,tags\n"
+)]
+#[case("jsonl", "application/jsonl", "{\"id\":")]
+#[case(
+    "marcxml",
+    "application/marcxml+xml",
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<collection"
+)]
+#[case("marc", "application/marc", "00")]
+#[tokio::test]
+async fn export_books_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] format: &str,
+    #[case] expected_content_type: &str,
+    #[case] expected_prefix: &str,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_stream_all()
+            .withf(|opt| opt.author.as_deref() == Some("Yuki"))
+            .returning(move |_| {
+                let book = Book {
+                    id: book_id,
+                    title: "RustによるWebアプリケーション開発".to_string(),
+                    isbn: "9784065369579".parse().unwrap(),
+                    author: "Yuki Toyoda".to_string(),
+                    authors: vec![],
+                    description: "".to_string(),
+                    owner: BookOwner {
+                        id: UserId::new(),
+                        name: "Yuki Toyoda".to_string(),
+                    },
+                    total_copies: 1,
+                    available_copies: 1,
+                    checkouts: vec![],
+                    tags: vec![],
+                    cover: None,
+                    location: None,
+                    status: BookStatus::Available,
+                    status_changed_at: None,
+                    deleted_at: None,
+                    version: 1,
+                    average_rating: None,
+                    review_count: 0,
+                };
+                Box::pin(tokio_stream::iter([Ok(book)]))
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/export?format={format}&author=Yuki")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["Content-Type"], expected_content_type);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    assert!(body.starts_with(expected_prefix.as_bytes()));
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains(&book_id.to_string()));
+    assert!(body.contains("9784065369579"));
+
+    Ok(())
+}
+
+#[rstest]
+#[case("csv", "\n#error,")]
+#[case("jsonl", "{\"error\":")]
+#[case("marcxml", "<!-- #error ")]
+#[case("marc", "#error ")]
+#[tokio::test]
+async fn export_books_with_error_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] format: &str,
+    #[case] expected_marker: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_stream_all().returning(move |_| {
+            Box::pin(tokio_stream::iter([Err(AppError::ConversionEntityError(
+                "invalid row".into(),
+            ))]))
+        });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/export?format={format}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    // ヘッダーの送信後に発生したエラーは、末尾の目印で分かるようにする
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains(expected_marker));
+    assert!(!body.contains("invalid row"));
+    assert!(!body.contains("</collection>"));
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn restore_book_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_restore()
+            .withf(move |event| event.book_id == book_id && !event.is_admin)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!("/books/{book_id}/restore")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn purge_book_403_for_non_admin(fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::delete(v1(&format!("/books/{}/purge", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_revisions_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let user_id = UserId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_revisions()
+            .withf(move |id| *id == book_id)
+            .returning(move |book_id| {
+                let fields = |title: &str| BookRevisionFields {
+                    title: title.into(),
+                    author: "".into(),
+                    isbn: "9784798061702".parse().unwrap(),
+                    description: "".into(),
+                };
+                Ok(vec![BookRevision {
+                    id: BookRevisionId::new(),
+                    book_id,
+                    version: 2,
+                    action: BookRevisionAction::Update,
+                    changed_by: BookRevisionUser {
+                        id: user_id,
+                        name: None,
+                    },
+                    old: Some(fields("更新前")),
+                    new: fields("更新後"),
+                    created_at: chrono::Utc::now(),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/revisions")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookRevisionsResponse);
+    assert_eq!(result.items.len(), 1);
+    let revision = &result.items[0];
+    assert_eq!(revision.action, BookRevisionActionResponse::Update);
+    assert_eq!(revision.changed_by.id, user_id);
+    assert_eq!(revision.old.as_ref().unwrap().title, "更新前");
+    assert_eq!(revision.new.title, "更新後");
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_audit_logs_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{}/audit-logs", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_audit_logs_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let owner_id = UserId::new();
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_audit_logs()
+            .withf(move |id| *id == book_id)
+            .returning(move |book_id| {
+                Ok(vec![BookAuditLog {
+                    id: BookAuditLogId::new(),
+                    book_id,
+                    owner_id,
+                    operated_by: BookRevisionUser {
+                        id: current_user_id(),
+                        name: Some("admin".into()),
+                    },
+                    action: BookRevisionAction::Purge,
+                    created_at: chrono::Utc::now(),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/audit-logs")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookAuditLogsResponse);
+    assert_eq!(result.items.len(), 1);
+    let log = &result.items[0];
+    assert_eq!(log.action, BookRevisionActionResponse::Purge);
+    assert_eq!(log.owner_id, owner_id);
+    assert_eq!(log.operated_by.id, current_user_id());
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn purge_book_200(mut fixture_admin: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_purge()
+            .withf(move |event| {
+                event.book_id == book_id && event.requested_user == current_user_id()
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::delete(v1(&format!("/books/{book_id}/purge")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// 所有者でない場合は 403、蔵書がない場合は 404 を返し、管理者かどうかをリポジトリに渡す
+#[rstest]
+#[case(
+    false,
+    Err(AppError::ForbiddenOperation),
+    axum::http::StatusCode::FORBIDDEN
+)]
+#[case(
+    false,
+    Err(AppError::EntityNotFound("specified book not found".into())),
+    axum::http::StatusCode::NOT_FOUND
+)]
+#[case(true, Ok(()), axum::http::StatusCode::CREATED)]
+#[tokio::test]
+async fn add_book_copy_as_non_owner(
+    #[from(fixture)] user_fixture: registry::MockAppRegistryExt,
+    #[from(fixture_admin)] admin_fixture: registry::MockAppRegistryExt,
+    #[case] is_admin: bool,
+    #[case] result: Result<(), AppError>,
+    #[case] expected: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let mut fixture = if is_admin {
+        admin_fixture
+    } else {
+        user_fixture
+    };
+    let book_id = BookId::new();
+    fixture.expect_book_repository().return_once(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_copy()
+            .withf(move |event| event.book_id == book_id && event.is_admin == is_admin)
+            .return_once(move |_| result);
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!("/books/{book_id}/copies")))
+        .bearer()
+        .application_json()
+        .body(Body::from("{}"))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn revert_book_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!(
+        "/books/{}/revisions/{}/revert",
+        BookId::new(),
+        BookRevisionId::new()
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn transfer_book_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let new_owner = UserId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_transfer()
+            .withf(move |event| {
+                event.book_id == book_id && event.new_owner == new_owner && !event.is_admin
+            })
+            .returning(|_| Ok(3));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({ "newOwnerId": new_owner });
+    let req = Request::post(v1(&format!("/books/{book_id}/transfer")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["ETag"], "\"3\"");
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn change_book_status_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_change_status()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.status == BookStatus::InRepair
+                    && event.reason.as_deref() == Some("背表紙の補修")
+            })
+            .returning(|_| Ok(5));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = r#"{"status": "in_repair", "reason": " 背表紙の補修 "}"#;
+    let req = Request::put(v1(&format!("/books/{book_id}/status")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["ETag"], "\"5\"");
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn mark_book_found_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!(
+        "/books/{}/copies/{}/found",
+        BookId::new(),
+        BookCopyId::new()
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_status_history_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let user_id = UserId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_status_history()
+            .withf(move |id| *id == book_id)
+            .returning(move |_| {
+                Ok(vec![BookStatusChange {
+                    from: BookStatus::Available,
+                    to: BookStatus::Lost,
+                    reason: Some("棚にない".into()),
+                    changed_by: BookRevisionUser {
+                        id: user_id,
+                        name: None,
+                    },
+                    changed_at: chrono::Utc::now(),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/status-history")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookStatusHistoryResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].from, BookStatusKind::Available);
+    assert_eq!(result.items[0].to, BookStatusKind::Lost);
+    assert_eq!(result.items[0].changed_by.id, user_id);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn transfer_user_books_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let owner = UserId::new();
+    let new_owner = UserId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_transfer_all()
+            .withf(move |event| event.owner == owner && event.new_owner == new_owner)
+            .returning(|_| Ok(5));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({ "newOwnerId": new_owner });
+    let req = Request::post(v1(&format!("/users/{owner}/books/transfer")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, TransferBooksResponse);
+    assert_eq!(result.transferred, 5);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("", false, axum::http::StatusCode::OK)]
+#[case("?force=true", true, axum::http::StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn delete_book_with_force(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_force: bool,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_delete()
+            .withf(move |event| event.force == expected_force)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::delete(v1(&format!("/books/{}{query}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn delete_checked_out_book_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let message =
+        format!("書籍 ({book_id}) は貸出中のため除籍できません。返却後に除籍してください。");
+    let expected_message = message.clone();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let message = message.clone();
+        mock.expect_delete()
+            .returning(move |_| Err(AppError::UnprocessableEntity(message.clone())));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::delete(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    let result = deserialize_json!(resp, serde_json::Value);
+    assert_eq!(result["message"], expected_message);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(true, None, axum::http::StatusCode::PRECONDITION_REQUIRED)]
+#[case(true, Some("\"2\""), axum::http::StatusCode::PRECONDITION_FAILED)]
+#[case(true, Some("\"1\""), axum::http::StatusCode::OK)]
+#[case(true, Some("*"), axum::http::StatusCode::OK)]
+// 所有者でない場合は、版数を比べる前に 403 を返す
+#[case(false, None, axum::http::StatusCode::FORBIDDEN)]
+#[case(false, Some("\"2\""), axum::http::StatusCode::FORBIDDEN)]
+#[case(false, Some("\"1\""), axum::http::StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn update_book_with_if_match(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] is_owner: bool,
+    #[case] if_match: Option<&str>,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let owner_id = if is_owner {
+        current_user_id()
+    } else {
+        UserId::new()
+    };
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(move |_| {
+            Ok(Some(Book {
+                id: book_id,
+                title: "RustによるWebアプリケーション開発".to_string(),
+                isbn: "9784065369579".parse()?,
+                author: "Yuki Toyoda".to_string(),
+                authors: vec![],
+                description: "".to_string(),
+                owner: BookOwner {
+                    id: owner_id,
+                    name: "Yuki Toyoda".to_string(),
+                },
+                total_copies: 1,
+                available_copies: 1,
+                checkouts: vec![],
+                tags: vec![],
+                cover: None,
+                location: None,
+                status: BookStatus::Available,
+                status_changed_at: None,
+                deleted_at: None,
+                version: 1,
+                average_rating: None,
+                review_count: 0,
+            }))
+        });
+        mock.expect_update()
+            .withf(|event| event.version == 1)
+            .returning(|event| Ok(event.version + 1));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::put(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .application_json();
+    if let Some(if_match) = if_match {
+        req = req.header("If-Match", if_match);
+    }
+    let req = req.body(Body::from(
+        r#"{"title": "Rust", "author": "Yuki Toyoda", "isbn": "9784065369579", "description": ""}"#,
+    ))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+    if expected_status.is_success() {
+        assert_eq!(resp.headers()["ETag"], "\"2\"");
+    }
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"author": "Yuki"}"#, axum::http::StatusCode::OK)]
+#[case(r#"{"description": null}"#, axum::http::StatusCode::OK)]
+#[case(r#"{"title": null}"#, axum::http::StatusCode::BAD_REQUEST)]
+#[case(r#"{"title": ""}"#, axum::http::StatusCode::BAD_REQUEST)]
+#[case(r#"{"isbn": "9784065369570"}"#, axum::http::StatusCode::BAD_REQUEST)]
+#[case(r#"{}"#, axum::http::StatusCode::BAD_REQUEST)]
+#[tokio::test]
+async fn patch_book(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(move |_| {
+            Ok(Some(Book {
+                id: book_id,
+                title: "RustによるWebアプリケーション開発".to_string(),
+                isbn: "9784065369579".parse()?,
+                author: "Yuki Toyoda".to_string(),
+                authors: vec![],
+                description: "RustによるWebアプリケーション開発".to_string(),
+                owner: BookOwner {
+                    id: current_user_id(),
+                    name: "Yuki Toyoda".to_string(),
+                },
+                total_copies: 1,
+                available_copies: 1,
+                checkouts: vec![],
+                tags: vec![],
+                cover: None,
+                location: None,
+                status: BookStatus::Available,
+                status_changed_at: None,
+                deleted_at: None,
+                version: 3,
+                average_rating: None,
+                review_count: 0,
+            }))
+        });
+        // 指定されなかった項目は更新の対象にしない
+        mock.expect_patch()
+            .withf(|event| {
+                event.title.is_none()
+                    && event.isbn.is_none()
+                    && (event.author.as_deref() == Some("Yuki")
+                        || event.description.as_deref() == Some(""))
+            })
+            .returning(|event| Ok(event.version + 1));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::patch(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .header("Content-Type", "application/merge-patch+json")
+        .header("If-Match", "\"3\"")
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+    if expected_status.is_success() {
+        assert_eq!(resp.headers()["ETag"], "\"4\"");
+    }
+
+    Ok(())
+}
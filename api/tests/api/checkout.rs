@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Method, Request},
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::checkout::{BookConditionsResponse, ConditionGrade};
+use kernel::{
+    model::{
+        book::BookStatus,
+        checkout::{BookCondition, BookConditionReport},
+        id::{BookId, CheckoutId, UserId},
+    },
+    repository::{book::MockBookRepository, checkout::MockCheckoutRepository},
+};
+
+#[rstest]
+#[tokio::test]
+async fn return_book_with_condition_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let checkout_id = CheckoutId::new();
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_update_returned()
+            .withf(move |event| {
+                event.checkout_id == checkout_id
+                    && event.condition.as_ref().is_some_and(|c| {
+                        c.condition == BookCondition::Worn
+                            && c.note.as_deref() == Some("角が折れている")
+                    })
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!(
+        "/books/{book_id}/checkouts/{checkout_id}/returned"
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(
+        r#"{"condition": "worn", "note": " 角が折れている "}"#,
+    ))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn return_book_note_without_condition_400(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!(
+        "/books/{}/checkouts/{}/returned",
+        BookId::new(),
+        CheckoutId::new()
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(r#"{"note": "角が折れている"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"replacementCharge": 1500}"#, Some(1500))]
+#[case("", None)]
+#[tokio::test]
+async fn report_lost_book_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected: Option<i64>,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let checkout_id = CheckoutId::new();
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_report_lost()
+            .withf(move |event| {
+                event.checkout_id == checkout_id
+                    && event.book_id == book_id
+                    && event.replacement_charge == expected
+                    && !event.is_admin
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!(
+        "/books/{book_id}/checkouts/{checkout_id}/lost"
+    )))
+    .bearer();
+    let req = if body.is_empty() {
+        req.body(Body::empty())?
+    } else {
+        req.application_json().body(Body::from(body))?
+    };
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn report_lost_book_non_positive_charge_400(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!(
+        "/books/{}/checkouts/{}/lost",
+        BookId::new(),
+        CheckoutId::new()
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(r#"{"replacementCharge": 0}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_condition_history_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let checkout_id = CheckoutId::new();
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_find_condition_history()
+            .withf(move |id| *id == book_id)
+            .returning(move |_| {
+                Ok(vec![BookConditionReport {
+                    checkout_id,
+                    copy_id: None,
+                    reported_by: UserId::new(),
+                    reported_at: chrono::Utc::now(),
+                    condition: BookCondition::Damaged,
+                    note: Some("表紙が破れている".into()),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/conditions")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookConditionsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].checkout_id, checkout_id);
+    assert_eq!(result.items[0].condition, ConditionGrade::Damaged);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Method::PUT, BookStatus::InRepair)]
+#[case(Method::DELETE, BookStatus::Available)]
+#[tokio::test]
+async fn update_book_repair_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] method: Method,
+    #[case] status: BookStatus,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_change_status()
+            .withf(move |event| event.book_id == book_id && event.status == status)
+            .returning(|_| Ok(3));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::builder()
+        .method(method)
+        .uri(v1(&format!("/books/{book_id}/repair")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["ETag"], "\"3\"");
+
+    Ok(())
+}
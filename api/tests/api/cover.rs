@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use chrono::Utc;
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::helper::{TestRequestExt, fixture, fixture_registry, make_router, v1};
+use kernel::{
+    model::{
+        book::{BookCoverImage, BookCoverSize},
+        id::BookId,
+    },
+    repository::book_cover::MockBookCoverRepository,
+};
+
+fn png() -> Vec<u8> {
+    let mut buf = Vec::new();
+    image::RgbImage::new(300, 450)
+        .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
+        .unwrap();
+    buf
+}
+
+#[rstest]
+#[tokio::test]
+async fn upload_book_cover_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_cover_repository().returning(move || {
+        let mut mock = MockBookCoverRepository::new();
+        mock.expect_update()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.content_type == "image/png"
+                    && (event.width, event.height) == (300, 450)
+                    && !event.thumbnail.is_empty()
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!("/books/{book_id}/cover")))
+        .bearer()
+        .header("Content-Type", "image/png")
+        .body(Body::from(png()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("image/gif", png(), axum::http::StatusCode::BAD_REQUEST)]
+#[case("image/jpeg", png(), axum::http::StatusCode::BAD_REQUEST)]
+#[case(
+    "image/png",
+    vec![0; 5 * 1024 * 1024 + 1],
+    axum::http::StatusCode::PAYLOAD_TOO_LARGE
+)]
+#[tokio::test]
+async fn upload_book_cover_rejected(
+    fixture: registry::MockAppRegistryExt,
+    #[case] content_type: &str,
+    #[case] body: Vec<u8>,
+    #[case] expected_status: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!("/books/{}/cover", BookId::new())))
+        .bearer()
+        .header("Content-Type", content_type)
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected_status);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_cover_with_cache_headers(
+    mut fixture_registry: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let updated_at = Utc::now();
+    fixture_registry
+        .expect_book_cover_repository()
+        .returning(move || {
+            let mut mock = MockBookCoverRepository::new();
+            mock.expect_find()
+                .withf(|_, size| *size == BookCoverSize::Thumbnail)
+                .returning(move |_, _| {
+                    Ok(Some(BookCoverImage {
+                        content_type: "image/jpeg".into(),
+                        data: b"jpeg".to_vec(),
+                        updated_at,
+                    }))
+                });
+            Arc::new(mock)
+        });
+
+    // 表紙画像は認証なしで取得できる
+    let app: axum::Router = make_router(fixture_registry);
+    let path = v1(&format!("/books/{}/cover/thumbnail", BookId::new()));
+
+    let req = Request::get(&path).body(Body::empty())?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["Content-Type"], "image/jpeg");
+    assert!(resp.headers().contains_key("Last-Modified"));
+    assert!(resp.headers()["Cache-Control"].to_str()?.contains("public"));
+    let etag = resp.headers()["ETag"].clone();
+
+    let req = Request::get(&path)
+        .header("If-None-Match", etag)
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::NOT_MODIFIED);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    assert!(body.is_empty());
+
+    Ok(())
+}
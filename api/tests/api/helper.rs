@@ -25,13 +25,20 @@ pub fn fixture_registry() -> MockAppRegistryExt {
     MockAppRegistryExt::new()
 }
 
+/// テストでアクセスするユーザーの ID
+pub fn current_user_id() -> UserId {
+    "8f5cb3e8-0e4f-4b8a-9d0e-3c8d2f6a1b7c"
+        .parse()
+        .expect("valid user id")
+}
+
 #[fixture]
 pub fn fixture_auth(mut fixture_registry: MockAppRegistryExt) -> MockAppRegistryExt {
     fixture_registry.expect_auth_repository().returning(|| {
         let mut mock_auth_repository = MockAuthRepository::new();
         mock_auth_repository
             .expect_fetch_user_id_from_token()
-            .returning(|_| Ok(Some(UserId::new())));
+            .returning(|_| Ok(Some(current_user_id())));
         mock_auth_repository
             .expect_verify_user()
             .returning(|_, _| Ok(UserId::new()));
@@ -44,17 +51,29 @@ pub fn fixture_auth(mut fixture_registry: MockAppRegistryExt) -> MockAppRegistry
 }
 
 #[fixture]
-pub fn fixture(mut fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
-    fixture_auth.expect_user_repository().returning(|| {
+pub fn fixture(fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
+    with_current_user(fixture_auth, Role::User)
+}
+
+/// 管理者としてアクセスする場合の fixture
+#[fixture]
+pub fn fixture_admin(fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
+    with_current_user(fixture_auth, Role::Admin)
+}
+
+fn with_current_user(mut fixture_auth: MockAppRegistryExt, role: Role) -> MockAppRegistryExt {
+    // `Role` は複製できないため、モックが呼ばれるたびに作り直す
+    let is_admin = role == Role::Admin;
+    fixture_auth.expect_user_repository().returning(move || {
         let mut mock_user_repository = MockUserRepository::new();
         mock_user_repository
             .expect_find_current_user()
-            .returning(|id| {
+            .returning(move |id| {
                 Ok(Some(User {
                     id,
                     name: "dummy-user".to_string(),
                     email: "dummy@example.com".to_string(),
-                    role: Role::User,
+                    role: if is_admin { Role::Admin } else { Role::User },
                 }))
             });
         Arc::new(mock_user_repository)
@@ -64,7 +83,7 @@ pub fn fixture(mut fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
 
 pub trait TestRequestExt {
     fn bearer(self) -> Builder;
-    //fn application_json(self) -> Builder;
+    fn application_json(self) -> Builder;
 }
 
 impl TestRequestExt for Builder {
@@ -72,9 +91,9 @@ impl TestRequestExt for Builder {
         self.header("Authorization", "Bearer dummy")
     }
 
-    // fn application_json(self) -> Builder {
-    //     self.header("Content-Type", "application/json")
-    // }
+    fn application_json(self) -> Builder {
+        self.header("Content-Type", "application/json")
+    }
 }
 
 // to_bytesなどを使って関数やトレイトに切り出してしまってもよいのだが、
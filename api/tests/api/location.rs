@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::location::{LocationLevel, LocationResponse};
+use kernel::{
+    model::{
+        id::{BookId, CheckoutId, LocationId},
+        location::{Location, LocationKind},
+    },
+    repository::{checkout::MockCheckoutRepository, location::MockLocationRepository},
+};
+
+#[rstest]
+#[tokio::test]
+async fn show_location_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let location_id = LocationId::new();
+    let parent_id = LocationId::new();
+
+    fixture.expect_location_repository().returning(move || {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_find_by_id()
+            .withf(move |id| *id == location_id)
+            .returning(move |id| {
+                Ok(Some(Location {
+                    id,
+                    parent_id: Some(parent_id),
+                    kind: LocationKind::Room,
+                    name: "閲覧室".into(),
+                }))
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/locations/{location_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, LocationResponse);
+    assert_eq!(result.id, location_id);
+    assert_eq!(result.parent_id, Some(parent_id));
+    assert_eq!(result.kind, LocationLevel::Room);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_location_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_find_by_id().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/locations/{}", LocationId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(
+    Request::post(v1("/locations")),
+    r#"{"kind": "branch", "name": "本館"}"#
+)]
+#[case(Request::put(v1(&format!("/locations/{}", LocationId::new()))), r#"{"name": "本館"}"#)]
+#[case(Request::delete(v1(&format!("/locations/{}", LocationId::new()))), "")]
+#[tokio::test]
+async fn manage_location_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+    #[case] req: axum::http::request::Builder,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = req.bearer().application_json().body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn update_book_location_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let shelf_id = LocationId::new();
+
+    fixture.expect_location_repository().returning(move || {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_update_book_location()
+            .withf(move |event| event.book_id == book_id && event.location_id == Some(shelf_id))
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!("/books/{book_id}/location")))
+        .bearer()
+        .application_json()
+        .body(Body::from(format!(r#"{{"locationId": "{shelf_id}"}}"#)))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some(LocationId::new()))]
+#[tokio::test]
+async fn return_book_with_location(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] location_id: Option<LocationId>,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let checkout_id = CheckoutId::new();
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_update_returned()
+            .withf(move |event| {
+                event.checkout_id == checkout_id && event.returned_location == location_id
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    // 返却場所を省略する場合はボディなしで返却できる
+    let req = Request::put(v1(&format!(
+        "/books/{book_id}/checkouts/{checkout_id}/returned"
+    )))
+    .bearer();
+    let req = match location_id {
+        Some(location_id) => req
+            .application_json()
+            .body(Body::from(format!(r#"{{"locationId": "{location_id}"}}"#)))?,
+        None => req.body(Body::empty())?,
+    };
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
@@ -1,2 +1,9 @@
+mod author;
 mod book;
+mod checkout;
+mod cover;
 mod helper;
+mod location;
+mod recommendation;
+mod review;
+mod tag;
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::recommendation::RecommendedBooksResponse;
+use kernel::{
+    model::{id::BookId, recommendation::RecommendedBook},
+    repository::recommendation::MockRecommendationRepository,
+};
+
+fn recommended_book(book_id: BookId, score: i64) -> anyhow::Result<RecommendedBook> {
+    Ok(RecommendedBook {
+        book_id,
+        title: "RustによるWebアプリケーション開発".into(),
+        author: "豊田優貴".into(),
+        isbn: "9784065369579".parse()?,
+        score,
+    })
+}
+
+#[rstest]
+#[case("", 10)]
+#[case("?limit=3", 3)]
+#[tokio::test]
+async fn show_related_books_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_limit: i64,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let related_id = BookId::new();
+
+    fixture
+        .expect_recommendation_repository()
+        .returning(move || {
+            let mut mock = MockRecommendationRepository::new();
+            mock.expect_find_related()
+                .withf(move |id, limit| *id == book_id && *limit == expected_limit)
+                .returning(move |_, _| Ok(vec![recommended_book(related_id, 2).unwrap()]));
+            Arc::new(mock)
+        });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/related{query}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, RecommendedBooksResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].id, related_id);
+    assert_eq!(result.items[0].score, 2);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("?limit=0")]
+#[case("?limit=51")]
+#[tokio::test]
+async fn show_related_books_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{}/related{query}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_my_recommendations_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let recommended_id = BookId::new();
+
+    fixture
+        .expect_recommendation_repository()
+        .returning(move || {
+            let mut mock = MockRecommendationRepository::new();
+            mock.expect_find_for_user()
+                .withf(|_, limit| *limit == 10)
+                .returning(move |_, _| Ok(vec![recommended_book(recommended_id, 3).unwrap()]));
+            Arc::new(mock)
+        });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1("/users/me/recommendations"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, RecommendedBooksResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].id, recommended_id);
+    assert_eq!(result.items[0].score, 3);
+
+    Ok(())
+}
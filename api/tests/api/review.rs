@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::review::{PaginatedReviewResponse, ReviewResponse};
+use kernel::{
+    model::{
+        id::{BookId, ReviewId, UserId},
+        list::PaginatedList,
+        review::Review,
+        user::Reviewer,
+    },
+    repository::review::MockReviewRepository,
+};
+
+#[rstest]
+#[tokio::test]
+async fn register_review_201(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_create()
+            .withf(move |event| event.book_id == book_id && event.rating == 5)
+            .returning(|event| {
+                Ok(Review {
+                    id: ReviewId::new(),
+                    book_id: event.book_id,
+                    reviewer: Reviewer {
+                        id: event.requested_user,
+                        name: "dummy-user".into(),
+                    },
+                    rating: event.rating,
+                    comment: event.comment,
+                    created_at: chrono::Utc::now(),
+                    updated_at: chrono::Utc::now(),
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!("/books/{book_id}/reviews")))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"rating": 5, "comment": "おすすめです"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    let result = deserialize_json!(resp, ReviewResponse);
+    assert_eq!(result.rating, 5);
+    assert_eq!(result.comment, "おすすめです");
+
+    Ok(())
+}
+
+#[rstest]
+#[case("?limit=-1")]
+#[case("?limit=101")]
+#[case("?offset=-1")]
+#[tokio::test]
+async fn show_review_list_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{}/reviews{query}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"rating": 0}"#)]
+#[case(r#"{"rating": 6}"#)]
+#[tokio::test]
+async fn register_review_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1(&format!("/books/{}/reviews", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("", 20, 0)]
+#[case("?limit=5&offset=10", 5, 10)]
+#[tokio::test]
+async fn show_review_list_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_limit: i64,
+    #[case] expected_offset: i64,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_find_by_book_id()
+            .withf(move |id, opt| {
+                *id == book_id && opt.limit == expected_limit && opt.offset == expected_offset
+            })
+            .returning(|book_id, opt| {
+                Ok(PaginatedList {
+                    total: 1,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![Review {
+                        id: ReviewId::new(),
+                        book_id,
+                        reviewer: Reviewer {
+                            id: UserId::new(),
+                            name: "reviewer".into(),
+                        },
+                        rating: 4,
+                        comment: "".into(),
+                        created_at: chrono::Utc::now(),
+                        updated_at: chrono::Utc::now(),
+                    }],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(v1(&format!("/books/{book_id}/reviews{query}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedReviewResponse);
+    assert_eq!(result.total, 1);
+    assert_eq!(result.limit, expected_limit);
+    assert_eq!(result.items[0].reviewer.name, "reviewer");
+
+    Ok(())
+}
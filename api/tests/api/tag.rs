@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+use api::model::tag::TagResponse;
+use kernel::{
+    model::{
+        id::{BookId, TagId},
+        tag::Tag,
+    },
+    repository::tag::MockTagRepository,
+};
+use shared::error::AppError;
+
+#[rstest]
+#[tokio::test]
+async fn register_tag_201(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_tag_repository().returning(|| {
+        let mut mock = MockTagRepository::new();
+        mock.expect_create()
+            .withf(|event| event.name == "Rust")
+            .returning(|event| {
+                Ok(Tag {
+                    id: TagId::new(),
+                    name: event.name,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/tags"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name": " Rust "}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    let result = deserialize_json!(resp, TagResponse);
+    assert_eq!(result.name, "Rust");
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"name": ""}"#)]
+#[case(r#"{"name": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}"#)]
+#[tokio::test]
+async fn register_tag_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(v1("/tags"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Request::put(v1(&format!("/tags/{}", TagId::new()))), r#"{"name": "Rust"}"#.to_string())]
+#[case(
+    Request::post(v1(&format!("/tags/{}/merge", TagId::new()))),
+    format!(r#"{{"targetTagId": "{}"}}"#, TagId::new())
+)]
+#[tokio::test]
+async fn manage_tag_403_for_non_admin(
+    fixture: registry::MockAppRegistryExt,
+    #[case] req: axum::http::request::Builder,
+    #[case] body: String,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = req.bearer().application_json().body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(AppError::ForbiddenOperation, axum::http::StatusCode::FORBIDDEN)]
+#[case(
+    AppError::EntityNotFound("specified book not found".into()),
+    axum::http::StatusCode::NOT_FOUND
+)]
+#[tokio::test]
+async fn attach_book_tag_error(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] error: AppError,
+    #[case] expected: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_tag_repository().return_once(move || {
+        let mut mock = MockTagRepository::new();
+        mock.expect_attach()
+            .withf(move |event| event.book_id == book_id && !event.is_admin)
+            .return_once(move |_| Err(error));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(v1(&format!("/books/{book_id}/tags/{}", TagId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
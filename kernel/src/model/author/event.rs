@@ -0,0 +1,31 @@
+use crate::model::author::AuthorRole;
+use crate::model::id::{AuthorId, BookId, UserId};
+
+#[derive(Debug)]
+pub struct CreateAuthor {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct UpdateAuthor {
+    pub author_id: AuthorId,
+    pub name: String,
+}
+
+/// `source` の著者を `target` の著者に統合する。
+/// `source` が関わっていた蔵書には同じ役割で `target` が関わることになり、`source` は削除される。
+#[derive(Debug)]
+pub struct MergeAuthor {
+    pub source: AuthorId,
+    pub target: AuthorId,
+}
+
+/// 蔵書に関わった著者を、指定した並び順の一覧で置き換える。
+#[derive(Debug)]
+pub struct UpdateBookAuthors {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の著者も置き換えられる
+    pub is_admin: bool,
+    pub authors: Vec<(AuthorId, AuthorRole)>,
+}
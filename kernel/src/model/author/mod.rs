@@ -0,0 +1,46 @@
+use strum::{AsRefStr, EnumString};
+
+use crate::model::id::{AuthorId, BookId};
+
+pub mod event;
+
+/// 蔵書の著者。名前は大文字・小文字を区別せずに一意である。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Author {
+    pub id: AuthorId,
+    pub name: String,
+}
+
+/// 著者の詳細。著者が関わった蔵書を含む。
+#[derive(Debug)]
+pub struct AuthorDetail {
+    pub id: AuthorId,
+    pub name: String,
+    /// 著者が関わった蔵書（書名順）。除籍した蔵書は含まない。
+    pub books: Vec<AuthoredBook>,
+}
+
+/// 著者が関わった蔵書
+#[derive(Debug)]
+pub struct AuthoredBook {
+    pub book_id: BookId,
+    pub title: String,
+    pub role: AuthorRole,
+}
+
+/// 蔵書に関わった著者とその役割
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookAuthor {
+    pub id: AuthorId,
+    pub name: String,
+    pub role: AuthorRole,
+}
+
+/// 蔵書における著者の役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuthorRole {
+    Author,
+    Translator,
+    Editor,
+}
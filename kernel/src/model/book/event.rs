@@ -1,10 +1,17 @@
-use crate::model::id::{BookId, UserId};
+use crate::model::book::{BookStatus, DuplicateBookPolicy};
+use crate::model::id::{BookCopyId, BookId, BookRevisionId, UserId};
+use crate::model::isbn::Isbn;
 
+#[derive(Debug)]
 pub struct CreateBook {
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
+    /// 登録する現物の冊数
+    pub copies: u32,
+    /// 同じ所有者の蔵書に重複するものがある場合の扱い。一括登録では使わず、ISBN が重複する行は常にスキップする。
+    pub on_duplicate: DuplicateBookPolicy,
 }
 
 #[derive(Debug)]
@@ -12,13 +19,159 @@ pub struct UpdateBook {
     pub book_id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
     pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書も更新できる
+    pub is_admin: bool,
+    /// 更新の元にした蔵書の版数。現在の版数と異なる場合は更新しない。
+    pub version: i64,
 }
 
+/// 蔵書の一部の項目の更新。`None` の項目は変更しない。
+#[derive(Debug)]
+pub struct PatchBook {
+    pub book_id: BookId,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub isbn: Option<Isbn>,
+    pub description: Option<String>,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書も更新できる
+    pub is_admin: bool,
+    /// 更新の元にした蔵書の版数。現在の版数と異なる場合は更新しない。
+    pub version: i64,
+}
+
+/// 蔵書の書誌情報を、指定した変更履歴の時点の内容に戻す（管理者のみ）
+#[derive(Debug)]
+pub struct RevertBook {
+    pub book_id: BookId,
+    pub revision_id: BookRevisionId,
+    pub requested_user: UserId,
+}
+
+/// 蔵書の除籍（論理削除）
 #[derive(Debug)]
 pub struct DeleteBook {
     pub book_id: BookId,
     pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書も除籍できる
+    pub is_admin: bool,
+    /// 管理者による強制除籍。貸出中の蔵書も除籍し、貸出中の貸出は返却済みとして記録する。
+    pub force: bool,
+}
+
+/// 除籍した蔵書の復元
+#[derive(Debug)]
+pub struct RestoreBook {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書も復元できる
+    pub is_admin: bool,
+}
+
+/// 蔵書の所有者の譲渡
+#[derive(Debug)]
+pub struct TransferBook {
+    pub book_id: BookId,
+    pub new_owner: UserId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書も譲渡できる
+    pub is_admin: bool,
+}
+
+/// ユーザーが所有するすべての蔵書の譲渡
+#[derive(Debug)]
+pub struct TransferBooks {
+    pub owner: UserId,
+    pub new_owner: UserId,
+    pub requested_user: UserId,
+    /// `true` の場合は他のユーザーの蔵書も譲渡できる
+    pub is_admin: bool,
+}
+
+/// 除籍した蔵書の完全な削除。管理者のみが行える
+#[derive(Debug)]
+pub struct PurgeBook {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+}
+
+/// 蔵書の状態の変更
+#[derive(Debug)]
+pub struct ChangeBookStatus {
+    pub book_id: BookId,
+    pub status: BookStatus,
+    pub reason: Option<String>,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の状態も変更できる
+    pub is_admin: bool,
+}
+
+/// 紛失した現物が見つかったことの記録。現物は再び貸し出せるようになり、
+/// 紛失の状態だった蔵書は貸出可能に戻る
+#[derive(Debug)]
+pub struct MarkBookFound {
+    pub book_id: BookId,
+    pub copy_id: BookCopyId,
+    pub requested_user: UserId,
+}
+
+#[derive(Debug)]
+pub struct CreateBookCopy {
+    pub book_id: BookId,
+    /// 未指定の場合は自動で採番する
+    pub barcode: Option<String>,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書にも現物を追加できる
+    pub is_admin: bool,
+}
+
+#[derive(Debug)]
+pub struct DeleteBookCopy {
+    pub book_id: BookId,
+    pub copy_id: BookCopyId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の現物も削除できる
+    pub is_admin: bool,
+}
+
+/// 蔵書の一括登録
+#[derive(Debug)]
+pub struct ImportBooks {
+    pub books: Vec<ImportBook>,
+    pub requested_user: UserId,
+    /// `true` の場合は 1 件でも登録に失敗したらすべてを取り消す
+    pub atomic: bool,
+}
+
+/// 一括登録する蔵書と、取り込み元データでの行番号
+#[derive(Debug)]
+pub struct ImportBook {
+    pub line: u64,
+    pub book: CreateBook,
+}
+
+/// 表紙画像の登録・差し替え。画像の検証と縮小画像の生成は済んでいるものとする。
+#[derive(Debug)]
+pub struct UpdateBookCover {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の表紙画像も登録できる
+    pub is_admin: bool,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+    pub original: Vec<u8>,
+    /// 縮小した JPEG 画像
+    pub thumbnail: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct DeleteBookCover {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の表紙画像も削除できる
+    pub is_admin: bool,
 }
@@ -1,32 +1,273 @@
 use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
 
-use crate::model::id::{BookId, CheckoutId};
+use crate::model::author::BookAuthor;
+use crate::model::id::{BookCopyId, BookId, CheckoutId, LocationId, UserId};
+use crate::model::isbn::Isbn;
+use crate::model::list::{SortOrder, cursor_scope};
+use crate::model::location::{BookLocation, Location};
+use crate::model::tag::Tag;
 use crate::model::user::BookOwner;
+use revision::BookRevisionUser;
+use shared::error::AppError;
 
 use super::user::CheckoutUser;
 
 pub mod event;
+pub mod revision;
 
 #[derive(Debug)]
 pub struct Book {
     pub id: BookId,
     pub title: String,
+    /// 著者表記。登録時に区切り文字で分割して `authors` に関連付ける。
     pub author: String,
-    pub isbn: String,
+    /// 蔵書に関わった著者（並び順どおり）
+    pub authors: Vec<BookAuthor>,
+    pub isbn: Isbn,
     pub description: String,
     pub owner: BookOwner,
+    /// 所蔵している現物の冊数
+    pub total_copies: i64,
+    /// 貸出中でない現物の冊数
+    pub available_copies: i64,
+    /// 貸出中の現物の貸出情報
+    pub checkouts: Vec<Checkout>,
+    /// 蔵書に付いているタグ（名前順）
+    pub tags: Vec<Tag>,
+    /// 表紙画像。登録されていない場合は `None`
+    pub cover: Option<BookCover>,
+    /// 配架場所。決まっていない場合は `None`
+    pub location: Option<BookLocation>,
+    /// 蔵書の状態。貸し出せるのは `BookStatus::Available` の蔵書のみ
+    pub status: BookStatus,
+    /// 状態を最後に変更した日時。登録後に一度も変更していない場合は `None`
+    pub status_changed_at: Option<DateTime<Utc>>,
+    /// 除籍した日時。除籍されていない場合は `None`
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 蔵書の情報を更新するたびに増える版数。更新の競合の検出に使う。
+    pub version: i64,
+    /// レビューの評価の平均。レビューがない場合は `None`
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+}
+
+/// 蔵書が取得したときの版数から更新されていた場合のエラー
+pub fn outdated_version_error(book_id: BookId) -> AppError {
+    AppError::PreconditionFailed(format!(
+        "書籍 ({book_id}) は他のユーザーによって更新されています。最新の情報を取得してから更新してください。"
+    ))
+}
+
+/// 蔵書の現物（複本）。貸出は現物単位で行う。
+#[derive(Debug)]
+pub struct BookCopy {
+    pub id: BookCopyId,
+    pub book_id: BookId,
+    pub barcode: String,
     pub checkout: Option<Checkout>,
+    /// 配架場所と異なる拠点で返却され、配架場所へ戻す途中の場合の現在の場所
+    pub in_transit_at: Option<Location>,
+    /// 紛失した日時。見つかるまで貸し出さない
+    pub lost_at: Option<DateTime<Utc>>,
+}
+
+/// 蔵書の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum BookStatus {
+    /// 貸出可能
+    Available,
+    /// 修理中
+    InRepair,
+    /// 紛失
+    Lost,
+    /// 貸出停止（所蔵したまま貸出の対象から外す）。
+    /// 除籍（`Book::deleted_at`）とは異なり、一覧や詳細には表示したまま、いつでも貸出可能に戻せる。
+    Suspended,
+}
+
+impl BookStatus {
+    /// 現在の状態から `to` へ変更できるか。紛失した蔵書は、見つかった場合か貸出停止にする場合のみ変更できる。
+    pub fn can_transition_to(&self, to: BookStatus) -> bool {
+        use BookStatus::*;
+        matches!(
+            (self, to),
+            (Available, InRepair | Lost | Suspended)
+                | (InRepair, Available | Lost | Suspended)
+                | (Lost, Available | Suspended)
+                | (Suspended, Available)
+        )
+    }
+}
+
+/// 蔵書の状態の変更履歴
+#[derive(Debug)]
+pub struct BookStatusChange {
+    pub from: BookStatus,
+    pub to: BookStatus,
+    pub reason: Option<String>,
+    pub changed_by: BookRevisionUser,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// 蔵書の表紙画像の情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookCover {
+    /// 元画像の MIME タイプ
+    pub content_type: String,
+    pub updated_at: DateTime<Utc>,
 }
 
+/// 表紙画像の大きさの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookCoverSize {
+    /// アップロードされた画像そのもの
+    Original,
+    /// 一覧表示向けに縮小した JPEG 画像
+    Thumbnail,
+}
+
+/// 配信する表紙画像の内容
 #[derive(Debug)]
+pub struct BookCoverImage {
+    pub content_type: String,
+    pub data: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 外部の書誌情報提供元から取得した、ISBN に対応する書誌情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookMetadata {
+    pub isbn: Isbn,
+    pub title: String,
+    pub author: String,
+    pub description: String,
+}
+
+/// 蔵書の登録時に、同じ所有者の蔵書に重複するもの（ISBN が同じ、または書名と著者がよく似ている）があった場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateBookPolicy {
+    /// 登録せずにエラーとする
+    #[default]
+    Reject,
+    /// 重複を承知のうえで別の蔵書として登録する
+    Allow,
+    /// 重複する蔵書に現物を追加する。重複する蔵書がない場合は新しく登録する。
+    AddCopy,
+}
+
+/// 蔵書の登録結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookRegistration {
+    Created(BookId),
+    /// 重複する既存の蔵書に現物を追加した
+    CopyAdded(BookId),
+}
+
+/// 蔵書の一括登録における、各行の結果
+#[derive(Debug)]
+pub struct BookImportResult {
+    pub line: u64,
+    pub status: BookImportStatus,
+}
+
+#[derive(Debug)]
+pub enum BookImportStatus {
+    Created(BookId),
+    /// 同じ所有者・ISBN の蔵書がすでにあるため登録しなかった
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct BookListOptions {
     pub limit: i64,
     pub offset: i64,
+    /// 書名・著者名・ISBN・説明を対象にしたキーワード検索の文字列
+    pub query: Option<String>,
+    /// 著者名の部分一致
+    pub author: Option<String>,
+    pub owner: Option<UserId>,
+    /// 配架場所。指定した場所の配下（拠点の場合はその拠点のすべての書架）に配架された蔵書に絞り込む。
+    pub location: Option<LocationId>,
+    pub availability: Option<BookAvailability>,
+    /// タグ名（大文字・小文字は区別しない）。指定したすべてのタグが付いた蔵書に絞り込む。
+    pub tags: Vec<String>,
+    /// 登録日時の下限（この日時を含む）
+    pub created_from: Option<DateTime<Utc>>,
+    /// 登録日時の上限（この日時を含まない）
+    pub created_to: Option<DateTime<Utc>>,
+    /// `true` の場合は除籍した蔵書のみ、`false` の場合は除籍されていない蔵書のみを対象にする
+    pub deleted: bool,
+    /// 未指定の場合、キーワード検索時は関連度順、それ以外は登録日時の降順になる。
+    pub sort: Option<BookSort>,
+}
+
+impl BookListOptions {
+    /// カーソルを発行した一覧と同じ並び順・絞り込みの条件かを確かめるための値。
+    /// 件数と開始位置はページごとに変えられるため含めない。
+    pub fn cursor_scope(&self) -> u64 {
+        let Self {
+            limit: _,
+            offset: _,
+            query,
+            author,
+            owner,
+            location,
+            availability,
+            tags,
+            created_from,
+            created_to,
+            deleted,
+            sort,
+        } = self;
+        cursor_scope(&format!(
+            "{query:?}|{author:?}|{owner:?}|{location:?}|{availability:?}|{tags:?}|{created_from:?}|{created_to:?}|{deleted}|{sort:?}"
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookAvailability {
+    /// 貸出可能な現物が 1 冊以上ある
+    Available,
+    /// 蔵書は貸出可能な状態だが、紛失していない現物がすべて貸出中
+    CheckedOut,
+    /// 蔵書が修理中・紛失・貸出停止のため貸し出せない
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSortKey {
+    Title,
+    Author,
+    CreatedAt,
+    /// レビューの評価の平均。レビューのない蔵書は評価が最も低いものとして扱う。
+    Rating,
+}
+
+impl BookSortKey {
+    /// 並び順が指定されなかった場合の並び順
+    pub fn default_order(&self) -> SortOrder {
+        match self {
+            Self::Title | Self::Author => SortOrder::Asc,
+            Self::CreatedAt | Self::Rating => SortOrder::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookSort {
+    pub key: BookSortKey,
+    pub order: SortOrder,
 }
 
 #[derive(Debug)]
 pub struct Checkout {
     pub checkout_id: CheckoutId,
+    pub copy_id: BookCopyId,
+    pub barcode: String,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
 }
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
+
+use crate::model::id::{BookAuditLogId, BookId, BookRevisionId, UserId};
+use crate::model::isbn::Isbn;
+
+/// 蔵書の変更履歴
+#[derive(Debug)]
+pub struct BookRevision {
+    pub id: BookRevisionId,
+    pub book_id: BookId,
+    /// 操作後の蔵書の版数
+    pub version: i64,
+    pub action: BookRevisionAction,
+    pub changed_by: BookRevisionUser,
+    /// 変更前の書誌情報。登録・除籍・復元・譲渡・状態の変更のように書誌情報を変更しない操作では `None`
+    pub old: Option<BookRevisionFields>,
+    /// 操作後の書誌情報
+    pub new: BookRevisionFields,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 変更履歴に記録する蔵書の書誌情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookRevisionFields {
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    pub description: String,
+}
+
+/// 管理者が所有者以外の蔵書を操作した記録
+#[derive(Debug)]
+pub struct BookAuditLog {
+    pub id: BookAuditLogId,
+    pub book_id: BookId,
+    /// 操作した時点の蔵書の所有者
+    pub owner_id: UserId,
+    pub operated_by: BookRevisionUser,
+    pub action: BookRevisionAction,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 蔵書を変更したユーザー。ユーザーが削除されている場合、名前は `None` になる。
+#[derive(Debug)]
+pub struct BookRevisionUser {
+    pub id: UserId,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum BookRevisionAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    /// 過去の変更履歴の内容への差し戻し
+    Revert,
+    /// 所有者の譲渡
+    Transfer,
+    /// 蔵書の状態の変更
+    ChangeStatus,
+    /// 除籍した蔵書の完全な削除。蔵書とともに変更履歴も削除されるため、監査ログにのみ記録する
+    Purge,
+}
@@ -1,4 +1,5 @@
-use crate::model::id::{BookId, CheckoutId, UserId};
+use crate::model::checkout::BookCondition;
+use crate::model::id::{BookId, CheckoutId, LocationId, UserId};
 use chrono::{DateTime, Utc};
 use derive_new::new;
 
@@ -15,4 +16,28 @@ pub struct UpdateReturned {
     pub book_id: BookId,
     pub returned_by: UserId,
     pub returned_at: DateTime<Utc>,
+    /// 返却された場所。配架場所と異なる拠点の場合、現物は配架場所へ戻す途中として扱う。
+    pub returned_location: Option<LocationId>,
+    /// 返却時の蔵書の状態。報告されなかった場合は `None`
+    pub condition: Option<ReportCondition>,
+}
+
+/// 返却時に報告された蔵書の状態
+#[derive(Debug, Clone, new)]
+pub struct ReportCondition {
+    pub condition: BookCondition,
+    pub note: Option<String>,
+}
+
+/// 貸出中の蔵書の紛失の届け出
+#[derive(Debug)]
+pub struct ReportLost {
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は借りたユーザー・蔵書の所有者以外も届け出られる
+    pub is_admin: bool,
+    pub reported_at: DateTime<Utc>,
+    /// 借りたユーザーに求める弁償金の額。蔵書の所有者または管理者のみが記録できる
+    pub replacement_charge: Option<i64>,
 }
@@ -1,20 +1,71 @@
-use crate::model::id::{BookId, CheckoutId, UserId};
+use crate::model::id::{BookCopyId, BookId, CheckoutId, UserId};
+use crate::model::isbn::Isbn;
+use crate::model::list::Cursor;
 use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
 pub mod event;
 
+/// 貸出情報一覧の取得条件。`limit` を指定するとカーソル方式でページングする。
+#[derive(Debug, Default)]
+pub struct CheckoutListOptions {
+    /// 未指定の場合はすべての貸出情報を取得する。
+    pub limit: Option<i64>,
+    pub cursor: Option<Cursor>,
+}
+
 #[derive(Debug)]
 pub struct Checkout {
     pub id: CheckoutId,
+    /// 貸し出した現物。複本管理の導入前に返却された貸出では `None`
+    pub copy_id: Option<BookCopyId>,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
+    /// 貸出を終えた日時。紛失の場合は紛失を届け出た日時
     pub returned_at: Option<DateTime<Utc>>,
+    /// 貸出の終わり方。未返却の場合は `None`
+    pub outcome: Option<CheckoutOutcome>,
+    /// 紛失した場合に記録された弁償金の額
+    pub replacement_charge: Option<i64>,
     pub book: CheckoutBook,
 }
 
+/// 貸出の終わり方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum CheckoutOutcome {
+    Returned,
+    /// 借りたユーザーが紛失した
+    Lost,
+}
+
 #[derive(Debug)]
 pub struct CheckoutBook {
     pub book_id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
+}
+
+/// 返却時に記録する蔵書の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum BookCondition {
+    /// 良好
+    Good,
+    /// 使用感がある
+    Worn,
+    /// 破損している
+    Damaged,
+}
+
+/// 返却時に記録された蔵書の状態
+#[derive(Debug)]
+pub struct BookConditionReport {
+    pub checkout_id: CheckoutId,
+    /// 返却された現物。複本管理の導入前に返却された貸出では `None`
+    pub copy_id: Option<BookCopyId>,
+    pub reported_by: UserId,
+    pub reported_at: DateTime<Utc>,
+    pub condition: BookCondition,
+    pub note: Option<String>,
 }
@@ -64,3 +64,10 @@ macro_rules! define_id {
 define_id!(UserId);
 define_id!(BookId);
 define_id!(CheckoutId);
+define_id!(BookCopyId);
+define_id!(TagId);
+define_id!(BookRevisionId);
+define_id!(BookAuditLogId);
+define_id!(ReviewId);
+define_id!(AuthorId);
+define_id!(LocationId);
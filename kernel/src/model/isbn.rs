@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use shared::error::AppError;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+/// ISBN-13 に正規化された ISBN。
+/// ISBN-10 と ISBN-13 をハイフンや空白の有無に関わらず受け付け、
+/// チェックディジットを検証したうえで、数字のみの ISBN-13 として保持する。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[serde(try_from = "String", into = "String")]
+#[sqlx(transparent)]
+pub struct Isbn(String);
+
+impl Isbn {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_format =
+            || AppError::InvalidIsbn(format!("ISBN の形式が正しくありません: {s}"));
+        let invalid_check_digit =
+            || AppError::InvalidIsbn(format!("ISBN のチェックディジットが正しくありません: {s}"));
+
+        let chars = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .collect::<Vec<_>>();
+        let digits = chars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| match c {
+                // ISBN-10 のチェックディジットに限り 10 を X で表す
+                'X' | 'x' if chars.len() == 10 && i == 9 => Some(10),
+                c => c.to_digit(10),
+            })
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(invalid_format)?;
+
+        match digits.len() {
+            13 => {
+                if !digits.starts_with(&[9, 7, 8]) && !digits.starts_with(&[9, 7, 9]) {
+                    return Err(invalid_format());
+                }
+                if isbn13_check_digit(&digits[..12]) != digits[12] {
+                    return Err(invalid_check_digit());
+                }
+                Ok(Self(to_string(&digits)))
+            }
+            10 => {
+                let sum: u32 = digits.iter().zip((1..=10).rev()).map(|(d, w)| d * w).sum();
+                if !sum.is_multiple_of(11) {
+                    return Err(invalid_check_digit());
+                }
+                let mut isbn13 = [9, 7, 8]
+                    .into_iter()
+                    .chain(digits[..9].iter().copied())
+                    .collect::<Vec<_>>();
+                isbn13.push(isbn13_check_digit(&isbn13));
+                Ok(Self(to_string(&isbn13)))
+            }
+            _ => Err(invalid_format()),
+        }
+    }
+}
+
+fn isbn13_check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .map(|(d, w)| d * w)
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+fn to_string(digits: &[u32]) -> String {
+    digits
+        .iter()
+        .filter_map(|d| char::from_digit(*d, 10))
+        .collect()
+}
+
+impl TryFrom<String> for Isbn {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for Isbn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Isbn> for String {
+    fn from(isbn: Isbn) -> Self {
+        isbn.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_isbn() {
+        for (input, expected) in [
+            ("9784798061702", "9784798061702"),
+            ("978-4798061702", "9784798061702"),
+            ("978-4-7980-6170-2", "9784798061702"),
+            ("4798061700", "9784798061702"),
+            ("4-7980-6170-0", "9784798061702"),
+            ("080442957X", "9780804429573"),
+            ("0 8044 2957 x", "9780804429573"),
+        ] {
+            assert_eq!(input.parse::<Isbn>().unwrap().as_str(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_isbn() {
+        for input in [
+            "",
+            "Test ISBN",
+            "978479806170",
+            "9784798061703",
+            "4798061701",
+            "1234567890123",
+            "97847980617X2",
+        ] {
+            assert!(input.parse::<Isbn>().is_err(), "{input}");
+        }
+    }
+}
@@ -1,3 +1,8 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use shared::error::AppError;
+use std::str::FromStr;
+
 #[derive(Debug)]
 pub struct PaginatedList<T> {
     pub total: i64,
@@ -11,3 +16,236 @@ impl<T> PaginatedList<T> {
         self.items
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// カーソル方式のページングの結果。件数の集計は行わない。
+#[derive(Debug)]
+pub struct CursorPaginatedList<T> {
+    pub items: Vec<T>,
+    /// 次のページを取得するためのカーソル。次のページがない場合は `None`
+    pub next_cursor: Option<Cursor>,
+    /// 前のページを取得するためのカーソル。前のページがない場合は `None`
+    pub prev_cursor: Option<Cursor>,
+}
+
+impl<T> CursorPaginatedList<T> {
+    /// `limit + 1` 件まで取得した `items` から、ページの内容と前後のカーソルを組み立てる。
+    /// `items` は `cursor` の向きに関わらず表示順に並んでいること。
+    /// `to_cursor` は要素と向きから、その要素を指すカーソルを作る。
+    pub fn from_items(
+        mut items: Vec<T>,
+        limit: i64,
+        cursor: Option<&Cursor>,
+        to_cursor: impl Fn(&T, CursorDirection) -> Cursor,
+    ) -> Self {
+        let limit = usize::try_from(limit).unwrap_or_default();
+        let has_more = items.len() > limit;
+        let direction = cursor.map(|c| c.direction).unwrap_or(CursorDirection::Next);
+        if has_more {
+            match direction {
+                CursorDirection::Next => {
+                    items.truncate(limit);
+                }
+                CursorDirection::Prev => {
+                    items.drain(..items.len() - limit);
+                }
+            }
+        }
+
+        let (has_next, has_prev) = match direction {
+            CursorDirection::Next => (has_more, cursor.is_some()),
+            CursorDirection::Prev => (true, has_more),
+        };
+        let next_cursor = items
+            .last()
+            .filter(|_| has_next)
+            .map(|item| to_cursor(item, CursorDirection::Next));
+        let prev_cursor = items
+            .first()
+            .filter(|_| has_prev)
+            .map(|item| to_cursor(item, CursorDirection::Prev));
+
+        Self {
+            items,
+            next_cursor,
+            prev_cursor,
+        }
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// カーソルの位置より後ろを取得する
+    Next,
+    /// カーソルの位置より前を取得する
+    Prev,
+}
+
+/// カーソル方式のページングで、ページの境界となる要素の ID と取得する向きを表す。
+/// 文字列表現は内容を解釈されないよう URL-safe な Base64 でエンコードする。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: uuid::Uuid,
+    pub direction: CursorDirection,
+    /// 並び替えや絞り込みのできる一覧で、カーソルが指す要素の位置。並び順が固定の一覧では `None`
+    pub position: Option<CursorPosition>,
+}
+
+/// 並び替えや絞り込みのできる一覧で、カーソルが指す要素の位置を表す。
+/// 要素が削除されても位置を特定できるよう、並び替えの基準の値そのものを持つ。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorPosition {
+    /// 並び替えの基準の値の文字列表現
+    pub sort_value: String,
+    /// カーソルを発行した一覧の並び順と絞り込みの条件から求めた値（`cursor_scope` を参照）
+    pub scope: u64,
+}
+
+impl Cursor {
+    pub fn new(key: uuid::Uuid, direction: CursorDirection) -> Self {
+        Self {
+            key,
+            direction,
+            position: None,
+        }
+    }
+
+    pub fn next(key: uuid::Uuid) -> Self {
+        Self::new(key, CursorDirection::Next)
+    }
+
+    pub fn prev(key: uuid::Uuid) -> Self {
+        Self::new(key, CursorDirection::Prev)
+    }
+
+    pub fn with_position(self, sort_value: String, scope: u64) -> Self {
+        Self {
+            position: Some(CursorPosition { sort_value, scope }),
+            ..self
+        }
+    }
+}
+
+/// 一覧の並び順と絞り込みの条件を表す文字列から、カーソルに含める値を求める。
+/// 条件の異なる一覧にカーソルが使われたことを検出するためのもので、
+/// サーバーを再起動しても同じ値になるよう FNV-1a で求める。
+pub fn cursor_scope(conditions: &str) -> u64 {
+    conditions.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = match self.direction {
+            CursorDirection::Next => "n",
+            CursorDirection::Prev => "p",
+        };
+        let raw = match &self.position {
+            None => format!("{direction}:{}", self.key.simple()),
+            // 並び替えの基準の値には区切り文字が含まれうるため、最後に置く
+            Some(CursorPosition { sort_value, scope }) => {
+                format!(
+                    "{direction}:{}:{scope:016x}:{sort_value}",
+                    self.key.simple()
+                )
+            }
+        };
+        write!(f, "{}", URL_SAFE_NO_PAD.encode(raw))
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError::BadRequest("不正なカーソルが指定されました。".into());
+        let raw = URL_SAFE_NO_PAD.decode(s).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let mut parts = raw.splitn(4, ':');
+        let direction = match parts.next() {
+            Some("n") => CursorDirection::Next,
+            Some("p") => CursorDirection::Prev,
+            _ => return Err(invalid()),
+        };
+        let key = parts
+            .next()
+            .and_then(|key| uuid::Uuid::parse_str(key).ok())
+            .ok_or_else(invalid)?;
+        let position = match (parts.next(), parts.next()) {
+            (None, None) => None,
+            (Some(scope), Some(sort_value)) => Some(CursorPosition {
+                sort_value: sort_value.into(),
+                scope: u64::from_str_radix(scope, 16).map_err(|_| invalid())?,
+            }),
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            key,
+            direction,
+            position,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::prev(uuid::Uuid::new_v4());
+        let encoded = cursor.to_string();
+        assert_eq!(encoded.parse::<Cursor>().unwrap(), cursor);
+        assert!("invalid".parse::<Cursor>().is_err());
+
+        // 並び替えの基準の値に区切り文字が含まれていても復元できる
+        let cursor = Cursor::next(uuid::Uuid::new_v4()).with_position(
+            "2026-10-18 12:34:56.789+00".into(),
+            cursor_scope("title:asc"),
+        );
+        let encoded = cursor.to_string();
+        assert_eq!(encoded.parse::<Cursor>().unwrap(), cursor);
+        assert_ne!(cursor_scope("title:asc"), cursor_scope("title:desc"));
+    }
+
+    #[test]
+    fn test_from_items() {
+        let ids = (0..4).map(|_| uuid::Uuid::new_v4()).collect::<Vec<_>>();
+
+        // 最初のページ
+        let list = CursorPaginatedList::from_items(ids[..3].to_vec(), 2, None, |id, d| {
+            Cursor::new(*id, d)
+        });
+        assert_eq!(list.items, ids[..2]);
+        assert_eq!(list.next_cursor, Some(Cursor::next(ids[1])));
+        assert_eq!(list.prev_cursor, None);
+
+        // 最後のページ
+        let cursor = Cursor::next(ids[1]);
+        let list = CursorPaginatedList::from_items(ids[2..].to_vec(), 2, Some(&cursor), |id, d| {
+            Cursor::new(*id, d)
+        });
+        assert_eq!(list.items, ids[2..]);
+        assert_eq!(list.next_cursor, None);
+        assert_eq!(list.prev_cursor, Some(Cursor::prev(ids[2])));
+
+        // 前のページに戻る
+        let cursor = Cursor::prev(ids[3]);
+        let list = CursorPaginatedList::from_items(ids[..3].to_vec(), 2, Some(&cursor), |id, d| {
+            Cursor::new(*id, d)
+        });
+        assert_eq!(list.items, ids[1..3]);
+        assert_eq!(list.next_cursor, Some(Cursor::next(ids[2])));
+        assert_eq!(list.prev_cursor, Some(Cursor::prev(ids[1])));
+    }
+}
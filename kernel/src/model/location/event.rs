@@ -0,0 +1,35 @@
+use crate::model::id::{BookCopyId, BookId, LocationId, UserId};
+use crate::model::location::LocationKind;
+
+#[derive(Debug)]
+pub struct CreateLocation {
+    pub parent_id: Option<LocationId>,
+    pub kind: LocationKind,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct UpdateLocation {
+    pub location_id: LocationId,
+    pub name: String,
+}
+
+/// 蔵書の配架場所を変更する。`location_id` が `None` の場合は配架場所を解除する。
+#[derive(Debug)]
+pub struct UpdateBookLocation {
+    pub book_id: BookId,
+    pub location_id: Option<LocationId>,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の配架場所も変更できる
+    pub is_admin: bool,
+}
+
+/// 配架場所へ戻す途中の現物を、配架場所に戻したことを記録する。
+#[derive(Debug)]
+pub struct ShelveBookCopy {
+    pub book_id: BookId,
+    pub copy_id: BookCopyId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書の現物も配架場所に戻せる
+    pub is_admin: bool,
+}
@@ -0,0 +1,43 @@
+use strum::{AsRefStr, EnumString};
+
+use crate::model::id::LocationId;
+
+pub mod event;
+
+/// 蔵書を配架する場所。拠点 → 部屋 → 書架 の階層になっている。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub id: LocationId,
+    /// 親の場所。拠点の場合は `None`
+    pub parent_id: Option<LocationId>,
+    pub kind: LocationKind,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum LocationKind {
+    Branch,
+    Room,
+    Shelf,
+}
+
+impl LocationKind {
+    /// 親の場所の種類。拠点は親を持たない。
+    pub fn parent_kind(&self) -> Option<LocationKind> {
+        match self {
+            LocationKind::Branch => None,
+            LocationKind::Room => Some(LocationKind::Branch),
+            LocationKind::Shelf => Some(LocationKind::Room),
+        }
+    }
+}
+
+/// 蔵書の配架場所（書架）と、書架が属する部屋・拠点の名前
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookLocation {
+    pub id: LocationId,
+    pub branch: String,
+    pub room: String,
+    pub shelf: String,
+}
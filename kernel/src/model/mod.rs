@@ -1,7 +1,13 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
 pub mod id;
+pub mod isbn;
 pub mod list;
+pub mod location;
+pub mod recommendation;
+pub mod review;
 pub mod role;
+pub mod tag;
 pub mod user;
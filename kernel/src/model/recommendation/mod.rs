@@ -0,0 +1,13 @@
+use crate::model::id::BookId;
+use crate::model::isbn::Isbn;
+
+/// 貸出履歴をもとに推薦する蔵書
+#[derive(Debug)]
+pub struct RecommendedBook {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    /// 推薦の強さ。一緒に借りたユーザーの数をもとにした値で、大きいほど関連が強い
+    pub score: i64,
+}
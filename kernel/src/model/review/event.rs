@@ -0,0 +1,27 @@
+use crate::model::id::{BookId, ReviewId, UserId};
+
+#[derive(Debug)]
+pub struct CreateReview {
+    pub book_id: BookId,
+    pub rating: i16,
+    pub comment: String,
+    pub requested_user: UserId,
+}
+
+#[derive(Debug)]
+pub struct UpdateReview {
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub rating: i16,
+    pub comment: String,
+    pub requested_user: UserId,
+}
+
+#[derive(Debug)]
+pub struct DeleteReview {
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub requested_user: UserId,
+    /// `true` の場合は他のユーザーのレビューも削除できる
+    pub is_admin: bool,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::id::{BookId, ReviewId};
+use crate::model::user::Reviewer;
+
+pub mod event;
+
+/// 蔵書のレビュー。1 人のユーザーが 1 冊の蔵書に書けるレビューは 1 件まで。
+#[derive(Debug)]
+pub struct Review {
+    pub id: ReviewId,
+    pub book_id: BookId,
+    pub reviewer: Reviewer,
+    /// 1 から 5 までの評価
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReviewListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
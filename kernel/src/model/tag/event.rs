@@ -0,0 +1,38 @@
+use crate::model::id::{BookId, TagId, UserId};
+
+#[derive(Debug)]
+pub struct CreateTag {
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct UpdateTag {
+    pub tag_id: TagId,
+    pub name: String,
+}
+
+/// `source` のタグを `target` のタグに統合する。
+/// `source` が付いていた蔵書には `target` が付き、`source` は削除される。
+#[derive(Debug)]
+pub struct MergeTag {
+    pub source: TagId,
+    pub target: TagId,
+}
+
+#[derive(Debug)]
+pub struct AttachTag {
+    pub book_id: BookId,
+    pub tag_id: TagId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書にもタグを付けられる
+    pub is_admin: bool,
+}
+
+#[derive(Debug)]
+pub struct DetachTag {
+    pub book_id: BookId,
+    pub tag_id: TagId,
+    pub requested_user: UserId,
+    /// `true` の場合は所有者以外の蔵書からもタグを外せる
+    pub is_admin: bool,
+}
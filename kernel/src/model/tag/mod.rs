@@ -0,0 +1,10 @@
+use crate::model::id::TagId;
+
+pub mod event;
+
+/// 蔵書を分類するためのタグ。名前は大文字・小文字を区別せずに一意である。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub id: TagId,
+    pub name: String,
+}
@@ -7,6 +7,14 @@ pub struct CreateUser {
     pub password: String,
 }
 
+/// ユーザー情報の一部の項目の更新。`None` の項目は変更しない。
+#[derive(Debug)]
+pub struct UpdateUser {
+    pub user_id: UserId,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct UpdateUserRole {
     pub user_id: UserId,
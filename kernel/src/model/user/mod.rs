@@ -21,3 +21,9 @@ pub struct CheckoutUser {
     pub id: UserId,
     pub name: String,
 }
+
+#[derive(Debug)]
+pub struct Reviewer {
+    pub id: UserId,
+    pub name: String,
+}
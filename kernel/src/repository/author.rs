@@ -0,0 +1,20 @@
+use crate::model::author::event::{CreateAuthor, MergeAuthor, UpdateAuthor, UpdateBookAuthors};
+use crate::model::author::{Author, AuthorDetail};
+use crate::model::id::AuthorId;
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait AuthorRepository: Send + Sync {
+    /// 著者を作成する。同じ名前（大文字・小文字は区別しない）の著者がいる場合はエラーとなる。
+    async fn create(&self, event: CreateAuthor) -> AppResult<Author>;
+    /// すべての著者を名前順に取得する。
+    async fn find_all(&self) -> AppResult<Vec<Author>>;
+    /// 著者と、著者が関わった蔵書を取得する。
+    async fn find_by_id(&self, author_id: AuthorId) -> AppResult<Option<AuthorDetail>>;
+    async fn update(&self, event: UpdateAuthor) -> AppResult<()>;
+    async fn merge(&self, event: MergeAuthor) -> AppResult<()>;
+    /// 蔵書に関わった著者を置き換える。蔵書の所有者のみが変更できる。
+    async fn update_book_authors(&self, event: UpdateBookAuthors) -> AppResult<()>;
+}
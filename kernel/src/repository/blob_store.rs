@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+/// 画像などのバイナリデータを、キーを指定して保存する先。
+/// キーは `/` 区切りのパスの形式とし、`.` や `..` の要素は含まない。
+#[mockall::automock]
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// データを保存する。同じキーのデータがある場合は置き換える。
+    async fn put(&self, key: &str, data: Vec<u8>) -> AppResult<()>;
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+    /// データを削除する。キーに対応するデータがない場合は何もしない。
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
@@ -1,17 +1,83 @@
-use crate::model::book::event::{DeleteBook, UpdateBook};
-use crate::model::book::{Book, BookListOptions, event::CreateBook};
+use crate::model::book::event::{
+    ChangeBookStatus, CreateBookCopy, DeleteBook, DeleteBookCopy, ImportBooks, MarkBookFound,
+    PatchBook, PurgeBook, RestoreBook, RevertBook, TransferBook, TransferBooks, UpdateBook,
+};
+use crate::model::book::revision::{BookAuditLog, BookRevision};
+use crate::model::book::{
+    Book, BookCopy, BookImportResult, BookListOptions, BookRegistration, BookStatusChange,
+    event::CreateBook,
+};
 use crate::model::id::{BookId, UserId};
-use crate::model::list::PaginatedList;
+use crate::model::list::{Cursor, CursorPaginatedList, PaginatedList};
 use async_trait::async_trait;
 use shared::error::AppResult;
+use std::pin::Pin;
+use tokio_stream::Stream;
+
+/// 蔵書を 1 件ずつ取得するストリーム
+pub type BookStream = Pin<Box<dyn Stream<Item = AppResult<Book>> + Send>>;
 
 #[mockall::automock]
 #[async_trait]
 pub trait BookRepository: Send + Sync {
-    async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()>;
+    /// 蔵書を登録する。重複する蔵書がある場合の扱いは `CreateBook::on_duplicate` に従い、
+    /// `DuplicateBookPolicy::Reject` の場合は既存の蔵書の ID を含む `AppError::Conflict` を返す。
+    async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<BookRegistration>;
+    /// 蔵書を一括登録する。同じ所有者・ISBN の蔵書がすでにある行は登録せずにスキップする。
+    /// `ImportBooks::atomic` が `true` で登録に失敗した行があった場合は何も登録せず、失敗した行の結果だけを返す。
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>>;
     async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>>;
+    /// カーソル方式のページングで蔵書一覧を取得する。`options` の `offset` は使用しない。
+    async fn find_all_with_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<Cursor>,
+    ) -> AppResult<CursorPaginatedList<Book>>;
+    /// 条件に一致する蔵書を、貸出状況を含めて一覧と同じ順に 1 件ずつ取得する。
+    /// `options` の `limit` と `offset` は使用しない。
+    fn stream_all(&self, options: BookListOptions) -> BookStream;
+    /// 蔵書を取得する。除籍した蔵書は取得しない。
     async fn find_by_id(&self, book_id: BookId) -> AppResult<Option<Book>>;
 
-    async fn update(&self, event: UpdateBook) -> AppResult<()>;
+    /// 蔵書の情報を更新し、更新後の版数を返す。
+    /// 指定した版数から変更されている場合は `AppError::PreconditionFailed` を返す。
+    /// 所有者以外による更新は、管理者の場合を除いて `AppError::ForbiddenOperation` を返す。
+    /// 管理者が所有者以外の蔵書を操作した場合は、監査ログに記録する（除籍・復元も同様）。
+    async fn update(&self, event: UpdateBook) -> AppResult<i64>;
+    /// 指定された項目のみを更新し、更新後の版数を返す。版数の扱いは `update` と同じ。
+    async fn patch(&self, event: PatchBook) -> AppResult<i64>;
+    /// 蔵書を除籍する。除籍した蔵書は一覧や検索の対象外になるが、貸出履歴とともに残る。
+    /// 貸出中の現物がある蔵書は、強制除籍の場合を除いて除籍できない。
     async fn delete(&self, event: DeleteBook) -> AppResult<()>;
+    /// 除籍した蔵書を復元する。
+    async fn restore(&self, event: RestoreBook) -> AppResult<()>;
+    /// 蔵書の変更履歴を新しい順に取得する。
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>>;
+    /// 管理者が所有者以外の蔵書を操作した記録を新しい順に取得する。完全に削除した蔵書の記録も取得できる。
+    async fn find_audit_logs(&self, book_id: BookId) -> AppResult<Vec<BookAuditLog>>;
+    /// 蔵書の書誌情報を、指定した変更履歴の時点の内容に戻し、更新後の版数を返す。
+    async fn revert(&self, event: RevertBook) -> AppResult<i64>;
+    /// 蔵書の所有者を変更し、更新後の版数を返す。貸出中の貸出はそのまま引き継ぐ。
+    async fn transfer(&self, event: TransferBook) -> AppResult<i64>;
+    /// ユーザーが所有するすべての蔵書を、除籍した蔵書も含めて譲渡し、譲渡した冊数を返す。
+    async fn transfer_all(&self, event: TransferBooks) -> AppResult<u64>;
+    /// 除籍した蔵書を、貸出履歴・変更履歴・譲渡履歴を含めて完全に削除する。貸出中の現物がある蔵書は削除できない。
+    async fn purge(&self, event: PurgeBook) -> AppResult<()>;
+
+    /// 蔵書の状態を変更し、更新後の版数を返す。`BookStatus::can_transition_to` で許可されない変更や、
+    /// 貸出中の現物がある蔵書を貸出可能以外の状態にする変更は `AppError::UnprocessableEntity` を返す。
+    /// 権限の扱いは `update` と同じ。
+    async fn change_status(&self, event: ChangeBookStatus) -> AppResult<i64>;
+    /// 紛失した現物を貸し出せる状態に戻し、更新後の版数を返す。管理者のみが行える。
+    /// 紛失の状態だった蔵書は貸出可能に戻る。現物が紛失していない場合は `AppError::UnprocessableEntity` を返す。
+    async fn mark_found(&self, event: MarkBookFound) -> AppResult<i64>;
+    /// 蔵書の状態の変更履歴を新しい順に取得する。蔵書が存在しない、または除籍されている場合は `AppError::EntityNotFound` を返す。
+    async fn find_status_history(&self, book_id: BookId) -> AppResult<Vec<BookStatusChange>>;
+
+    /// 蔵書の現物を、貸出状況を含めて取得する。蔵書が存在しない、または除籍されている場合は `AppError::EntityNotFound` を返す。
+    async fn find_copies(&self, book_id: BookId) -> AppResult<Vec<BookCopy>>;
+    /// 蔵書に現物を追加する。蔵書の所有者のみが追加できる。
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<()>;
+    /// 蔵書の現物を削除する。貸出中の現物と、最後の 1 冊は削除できない。
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()>;
 }
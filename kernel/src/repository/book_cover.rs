@@ -0,0 +1,16 @@
+use crate::model::book::event::{DeleteBookCover, UpdateBookCover};
+use crate::model::book::{BookCoverImage, BookCoverSize};
+use crate::model::id::BookId;
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait BookCoverRepository: Send + Sync {
+    /// 表紙画像を登録する。すでに登録されている場合は差し替える。蔵書の所有者のみが登録できる。
+    async fn update(&self, event: UpdateBookCover) -> AppResult<()>;
+    async fn find(&self, book_id: BookId, size: BookCoverSize)
+    -> AppResult<Option<BookCoverImage>>;
+    /// 表紙画像を削除する。蔵書の所有者のみが削除できる。
+    async fn delete(&self, event: DeleteBookCover) -> AppResult<()>;
+}
@@ -0,0 +1,12 @@
+use crate::model::book::BookMetadata;
+use crate::model::isbn::Isbn;
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+/// ISBN から書誌情報を取得する提供元。
+#[mockall::automock]
+#[async_trait]
+pub trait BookMetadataProvider: Send + Sync {
+    /// 書誌情報が見つからない場合は `None` を返す。
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>>;
+}
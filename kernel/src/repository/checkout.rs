@@ -1,6 +1,7 @@
-use crate::model::checkout::Checkout;
-use crate::model::checkout::event::{CreateCheckout, UpdateReturned};
+use crate::model::checkout::event::{CreateCheckout, ReportLost, UpdateReturned};
+use crate::model::checkout::{BookConditionReport, Checkout, CheckoutListOptions};
 use crate::model::id::{BookId, UserId};
+use crate::model::list::CursorPaginatedList;
 use async_trait::async_trait;
 use shared::error::AppResult;
 
@@ -10,15 +11,34 @@ pub trait CheckoutRepository: Send + Sync {
     /// 貸出操作を行う。
     async fn create(&self, event: CreateCheckout) -> AppResult<()>;
 
-    /// 返却操作を行う。
+    /// 返却操作を行う。返却時の蔵書の状態が報告された場合は、返却の記録とともに保存する。
     async fn update_returned(&self, event: UpdateReturned) -> AppResult<()>;
 
+    /// 貸出中の蔵書の紛失を届け出る。貸出は紛失として終了し、貸し出した現物を紛失として記録する。
+    /// 貸し出せる現物が残っていない場合は、蔵書を紛失の状態にする。
+    /// 届け出られるのは借りたユーザー・蔵書の所有者・管理者のみ。
+    async fn report_lost(&self, event: ReportLost) -> AppResult<()>;
+
     /// すべての未返却の貸し出し情報を取得する。
-    async fn find_unreturned_all(&self) -> AppResult<Vec<Checkout>>;
+    async fn find_unreturned_all(
+        &self,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
 
     /// ユーザーIDに紐づく未返却の貸出情報を取得する。
-    async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>>;
+    async fn find_unreturned_by_user_id(
+        &self,
+        user_id: UserId,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
 
     /// 蔵書の貸出履歴（返却済みも含む）を取得する。
-    async fn find_history_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>>;
+    async fn find_history_by_book_id(
+        &self,
+        book_id: BookId,
+        options: CheckoutListOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
+
+    /// 蔵書の返却時に記録された状態を、返却日時の新しい順に取得する。
+    async fn find_condition_history(&self, book_id: BookId) -> AppResult<Vec<BookConditionReport>>;
 }
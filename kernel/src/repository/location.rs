@@ -0,0 +1,24 @@
+use crate::model::id::LocationId;
+use crate::model::location::Location;
+use crate::model::location::event::{
+    CreateLocation, ShelveBookCopy, UpdateBookLocation, UpdateLocation,
+};
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait LocationRepository: Send + Sync {
+    /// 場所を作成する。親の場所の種類が階層に合わない場合はエラーとなる。
+    async fn create(&self, event: CreateLocation) -> AppResult<Location>;
+    /// すべての場所を、拠点ごと・部屋ごとにまとまるよう階層順に取得する。
+    async fn find_all(&self) -> AppResult<Vec<Location>>;
+    async fn find_by_id(&self, location_id: LocationId) -> AppResult<Option<Location>>;
+    async fn update(&self, event: UpdateLocation) -> AppResult<()>;
+    /// 場所を削除する。配下の場所や配架された蔵書がある場合はエラーとなる。
+    async fn delete(&self, location_id: LocationId) -> AppResult<()>;
+    /// 蔵書の配架場所を変更する。蔵書の所有者のみが変更でき、配架場所には書架のみを指定できる。
+    async fn update_book_location(&self, event: UpdateBookLocation) -> AppResult<()>;
+    /// 配架場所へ戻す途中の現物を配架済みにする。蔵書の所有者のみが記録できる。
+    async fn shelve_copy(&self, event: ShelveBookCopy) -> AppResult<()>;
+}
@@ -0,0 +1,17 @@
+use crate::model::id::{BookId, UserId};
+use crate::model::recommendation::RecommendedBook;
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait RecommendationRepository: Send + Sync {
+    /// 貸出履歴から、蔵書ごとに一緒に借りられた蔵書の集計を作り直す。
+    async fn refresh(&self) -> AppResult<()>;
+    /// 指定の蔵書を借りたユーザーが、ほかに借りた蔵書を関連の強い順に取得する。
+    /// 削除済み・紛失中・貸出停止中の蔵書は含めない。
+    async fn find_related(&self, book_id: BookId, limit: i64) -> AppResult<Vec<RecommendedBook>>;
+    /// ユーザーの貸出履歴をもとに、おすすめの蔵書を取得する。
+    /// ユーザーが借りたことのある蔵書と、ユーザー自身が所有する蔵書は含めない。
+    async fn find_for_user(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RecommendedBook>>;
+}
@@ -0,0 +1,24 @@
+use crate::model::id::BookId;
+use crate::model::list::PaginatedList;
+use crate::model::review::event::{CreateReview, DeleteReview, UpdateReview};
+use crate::model::review::{Review, ReviewListOptions};
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait ReviewRepository: Send + Sync {
+    /// 蔵書のレビューを登録する。返却済みの貸出がある蔵書のみレビューでき、
+    /// すでにレビューを書いた蔵書にはもう一度書けない。
+    async fn create(&self, event: CreateReview) -> AppResult<Review>;
+    /// 蔵書のレビューを新しい順に取得する。
+    async fn find_by_book_id(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<Review>>;
+    /// レビューを更新する。レビューを書いたユーザーのみが更新できる。
+    async fn update(&self, event: UpdateReview) -> AppResult<()>;
+    /// レビューを削除する。レビューを書いたユーザーと管理者のみが削除できる。
+    async fn delete(&self, event: DeleteReview) -> AppResult<()>;
+}
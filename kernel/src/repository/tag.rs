@@ -0,0 +1,21 @@
+use crate::model::id::TagId;
+use crate::model::tag::Tag;
+use crate::model::tag::event::{AttachTag, CreateTag, DetachTag, MergeTag, UpdateTag};
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// タグを作成する。同じ名前（大文字・小文字は区別しない）のタグがある場合はエラーとなる。
+    async fn create(&self, event: CreateTag) -> AppResult<Tag>;
+    /// すべてのタグを名前順に取得する。
+    async fn find_all(&self) -> AppResult<Vec<Tag>>;
+    async fn find_by_id(&self, tag_id: TagId) -> AppResult<Option<Tag>>;
+    async fn update(&self, event: UpdateTag) -> AppResult<()>;
+    async fn merge(&self, event: MergeTag) -> AppResult<()>;
+    /// 蔵書にタグを付ける。蔵書の所有者のみが付けられる。すでに付いている場合は何もしない。
+    async fn attach(&self, event: AttachTag) -> AppResult<()>;
+    /// 蔵書からタグを外す。蔵書の所有者のみが外せる。
+    async fn detach(&self, event: DetachTag) -> AppResult<()>;
+}
@@ -1,6 +1,8 @@
 use crate::model::id::UserId;
 use crate::model::user::User;
-use crate::model::user::event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole};
+use crate::model::user::event::{
+    CreateUser, DeleteUser, UpdateUser, UpdateUserPassword, UpdateUserRole,
+};
 use async_trait::async_trait;
 use shared::error::AppResult;
 
@@ -10,6 +12,8 @@ pub trait UserRepository: Send + Sync {
     async fn find_current_user(&self, current_user_id: UserId) -> AppResult<Option<User>>;
     async fn find_all(&self) -> AppResult<Vec<User>>;
     async fn create(&self, event: CreateUser) -> AppResult<User>;
+    /// 名前・メールアドレスのうち指定された項目を更新し、更新後のユーザーを返す。
+    async fn update(&self, event: UpdateUser) -> AppResult<User>;
     async fn update_password(&self, event: UpdateUserPassword) -> AppResult<()>;
     async fn update_role(&self, event: UpdateUserRole) -> AppResult<()>;
     async fn delete(&self, event: DeleteUser) -> AppResult<()>;
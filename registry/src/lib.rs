@@ -1,16 +1,35 @@
 use adapter::database::ConnectionPool;
 use adapter::redis::RedisClient;
 use adapter::repository::auth::AuthRepositoryImpl;
+use adapter::repository::author::AuthorRepositoryImpl;
+use adapter::repository::blob_store::LocalFsBlobStore;
 use adapter::repository::book::BookRepositoryImpl;
+use adapter::repository::book_cover::BookCoverRepositoryImpl;
+use adapter::repository::book_metadata::{
+    JsonFileBookMetadataProvider, OpenBdBookMetadataProvider,
+};
 use adapter::repository::checkout::CheckoutRepositoryImpl;
 use adapter::repository::health::HealthCheckRepositoryImpl;
+use adapter::repository::location::LocationRepositoryImpl;
+use adapter::repository::recommendation::RecommendationRepositoryImpl;
+use adapter::repository::review::ReviewRepositoryImpl;
+use adapter::repository::tag::TagRepositoryImpl;
 use adapter::repository::user::UserRepositoryImpl;
 use kernel::repository::auth::AuthRepository;
+use kernel::repository::author::AuthorRepository;
+use kernel::repository::blob_store::BlobStore;
 use kernel::repository::book::BookRepository;
+use kernel::repository::book_cover::BookCoverRepository;
+use kernel::repository::book_metadata::BookMetadataProvider;
 use kernel::repository::checkout::CheckoutRepository;
 use kernel::repository::health::HealthCheckRepository;
+use kernel::repository::location::LocationRepository;
+use kernel::repository::recommendation::RecommendationRepository;
+use kernel::repository::review::ReviewRepository;
+use kernel::repository::tag::TagRepository;
 use kernel::repository::user::UserRepository;
-use shared::config::AppConfig;
+use shared::config::{AppConfig, BlobStoreConfig, BookMetadataConfig};
+use shared::error::AppResult;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -21,6 +40,13 @@ pub struct AppRegistryImpl {
     auth_repository: Arc<dyn AuthRepository>,
     user_repository: Arc<dyn UserRepository>,
     checkout_repository: Arc<dyn CheckoutRepository>,
+    book_metadata_provider: Arc<dyn BookMetadataProvider>,
+    tag_repository: Arc<dyn TagRepository>,
+    book_cover_repository: Arc<dyn BookCoverRepository>,
+    review_repository: Arc<dyn ReviewRepository>,
+    author_repository: Arc<dyn AuthorRepository>,
+    location_repository: Arc<dyn LocationRepository>,
+    recommendation_repository: Arc<dyn RecommendationRepository>,
 }
 
 impl AppRegistryImpl {
@@ -28,9 +54,8 @@ impl AppRegistryImpl {
         pool: ConnectionPool,
         redis_client: Arc<RedisClient>,
         app_config: AppConfig,
-    ) -> Self {
+    ) -> AppResult<Self> {
         let health_check_repository = Arc::new(HealthCheckRepositoryImpl::new(pool.clone()));
-        let book_repository = Arc::new(BookRepositoryImpl::new(pool.clone()));
         let auth_repository = Arc::new(AuthRepositoryImpl::new(
             pool.clone(),
             redis_client.clone(),
@@ -38,14 +63,40 @@ impl AppRegistryImpl {
         ));
         let user_repository = Arc::new(UserRepositoryImpl::new(pool.clone()));
         let checkout_repository = Arc::new(CheckoutRepositoryImpl::new(pool.clone()));
+        let book_metadata_provider: Arc<dyn BookMetadataProvider> = match app_config.book_metadata {
+            BookMetadataConfig::OpenBd { endpoint } => {
+                Arc::new(OpenBdBookMetadataProvider::new(endpoint)?)
+            }
+            BookMetadataConfig::File { path } => {
+                Arc::new(JsonFileBookMetadataProvider::from_path(path)?)
+            }
+        };
+        let tag_repository = Arc::new(TagRepositoryImpl::new(pool.clone()));
+        let blob_store: Arc<dyn BlobStore> = match app_config.blob_store {
+            BlobStoreConfig::Local { root } => Arc::new(LocalFsBlobStore::new(root)),
+        };
+        let book_repository = Arc::new(BookRepositoryImpl::new(pool.clone(), blob_store.clone()));
+        let book_cover_repository =
+            Arc::new(BookCoverRepositoryImpl::new(pool.clone(), blob_store));
+        let review_repository = Arc::new(ReviewRepositoryImpl::new(pool.clone()));
+        let author_repository = Arc::new(AuthorRepositoryImpl::new(pool.clone()));
+        let location_repository = Arc::new(LocationRepositoryImpl::new(pool.clone()));
+        let recommendation_repository = Arc::new(RecommendationRepositoryImpl::new(pool.clone()));
 
-        Self {
+        Ok(Self {
             health_check_repository,
             book_repository,
             auth_repository,
             user_repository,
             checkout_repository,
-        }
+            book_metadata_provider,
+            tag_repository,
+            book_cover_repository,
+            review_repository,
+            author_repository,
+            location_repository,
+            recommendation_repository,
+        })
     }
 }
 
@@ -56,6 +107,13 @@ pub trait AppRegistryExt {
     fn auth_repository(&self) -> Arc<dyn AuthRepository>;
     fn checkout_repository(&self) -> Arc<dyn CheckoutRepository>;
     fn user_repository(&self) -> Arc<dyn UserRepository>;
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider>;
+    fn tag_repository(&self) -> Arc<dyn TagRepository>;
+    fn book_cover_repository(&self) -> Arc<dyn BookCoverRepository>;
+    fn review_repository(&self) -> Arc<dyn ReviewRepository>;
+    fn author_repository(&self) -> Arc<dyn AuthorRepository>;
+    fn location_repository(&self) -> Arc<dyn LocationRepository>;
+    fn recommendation_repository(&self) -> Arc<dyn RecommendationRepository>;
 }
 
 impl AppRegistryExt for AppRegistryImpl {
@@ -78,6 +136,34 @@ impl AppRegistryExt for AppRegistryImpl {
     fn user_repository(&self) -> Arc<dyn UserRepository> {
         self.user_repository.clone()
     }
+
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider> {
+        self.book_metadata_provider.clone()
+    }
+
+    fn tag_repository(&self) -> Arc<dyn TagRepository> {
+        self.tag_repository.clone()
+    }
+
+    fn book_cover_repository(&self) -> Arc<dyn BookCoverRepository> {
+        self.book_cover_repository.clone()
+    }
+
+    fn review_repository(&self) -> Arc<dyn ReviewRepository> {
+        self.review_repository.clone()
+    }
+
+    fn author_repository(&self) -> Arc<dyn AuthorRepository> {
+        self.author_repository.clone()
+    }
+
+    fn location_repository(&self) -> Arc<dyn LocationRepository> {
+        self.location_repository.clone()
+    }
+
+    fn recommendation_repository(&self) -> Arc<dyn RecommendationRepository> {
+        self.recommendation_repository.clone()
+    }
 }
 
 #[derive(Clone)]
@@ -4,6 +4,9 @@ pub struct AppConfig {
     pub database: DatabaseConfig,
     pub redis: RedisConfig,
     pub auth: AuthConfig,
+    pub book_metadata: BookMetadataConfig,
+    pub blob_store: BlobStoreConfig,
+    pub recommendation: RecommendationConfig,
 }
 
 impl AppConfig {
@@ -25,10 +28,42 @@ impl AppConfig {
             ttl: std::env::var("AUTH_TOKEN_TTL")?.parse::<u64>()?,
         };
 
+        let book_metadata = match std::env::var("BOOK_METADATA_PROVIDER").as_deref() {
+            Ok("file") => BookMetadataConfig::File {
+                path: std::env::var("BOOK_METADATA_FILE")?,
+            },
+            Ok("openbd") | Err(_) => BookMetadataConfig::OpenBd {
+                endpoint: std::env::var("BOOK_METADATA_ENDPOINT")
+                    .unwrap_or_else(|_| DEFAULT_OPENBD_ENDPOINT.into()),
+            },
+            Ok(other) => anyhow::bail!("unknown BOOK_METADATA_PROVIDER: {other}"),
+        };
+
+        let blob_store = match std::env::var("BLOB_STORE").as_deref() {
+            Ok("local") | Err(_) => BlobStoreConfig::Local {
+                root: std::env::var("BLOB_STORE_PATH")
+                    .unwrap_or_else(|_| DEFAULT_BLOB_STORE_PATH.into()),
+            },
+            Ok(other) => anyhow::bail!("unknown BLOB_STORE: {other}"),
+        };
+
+        let refresh_interval = match std::env::var("RECOMMENDATION_REFRESH_INTERVAL") {
+            Ok(v) => v.parse::<u64>()?,
+            Err(_) => DEFAULT_RECOMMENDATION_REFRESH_INTERVAL,
+        };
+        anyhow::ensure!(
+            refresh_interval > 0,
+            "RECOMMENDATION_REFRESH_INTERVAL must be greater than 0"
+        );
+        let recommendation = RecommendationConfig { refresh_interval };
+
         Ok(Self {
             database,
             redis,
             auth,
+            book_metadata,
+            blob_store,
+            recommendation,
         })
     }
 }
@@ -49,3 +84,29 @@ pub struct RedisConfig {
 pub struct AuthConfig {
     pub ttl: u64,
 }
+
+const DEFAULT_OPENBD_ENDPOINT: &str = "https://api.openbd.jp/v1/get";
+
+/// ISBN から書誌情報を取得する提供元の設定
+pub enum BookMetadataConfig {
+    /// openBD の API から取得する
+    OpenBd { endpoint: String },
+    /// ローカルの JSON ファイルから取得する（オフライン環境やテスト向け）
+    File { path: String },
+}
+
+const DEFAULT_BLOB_STORE_PATH: &str = "blobs";
+
+/// 表紙画像などのバイナリデータの保存先の設定
+pub enum BlobStoreConfig {
+    /// ローカルのファイルシステムの、指定したディレクトリ以下に保存する
+    Local { root: String },
+}
+
+const DEFAULT_RECOMMENDATION_REFRESH_INTERVAL: u64 = 60 * 60;
+
+/// 貸出履歴から作る推薦の集計の設定
+pub struct RecommendationConfig {
+    /// 集計を作り直す間隔（秒）
+    pub refresh_interval: u64,
+}
@@ -1,12 +1,28 @@
-use axum::{http::StatusCode, response::IntoResponse};
+use axum::{Json, http::StatusCode, response::IntoResponse};
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum AppError {
+    #[error("{0}")]
+    BadRequest(String),
+    /// ISBN の形式やチェックディジットが正しくない場合のエラー。原因をレスポンスのメッセージで返す
+    #[error("{0}")]
+    InvalidIsbn(String),
     #[error("{0}")]
     UnprocessableEntity(String),
     #[error("{0}")]
     EntityNotFound(String),
+    /// 既存のリソースと重複する場合のエラー。`existing_id` は重複した既存のリソースの ID
+    #[error("{message}")]
+    Conflict {
+        message: String,
+        existing_id: String,
+    },
+    #[error("{0}")]
+    PreconditionFailed(String),
+    #[error("{0}")]
+    PreconditionRequired(String),
     #[error("{0}")]
     ValidationError(#[from] garde::Report),
     #[error("トランザクションを実行できませんでした。")]
@@ -18,6 +34,8 @@ pub enum AppError {
     #[error("{0}")]
     KeyValueStoreError(#[from] redis::RedisError),
     #[error("{0}")]
+    BlobStoreError(String),
+    #[error("{0}")]
     BcryptError(#[from] bcrypt::BcryptError),
     #[error("{0}")]
     ConvertToUuidError(#[from] uuid::Error),
@@ -29,22 +47,36 @@ pub enum AppError {
     ForbiddenOperation,
     #[error("{0}")]
     ConversionEntityError(String),
+    #[error("{0}")]
+    ExternalServiceError(String),
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        let status_code = match self {
+        let status_code = match &self {
             AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::EntityNotFound(_) => StatusCode::NOT_FOUND,
-            AppError::ValidationError(_) | AppError::ConvertToUuidError(_) => {
-                StatusCode::BAD_REQUEST
-            }
+            AppError::Conflict { .. } => StatusCode::CONFLICT,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            AppError::BadRequest(_)
+            | AppError::InvalidIsbn(_)
+            | AppError::ValidationError(_)
+            | AppError::ConvertToUuidError(_) => StatusCode::BAD_REQUEST,
             AppError::UnauthenticatedError | AppError::ForbiddenOperation => StatusCode::FORBIDDEN,
             AppError::UnauthorizedError => StatusCode::UNAUTHORIZED,
+            e @ AppError::ExternalServiceError(_) => {
+                tracing::error!(
+                    error.message = %e,
+                    "External service error happened"
+                );
+                StatusCode::BAD_GATEWAY
+            }
             e @ (AppError::TransactionError(_)
             | AppError::SpecificOperationError(_)
             | AppError::NoRowsAffectedError(_)
             | AppError::KeyValueStoreError(_)
+            | AppError::BlobStoreError(_)
             | AppError::BcryptError(_)
             | AppError::ConversionEntityError(_)) => {
                 tracing::error! (
@@ -55,8 +87,35 @@ impl IntoResponse for AppError {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
         };
-        status_code.into_response()
+        // クライアント側で原因や既存のリソースが分かるよう、利用者向けのメッセージを持つエラーは本文で返す
+        let body = match self {
+            AppError::BadRequest(message)
+            | AppError::InvalidIsbn(message)
+            | AppError::UnprocessableEntity(message)
+            | AppError::PreconditionFailed(message)
+            | AppError::PreconditionRequired(message) => ErrorResponse {
+                message,
+                existing_id: None,
+            },
+            AppError::Conflict {
+                message,
+                existing_id,
+            } => ErrorResponse {
+                message,
+                existing_id: Some(existing_id),
+            },
+            _ => return status_code.into_response(),
+        };
+        (status_code, Json(body)).into_response()
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    existing_id: Option<String>,
+}
+
 pub type AppResult<T> = Result<T, AppError>;
@@ -14,6 +14,7 @@ use shared::config::AppConfig;
 use shared::env::{Environment, which};
 use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer};
@@ -93,7 +94,9 @@ async fn bootstrap(tracer_provider: SdkTracerProvider) -> Result<()> {
     let app_config = AppConfig::new()?;
     let pool = connect_database_with(&app_config.database);
     let kv = Arc::new(RedisClient::new(&app_config.redis)?);
-    let registry = AppRegistry(Arc::new(AppRegistryImpl::new(pool, kv, app_config)));
+    let refresh_interval = Duration::from_secs(app_config.recommendation.refresh_interval);
+    let registry = AppRegistry(Arc::new(AppRegistryImpl::new(pool, kv, app_config)?));
+    spawn_recommendation_refresher(registry.clone(), refresh_interval);
     let router = Router::new().merge(v1::routes()).merge(auth::routes());
     #[cfg(debug_assertions)]
     let router = router.merge(Redoc::with_url("/docs", ApiDoc::openapi()));
@@ -127,6 +130,24 @@ async fn bootstrap(tracer_provider: SdkTracerProvider) -> Result<()> {
         })
 }
 
+/// 推薦の集計を一定間隔で作り直す。起動直後にも一度作り直す。
+fn spawn_recommendation_refresher(registry: AppRegistry, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = registry.recommendation_repository().refresh().await {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    error.message = %e,
+                    "Failed to refresh recommendations"
+                );
+            }
+        }
+    });
+}
+
 async fn shutdown_signal(tracer_provider: SdkTracerProvider) {
     fn purge_spans(tracer_provider: &SdkTracerProvider) {
         tracer_provider